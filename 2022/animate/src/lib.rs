@@ -0,0 +1,161 @@
+//! Watching the simulation puzzles run, frame by frame, in the terminal.
+//!
+//! A simulation takes a [`Hook`] and hands it a [`Frame`] at each step. The
+//! frame is only drawn when something is watching, so solving normally, with
+//! [`Hook::none`], costs nothing. Days with something worth watching implement
+//! [`Animate`], and the [`Player`] shows their frames with controls to pause,
+//! step and change speed, scrolling to keep the frame's focus in view.
+
+use anyhow::Result;
+use common::{Answer, Part, Solution};
+use std::fmt::Display;
+use std::ops::ControlFlow;
+
+mod player;
+
+pub use player::{Controls, Key, Options, Player, Viewport};
+
+/// A picture of a simulation at one step.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    /// The picture, one row of characters at a time.
+    pub rows: Vec<Vec<char>>,
+    /// The `(column, row)` to keep in view when the picture doesn't fit.
+    pub focus: Option<(usize, usize)>,
+    /// A line of text to show under the picture, like a step count.
+    pub caption: String,
+}
+
+impl Frame {
+    /// A frame showing something drawn with `Display`, a line per row.
+    pub fn new(picture: impl Display) -> Self {
+        let rows = picture
+            .to_string()
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+        Frame {
+            rows,
+            ..Frame::default()
+        }
+    }
+
+    /// Draw `glyph` over the picture at `(column, row)`, if that's inside it.
+    pub fn plot(mut self, (col, row): (usize, usize), glyph: char) -> Self {
+        if let Some(cell) = self.rows.get_mut(row).and_then(|row| row.get_mut(col)) {
+            *cell = glyph;
+        }
+        self
+    }
+
+    pub fn focus(mut self, (col, row): (usize, usize)) -> Self {
+        self.focus = Some((col, row));
+        self
+    }
+
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// The width of the widest row.
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+}
+
+/// Where a simulation sends its frames. Whatever is watching can stop at any
+/// frame by returning `ControlFlow::Break`, and the simulation then carries
+/// on to its answer without drawing any more.
+#[derive(Default)]
+pub struct Hook<'a> {
+    watcher: Option<&'a mut dyn FnMut(Frame) -> ControlFlow<()>>,
+}
+
+impl<'a> Hook<'a> {
+    /// A hook nobody is watching, for solving without drawing anything.
+    pub fn none() -> Self {
+        Hook { watcher: None }
+    }
+
+    pub fn new(watcher: &'a mut dyn FnMut(Frame) -> ControlFlow<()>) -> Self {
+        Hook {
+            watcher: Some(watcher),
+        }
+    }
+
+    /// Send a frame to the watcher, drawing it with `draw` only if there is
+    /// one.
+    pub fn frame(&mut self, draw: impl FnOnce() -> Frame) {
+        if let Some(watcher) = &mut self.watcher {
+            if watcher(draw()).is_break() {
+                self.watcher = None;
+            }
+        }
+    }
+}
+
+/// A solution with a simulation to watch.
+pub trait Animate: Solution {
+    /// Solve one part of the puzzle, sending a frame to `hook` at each step
+    /// of the simulation. Parts without a simulation fail.
+    fn animate(&self, input: &Self::Input, part: Part, hook: &mut Hook) -> Result<Answer>;
+}
+
+/// An object-safe view of an `Animate`, so that the runner can keep the days
+/// side by side, like `DynSolution`.
+pub trait DynAnimate {
+    /// Parse `input` with [`common::parse_tidied`] and watch a single part
+    /// of the puzzle solve it.
+    fn animate(&self, input: &str, part: Part, hook: &mut Hook) -> Result<Answer>;
+}
+
+impl<S: Animate> DynAnimate for S {
+    fn animate(&self, input: &str, part: Part, hook: &mut Hook) -> Result<Answer> {
+        let input = common::parse_tidied(self, input)?;
+        Animate::animate(self, &input, part, hook)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_plot_over_the_picture() {
+        let frame = Frame::new("...\n.#.\n")
+            .plot((2, 0), 'o')
+            .plot((5, 5), 'x')
+            .focus((2, 0));
+        assert_eq!(frame.rows, vec![vec!['.', '.', 'o'], vec!['.', '#', '.']]);
+        assert_eq!(frame.width(), 3);
+        assert_eq!(frame.focus, Some((2, 0)));
+    }
+
+    #[test]
+    fn hooks_only_draw_while_watched() {
+        let mut drawn = 0;
+        let mut hook = Hook::none();
+        hook.frame(|| {
+            drawn += 1;
+            Frame::default()
+        });
+        assert_eq!(drawn, 0);
+
+        // A watcher that has seen enough after two frames
+        let mut seen = Vec::new();
+        let mut watcher = |frame: Frame| {
+            seen.push(frame.caption);
+            if seen.len() == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+        let mut hook = Hook::new(&mut watcher);
+        for step in 0..5 {
+            hook.frame(|| Frame::default().caption(format!("step {step}")));
+        }
+        assert_eq!(seen, ["step 0", "step 1"]);
+    }
+}
@@ -0,0 +1,346 @@
+use anyhow::{bail, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, terminal};
+use std::io::{self, IsTerminal, Stdout, Write};
+use std::mem;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+use crate::Frame;
+
+/// The slowest and fastest the player goes, in frames per second.
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 100_000.0;
+
+/// The most frames a second the terminal is asked to draw. Faster than this,
+/// frames go by without being drawn.
+const DRAWS_PER_SECOND: f64 = 60.0;
+
+const HELP: &str = "space play/pause, . step, +/- speed, f follow, q quit";
+
+/// What a key press asks the player to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    PlayPause,
+    /// Pause, and show just the next frame.
+    Step,
+    Faster,
+    Slower,
+    /// Turn scrolling to keep the frame's focus in view on or off.
+    Follow,
+    Quit,
+}
+
+impl Key {
+    fn from_event(key: KeyEvent) -> Option<Key> {
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+        Some(match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Key::Quit,
+            KeyCode::Char(' ' | 'p') => Key::PlayPause,
+            KeyCode::Char('.') | KeyCode::Right => Key::Step,
+            KeyCode::Char('+' | '=') | KeyCode::Up => Key::Faster,
+            KeyCode::Char('-') | KeyCode::Down => Key::Slower,
+            KeyCode::Char('f') => Key::Follow,
+            KeyCode::Char('q') | KeyCode::Esc => Key::Quit,
+            _ => return None,
+        })
+    }
+}
+
+/// How the player starts out.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub fps: f64,
+    pub paused: bool,
+}
+
+/// The state of the player's controls, as the keys pressed so far left it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Controls {
+    pub paused: bool,
+    pub fps: f64,
+    pub follow: bool,
+    pub quit: bool,
+    /// Whether to show the next frame, though paused.
+    step: bool,
+}
+
+impl Controls {
+    pub fn new(options: &Options) -> Self {
+        Controls {
+            paused: options.paused,
+            fps: options.fps.clamp(MIN_FPS, MAX_FPS),
+            follow: true,
+            quit: false,
+            step: false,
+        }
+    }
+
+    pub fn press(&mut self, key: Key) {
+        match key {
+            Key::PlayPause => self.paused = !self.paused,
+            Key::Step => {
+                self.paused = true;
+                self.step = true;
+            }
+            Key::Faster => self.fps = (self.fps * 2.0).min(MAX_FPS),
+            Key::Slower => self.fps = (self.fps / 2.0).max(MIN_FPS),
+            Key::Follow => self.follow = !self.follow,
+            Key::Quit => self.quit = true,
+        }
+    }
+
+    /// Whether a step to the next frame was asked for, which it uses up.
+    pub fn take_step(&mut self) -> bool {
+        mem::take(&mut self.step)
+    }
+
+    /// How many frames go by for each one drawn: every one while paused, and
+    /// only as many as the terminal can keep up with while playing.
+    pub fn skip(&self) -> usize {
+        if self.paused {
+            1
+        } else {
+            (self.fps / DRAWS_PER_SECOND).ceil().max(1.0) as usize
+        }
+    }
+
+    /// How long a drawn frame stays up while playing.
+    pub fn delay(&self) -> Duration {
+        Duration::from_secs_f64(self.skip() as f64 / self.fps)
+    }
+}
+
+/// The part of a frame on screen, as the column and row of its top left
+/// corner and its size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Viewport {
+    pub col: usize,
+    pub row: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    /// Scroll just far enough to keep `focus` a quarter of the view away
+    /// from the edges, without scrolling past a picture of `size`.
+    pub fn follow(&mut self, focus: (usize, usize), size: (usize, usize)) {
+        self.col = scroll(self.col, self.width, focus.0, size.0);
+        self.row = scroll(self.row, self.height, focus.1, size.1);
+    }
+
+    /// Stay put, unless the view would go past the edge of a picture of
+    /// `size`.
+    pub fn clamp(&mut self, size: (usize, usize)) {
+        self.col = self.col.min(size.0.saturating_sub(self.width));
+        self.row = self.row.min(size.1.saturating_sub(self.height));
+    }
+}
+
+/// Where a view of `len` should start along one axis to keep `focus` in it.
+fn scroll(start: usize, len: usize, focus: usize, total: usize) -> usize {
+    let margin = len / 4;
+    let start = if focus < start + margin {
+        focus.saturating_sub(margin)
+    } else if focus + margin >= start + len {
+        focus + margin + 1 - len
+    } else {
+        start
+    };
+    start.min(total.saturating_sub(len))
+}
+
+/// Plays frames in the terminal as they arrive, taking over the screen until
+/// it's dropped.
+pub struct Player {
+    out: Stdout,
+    controls: Controls,
+    viewport: Viewport,
+    frames: usize,
+    shown_at: Instant,
+}
+
+impl Player {
+    pub fn start(options: Options) -> Result<Player> {
+        let mut out = io::stdout();
+        if !out.is_terminal() {
+            bail!("Animations need a terminal to play in");
+        }
+        terminal::enable_raw_mode()?;
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Player {
+            out,
+            controls: Controls::new(&options),
+            viewport: Viewport::default(),
+            frames: 0,
+            shown_at: Instant::now(),
+        })
+    }
+
+    /// How many frames have gone by.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Show a frame until it's time for the next one, handling the keys
+    /// pressed meanwhile. Breaks once the viewer quits, or the terminal
+    /// can't be drawn in any more.
+    pub fn show(&mut self, frame: Frame) -> ControlFlow<()> {
+        self.frames += 1;
+        match self.play(&frame) {
+            Ok(flow) => flow,
+            Err(_) => ControlFlow::Break(()),
+        }
+    }
+
+    fn play(&mut self, frame: &Frame) -> io::Result<ControlFlow<()>> {
+        if !self.frames.is_multiple_of(self.controls.skip()) {
+            return Ok(ControlFlow::Continue(()));
+        }
+
+        self.draw(frame)?;
+        let deadline = self.shown_at + self.controls.delay();
+        loop {
+            if self.controls.quit {
+                return Ok(ControlFlow::Break(()));
+            }
+            if self.controls.paused {
+                if self.controls.take_step() {
+                    break;
+                }
+                self.read_key(frame)?;
+            } else {
+                let wait = deadline.saturating_duration_since(Instant::now());
+                if wait.is_zero() {
+                    break;
+                }
+                if event::poll(wait)? {
+                    self.read_key(frame)?;
+                }
+            }
+        }
+        self.shown_at = Instant::now();
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Wait for a key and act on it, then redraw the frame to show what
+    /// changed.
+    fn read_key(&mut self, frame: &Frame) -> io::Result<()> {
+        if let Event::Key(key) = event::read()? {
+            if let Some(key) = Key::from_event(key) {
+                self.controls.press(key);
+                self.draw(frame)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &Frame) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+        // Leave a line each for the caption and the status
+        self.viewport.width = width;
+        self.viewport.height = height.saturating_sub(2);
+        let size = (frame.width(), frame.rows.len());
+        match frame.focus {
+            Some(focus) if self.controls.follow => self.viewport.follow(focus, size),
+            _ => self.viewport.clamp(size),
+        }
+
+        let Viewport { col, row, .. } = self.viewport;
+        let mut screen = String::from("\x1b[H");
+        for line in frame.rows.iter().skip(row).take(self.viewport.height) {
+            screen.extend(line.iter().skip(col).take(width));
+            screen.push_str("\x1b[K\r\n");
+        }
+        screen.push_str("\x1b[J");
+
+        let status = format!(
+            " frame {} | {} fps | {} | follow {} | {HELP}",
+            self.frames,
+            self.controls.fps,
+            if self.controls.paused {
+                "paused"
+            } else {
+                "playing"
+            },
+            if self.controls.follow { "on" } else { "off" },
+        );
+        screen.push_str(&format!("\x1b[{};1H", height.saturating_sub(1)));
+        screen.extend(frame.caption.chars().take(width));
+        screen.push_str("\x1b[K\r\n\x1b[7m");
+        screen.extend(status.chars().take(width));
+        screen.push_str("\x1b[K\x1b[0m");
+
+        self.out.write_all(screen.as_bytes())?;
+        self.out.flush()
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        let _ = execute!(self.out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_pause_step_and_change_speed() {
+        let mut controls = Controls::new(&Options {
+            fps: 30.0,
+            paused: false,
+        });
+        assert_eq!(controls.skip(), 1);
+
+        controls.press(Key::Step);
+        assert!(controls.paused);
+        assert!(controls.take_step());
+        assert!(!controls.take_step());
+
+        controls.press(Key::PlayPause);
+        assert!(!controls.paused);
+        for _ in 0..3 {
+            controls.press(Key::Faster);
+        }
+        assert_eq!(controls.fps, 240.0);
+        assert_eq!(controls.skip(), 4);
+        assert_eq!(controls.delay(), Duration::from_secs_f64(4.0 / 240.0));
+
+        for _ in 0..20 {
+            controls.press(Key::Slower);
+        }
+        assert_eq!(controls.fps, MIN_FPS);
+    }
+
+    #[test]
+    fn viewport_follows_the_focus() {
+        let mut viewport = Viewport {
+            width: 20,
+            height: 10,
+            ..Viewport::default()
+        };
+
+        // Near the top left, there's no need to scroll
+        viewport.follow((6, 3), (100, 50));
+        assert_eq!((viewport.col, viewport.row), (0, 0));
+
+        // Further out, it scrolls to keep a margin around the focus
+        viewport.follow((30, 9), (100, 50));
+        assert_eq!((viewport.col, viewport.row), (16, 2));
+
+        // But never past the picture's edges
+        viewport.follow((99, 49), (100, 50));
+        assert_eq!((viewport.col, viewport.row), (80, 40));
+        viewport.follow((0, 0), (100, 50));
+        assert_eq!((viewport.col, viewport.row), (0, 0));
+
+        viewport.follow((30, 30), (10, 5));
+        assert_eq!((viewport.col, viewport.row), (0, 0));
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::{Context, Result};
+use common::{Answer, Part};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::registry::Day;
+
+/// The answers a day's solution is known to give for its embedded input,
+/// stored as `answers.toml` next to the day's `input.txt`, or for one of its
+/// other inputs, stored next to it like `inputs/alice.answers.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<Answer>,
+}
+
+impl Answers {
+    pub fn path(day: &Day) -> PathBuf {
+        day.dir().join("answers.toml")
+    }
+
+    /// Where the answers for an input file other than the day's own are
+    /// recorded: `alice.answers.toml` for `alice.txt`.
+    pub fn path_for(input: &Path) -> PathBuf {
+        input.with_extension("answers.toml")
+    }
+
+    /// Read a day's recorded answers. A missing file just means nothing has
+    /// been recorded yet.
+    pub fn load(day: &Day) -> Result<Answers> {
+        Answers::read(&Answers::path(day))
+    }
+
+    pub fn save(&self, day: &Day) -> Result<()> {
+        self.write(&Answers::path(day))
+    }
+
+    /// Read the answers recorded at `path`, if there are any.
+    pub fn read(path: &Path) -> Result<Answers> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let contents = toml::to_string(self)?;
+        fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: Answer) {
+        match part {
+            Part::One => self.part1 = Some(answer),
+            Part::Two => self.part2 = Some(answer),
+        }
+    }
+}
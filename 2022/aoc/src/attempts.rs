@@ -0,0 +1,176 @@
+use anyhow::{Context, Result};
+use common::{Answer, Part};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::registry::Day;
+use crate::submit::Outcome;
+
+/// Every answer submitted for a day, stored as `attempts.toml` next to the
+/// day's `input.txt`, so that answers the site has already turned down are
+/// never sent again.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Attempts {
+    #[serde(default, rename = "attempt")]
+    attempts: Vec<Attempt>,
+}
+
+/// One answer sent to the site, and what it made of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: Answer,
+    pub outcome: Outcome,
+    /// When it was sent, in seconds since the Unix epoch.
+    pub at: u64,
+    /// When the site will take another answer, if it asked for a wait.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_at: Option<u64>,
+}
+
+impl Attempts {
+    pub fn path(day: &Day) -> PathBuf {
+        day.dir().join("attempts.toml")
+    }
+
+    /// Read a day's attempts. A missing file just means nothing has been
+    /// submitted yet.
+    pub fn load(day: &Day) -> Result<Attempts> {
+        let path = Attempts::path(day);
+        if !path.exists() {
+            return Ok(Attempts::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, day: &Day) -> Result<()> {
+        let path = Attempts::path(day);
+        let contents = toml::to_string(self)?;
+        fs::write(&path, contents).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    fn of(&self, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |attempt| Part::try_from(attempt.part).is_ok_and(|p| p == part))
+    }
+
+    /// Why `answer` shouldn't be submitted for `part` at `now`, judging by
+    /// the earlier attempts, if there's any reason.
+    pub fn refusal(&self, part: Part, answer: &Answer, now: u64) -> Option<String> {
+        if let Some(right) = self.of(part).find(|a| a.outcome == Outcome::Right) {
+            return Some(format!(
+                "part {part} was already solved with {}",
+                right.answer
+            ));
+        }
+        if let Some(earlier) = self
+            .of(part)
+            .find(|a| &a.answer == answer && a.outcome.is_wrong())
+        {
+            return Some(format!("it was already {}", earlier.outcome));
+        }
+
+        // A wrong answer that the site said was too high (or low) rules out
+        // everything above (or below) it too
+        if let Answer::Int(value) = *answer {
+            let bound = |outcome| {
+                self.of(part)
+                    .filter(move |a| a.outcome == outcome)
+                    .filter_map(|a| match a.answer {
+                        Answer::Int(bound) => Some(bound),
+                        Answer::Text(_) => None,
+                    })
+            };
+            if let Some(high) = bound(Outcome::TooHigh).filter(|&high| value > high).min() {
+                return Some(format!("it's too high, since {high} already was"));
+            }
+            if let Some(low) = bound(Outcome::TooLow).filter(|&low| value < low).max() {
+                return Some(format!("it's too low, since {low} already was"));
+            }
+        }
+
+        // The site's waits cover every part of every day, but the log is kept
+        // per day, so this only catches waits from this day's attempts
+        let retry_at = self.attempts.iter().filter_map(|a| a.retry_at).max()?;
+        (now < retry_at).then(|| {
+            format!(
+                "the site asked to wait another {}s before trying again",
+                retry_at - now
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(part: u8, answer: i64, outcome: Outcome, retry_at: Option<u64>) -> Attempt {
+        Attempt {
+            part,
+            answer: Answer::Int(answer),
+            outcome,
+            at: 1000,
+            retry_at,
+        }
+    }
+
+    #[test]
+    fn refuses_answers_already_ruled_out() {
+        let mut attempts = Attempts::default();
+        attempts.record(attempt(1, 100, Outcome::TooHigh, Some(1060)));
+        attempts.record(attempt(1, 20, Outcome::TooLow, None));
+        attempts.record(attempt(1, 50, Outcome::Wrong, None));
+
+        let refusal = |answer: i64| attempts.refusal(Part::One, &Answer::Int(answer), 2000);
+        assert_eq!(refusal(50).unwrap(), "it was already wrong");
+        assert_eq!(refusal(100).unwrap(), "it was already too high");
+        assert_eq!(
+            refusal(150).unwrap(),
+            "it's too high, since 100 already was"
+        );
+        assert_eq!(refusal(7).unwrap(), "it's too low, since 20 already was");
+        assert_eq!(refusal(60), None);
+        assert_eq!(attempts.refusal(Part::Two, &Answer::Int(150), 2000), None);
+
+        assert_eq!(
+            attempts.refusal(Part::One, &Answer::Int(60), 1030).unwrap(),
+            "the site asked to wait another 30s before trying again"
+        );
+    }
+
+    #[test]
+    fn refuses_parts_already_solved() {
+        let mut attempts = Attempts::default();
+        attempts.record(attempt(2, 42, Outcome::Right, None));
+
+        assert_eq!(
+            attempts.refusal(Part::Two, &Answer::Int(43), 2000).unwrap(),
+            "part 2 was already solved with 42"
+        );
+        assert_eq!(attempts.refusal(Part::One, &Answer::Int(43), 2000), None);
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let mut attempts = Attempts::default();
+        attempts.record(attempt(1, 100, Outcome::TooHigh, Some(1060)));
+        attempts.record(Attempt {
+            answer: Answer::Text("EHZFZHCZ".to_string()),
+            ..attempt(2, 0, Outcome::Right, None)
+        });
+
+        let contents = toml::to_string(&attempts).unwrap();
+        assert!(contents.contains("[[attempt]]\npart = 1\nanswer = 100\noutcome = \"too-high\""));
+        let read: Attempts = toml::from_str(&contents).unwrap();
+        assert_eq!(read.attempts, attempts.attempts);
+    }
+}
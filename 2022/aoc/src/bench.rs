@@ -0,0 +1,339 @@
+use anyhow::{Context, Result};
+use common::Part;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::registry::Day;
+
+/// A step of a day's solution that gets timed on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Stage {
+    pub fn all(parts: &[Part]) -> Vec<Stage> {
+        let parts = parts.iter().map(|&part| Stage::Part(part));
+        std::iter::once(Stage::Parse).chain(parts).collect()
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+/// How long to spend measuring each stage.
+pub struct Options {
+    pub warm_up: Duration,
+    pub measurement_time: Duration,
+    pub samples: usize,
+    /// Slow stages take fewer samples to fit the measurement time, but never
+    /// fewer than this.
+    pub min_samples: usize,
+    /// How much slower, as a fraction, a stage has to get to be flagged.
+    pub threshold: f64,
+}
+
+/// A summary of the samples for one stage. Timings are per iteration, in
+/// nanoseconds.
+///
+/// The median is reported with a distribution-free 95% confidence interval
+/// (taken from the order statistics of the samples), so outliers from a
+/// noisy machine don't drag it around the way they would a mean.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stats {
+    pub median: f64,
+    pub low: f64,
+    pub high: f64,
+    pub samples: usize,
+    pub iterations: u64,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<f64>, iterations: u64) -> Stats {
+        samples.sort_by(f64::total_cmp);
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2.0
+        } else {
+            samples[n / 2]
+        };
+
+        // The rank of the median is binomially distributed, so the normal
+        // approximation gives the ranks bounding the 95% interval.
+        let spread = 0.98 * (n as f64).sqrt();
+        let low = ((n as f64 / 2.0 - spread).floor().max(0.0)) as usize;
+        let high = ((n as f64 / 2.0 + spread).ceil() as usize).min(n - 1);
+
+        Stats {
+            median,
+            low: samples[low],
+            high: samples[high],
+            samples: n,
+            iterations,
+        }
+    }
+}
+
+/// How a stage's timing compares with the baseline.
+enum Change {
+    /// No baseline to compare against.
+    New,
+    /// Within the threshold, or too noisy to tell apart from the baseline.
+    Unchanged(f64),
+    Improved(f64),
+    Regressed(f64),
+}
+
+impl Change {
+    /// A change only counts once it's past the threshold *and* the confidence
+    /// intervals of the two runs no longer overlap.
+    fn between(baseline: Option<&Stats>, current: &Stats, threshold: f64) -> Change {
+        let Some(baseline) = baseline else {
+            return Change::New;
+        };
+        let ratio = current.median / baseline.median - 1.0;
+        if ratio > threshold && current.low > baseline.high {
+            Change::Regressed(ratio)
+        } else if ratio < -threshold && current.high < baseline.low {
+            Change::Improved(ratio)
+        } else {
+            Change::Unchanged(ratio)
+        }
+    }
+}
+
+/// Measurements saved from an earlier run, keyed by year, day and stage:
+/// timings by default, or whatever else `S` holds for each stage.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "S: Serialize + DeserializeOwned")]
+pub struct Baseline<S = Stats> {
+    #[serde(default = "Vec::new")]
+    stages: Vec<Saved<S>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Saved<S> {
+    year: u16,
+    day: u8,
+    stage: String,
+    #[serde(flatten)]
+    stats: S,
+}
+
+impl<S> Default for Baseline<S> {
+    fn default() -> Self {
+        Baseline { stages: Vec::new() }
+    }
+}
+
+impl Baseline {
+    /// Where baselines live unless another path is given: under `target`, so
+    /// they're never committed. Timings only mean something on the machine
+    /// that took them.
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/aoc-bench/baseline.toml")
+    }
+}
+
+impl<S: Serialize + DeserializeOwned> Baseline<S> {
+    pub fn load(path: &Path) -> Result<Baseline<S>> {
+        if !path.exists() {
+            return Ok(Baseline::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, day: &Day, stage: Stage) -> Option<&S> {
+        let stage = stage.to_string();
+        self.stages
+            .iter()
+            .find(|s| s.year == day.year && s.day == day.day && s.stage == stage)
+            .map(|s| &s.stats)
+    }
+
+    pub fn set(&mut self, day: &Day, stage: Stage, stats: S) {
+        let saved = Saved {
+            year: day.year,
+            day: day.day,
+            stage: stage.to_string(),
+            stats,
+        };
+        match self
+            .stages
+            .iter_mut()
+            .find(|s| s.year == saved.year && s.day == saved.day && s.stage == saved.stage)
+        {
+            Some(existing) => *existing = saved,
+            None => self.stages.push(saved),
+        }
+    }
+}
+
+/// Time `f` according to `options`: run it for the warm-up period, use that
+/// to size the samples, then collect them.
+fn measure(options: &Options, mut f: impl FnMut()) -> Stats {
+    let start = Instant::now();
+    let mut warm_up_runs = 0u32;
+    while warm_up_runs == 0 || start.elapsed() < options.warm_up {
+        f();
+        warm_up_runs += 1;
+    }
+    let estimate = start.elapsed().as_secs_f64() / warm_up_runs as f64;
+
+    // Fast stages run several times per sample so each sample is long enough
+    // to time accurately; slow ones take fewer samples to fit the budget.
+    let budget = options.measurement_time.as_secs_f64();
+    let samples = ((budget / estimate) as usize).clamp(options.min_samples, options.samples);
+    let iterations = ((budget / samples as f64 / estimate) as u64).max(1);
+
+    let samples = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                f();
+            }
+            start.elapsed().as_nanos() as f64 / iterations as f64
+        })
+        .collect();
+
+    Stats::from_samples(samples, iterations)
+}
+
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.2?}", Duration::from_nanos(nanos as u64))
+}
+
+/// Benchmark the stages of a day against its embedded input, printing each
+/// result and how it compares with `baseline`. The new timings are written
+/// into `baseline` but it's up to the caller whether to save them. Returns
+/// the number of stages that regressed.
+pub fn bench(
+    day: &Day,
+    stages: &[Stage],
+    options: &Options,
+    baseline: &mut Baseline,
+) -> Result<usize> {
+    let input = day.solution.embedded_input();
+    let parsed = day.solution.parse(input)?;
+    for &stage in stages {
+        if let Stage::Part(part) = stage {
+            day.solution
+                .solve_parsed(parsed.as_ref(), part)
+                .with_context(|| format!("part {part} failed"))?;
+        }
+    }
+    let mut regressions = 0;
+
+    for &stage in stages {
+        let stats = match stage {
+            Stage::Parse => measure(options, || {
+                black_box(day.solution.parse(black_box(input)).ok());
+            }),
+            Stage::Part(part) => measure(options, || {
+                black_box(
+                    day.solution
+                        .solve_parsed(black_box(parsed.as_ref()), part)
+                        .ok(),
+                );
+            }),
+        };
+
+        let change = Change::between(baseline.get(day, stage), &stats, options.threshold);
+        let change = match change {
+            Change::New => String::new(),
+            Change::Unchanged(ratio) => format!("{:+.1}%", ratio * 100.0),
+            Change::Improved(ratio) => format!("{:+.1}% improved", ratio * 100.0),
+            Change::Regressed(ratio) => {
+                regressions += 1;
+                format!("{:+.1}% REGRESSED", ratio * 100.0)
+            }
+        };
+        let line = format!(
+            "{} day {:02} {:<6} {:>10} [{} .. {}] x{:<3} {change}",
+            day.year,
+            day.day,
+            stage.to_string(),
+            format_nanos(stats.median),
+            format_nanos(stats.low),
+            format_nanos(stats.high),
+            stats.samples,
+        );
+        println!("{}", line.trim_end());
+
+        baseline.set(day, stage, stats);
+    }
+
+    Ok(regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(samples: &[f64]) -> Stats {
+        Stats::from_samples(samples.to_vec(), 1)
+    }
+
+    #[test]
+    fn median_interval_ignores_outliers() {
+        let mut samples = vec![100.0; 28];
+        samples.extend([5000.0, 9000.0]);
+        let stats = stats(&samples);
+        assert_eq!(stats.median, 100.0);
+        assert_eq!(stats.high, 100.0);
+    }
+
+    #[test]
+    fn median_of_an_even_number_of_samples() {
+        assert_eq!(stats(&[4.0, 1.0, 3.0, 2.0]).median, 2.5);
+    }
+
+    #[test]
+    fn flags_only_clear_changes_past_the_threshold() {
+        let baseline = stats(&[98.0, 99.0, 100.0, 101.0, 102.0]);
+        let slower = stats(&[118.0, 119.0, 120.0, 121.0, 122.0]);
+        let noisy = stats(&[60.0, 80.0, 120.0, 160.0, 180.0]);
+        let faster = stats(&[48.0, 49.0, 50.0, 51.0, 52.0]);
+
+        assert!(matches!(Change::between(None, &slower, 0.05), Change::New));
+        assert!(matches!(
+            Change::between(Some(&baseline), &slower, 0.05),
+            Change::Regressed(_)
+        ));
+        assert!(matches!(
+            Change::between(Some(&baseline), &slower, 0.5),
+            Change::Unchanged(_)
+        ));
+        assert!(matches!(
+            Change::between(Some(&baseline), &noisy, 0.05),
+            Change::Unchanged(_)
+        ));
+        assert!(matches!(
+            Change::between(Some(&baseline), &faster, 0.05),
+            Change::Improved(_)
+        ));
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::site::Site;
+
+/// Download a day's puzzle input into `input.txt` in the day's directory.
+/// An input that's already there is never fetched again: the puzzle input
+/// doesn't change, and the site asks not to be hit for it repeatedly. An
+/// empty `input.txt`, like the placeholder `aoc new` leaves, doesn't count.
+pub fn fetch(site: &Site, year: u16, day: u8, dir: &Path) -> Result<PathBuf> {
+    let path = dir.join("input.txt");
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        bail!(
+            "{} already exists, so it won't be fetched again. Delete it first to replace it",
+            path.display()
+        );
+    }
+
+    let input = site.input(year, day)?;
+
+    // Write somewhere else first, so an interrupted write doesn't leave a
+    // truncated input that looks like it was cached
+    let partial = dir.join("input.txt.part");
+    fs::write(&partial, input).with_context(|| format!("failed to write {}", partial.display()))?;
+    fs::rename(&partial, &path).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::site::stand_in;
+    use std::env;
+
+    fn empty_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn caches_the_input_in_the_day_directory() {
+        let (base_url, requests) = stand_in::serve(vec![(200, "A Y\nB X\nC Z\n")]);
+        let dir = empty_dir("caches");
+
+        let path = fetch(&Site::new(&base_url, "abc123"), 2022, 2, &dir).unwrap();
+        assert_eq!(path, dir.join("input.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A Y\nB X\nC Z\n");
+        assert_eq!(
+            requests.recv().unwrap().line,
+            "GET /2022/day/2/input HTTP/1.1"
+        );
+        assert!(!dir.join("input.txt.part").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_to_fetch_an_input_again() {
+        let (base_url, requests) = stand_in::serve(vec![(200, "new\n")]);
+        let dir = empty_dir("refuses");
+        fs::write(dir.join("input.txt"), "old\n").unwrap();
+
+        let error = fetch(&Site::new(&base_url, "abc123"), 2022, 2, &dir).unwrap_err();
+        assert!(error.to_string().contains("won't be fetched again"));
+        assert_eq!(fs::read_to_string(dir.join("input.txt")).unwrap(), "old\n");
+        assert!(requests.try_recv().is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn replaces_an_empty_placeholder() {
+        let (base_url, _requests) = stand_in::serve(vec![(200, "1\n")]);
+        let dir = empty_dir("placeholder");
+        fs::write(dir.join("input.txt"), "").unwrap();
+
+        fetch(&Site::new(&base_url, "abc123"), 2022, 21, &dir).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("input.txt")).unwrap(), "1\n");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn leaves_nothing_behind_when_the_day_is_locked() {
+        let (base_url, _requests) = stand_in::serve(vec![(404, "Not yet!\n")]);
+        let dir = empty_dir("locked");
+
+        assert!(fetch(&Site::new(&base_url, "abc123"), 2022, 25, &dir).is_err());
+        assert!(!dir.join("input.txt").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+use common::{Answer, Param};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// A part's answer with its type spelled out, so that scripts don't have to
+/// guess whether `"123"` was a number.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum TypedAnswer {
+    Integer(i64),
+    Text(String),
+    /// A picture drawn on a screen, like day 10's CRT, one string per row.
+    /// The rows are padded with spaces to the same width, since answers
+    /// lose the blanks at the ends of their lines.
+    Grid(Vec<String>),
+}
+
+impl From<&Answer> for TypedAnswer {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Int(value) => TypedAnswer::Integer(*value),
+            // Answers only span several lines when they're drawn out, as
+            // `print_answer` assumes too
+            Answer::Text(text) if text.contains('\n') => {
+                let rows: Vec<&str> = text.lines().collect();
+                let width = rows
+                    .iter()
+                    .map(|row| row.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                TypedAnswer::Grid(rows.iter().map(|row| format!("{row:width$}")).collect())
+            }
+            Answer::Text(text) => TypedAnswer::Text(text.clone()),
+        }
+    }
+}
+
+/// What `run --json` prints for a part, as a line of its own.
+#[derive(Debug, Serialize)]
+pub struct PartReport<'a> {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: TypedAnswer,
+    /// Seconds spent parsing the input, shared by both parts.
+    pub parse_secs: f64,
+    /// Seconds spent solving the part once the input was parsed.
+    pub solve_secs: f64,
+    /// The SHA-256 of the input, in hex.
+    pub input_sha256: &'a str,
+    /// The puzzle parameters the part was solved with, if the day has any.
+    pub params: BTreeMap<&'static str, &'a str>,
+}
+
+impl PartReport<'_> {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("reports only hold strings and numbers")
+    }
+}
+
+/// The parameters' values by name.
+pub fn param_values(params: &[Param]) -> BTreeMap<&'static str, &str> {
+    params
+        .iter()
+        .map(|param| (param.name, param.value.as_str()))
+        .collect()
+}
+
+/// The SHA-256 of a puzzle input, in hex.
+pub fn sha256(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn types_answers() {
+        assert_eq!(
+            TypedAnswer::from(&Answer::Int(-3)),
+            TypedAnswer::Integer(-3)
+        );
+        assert_eq!(
+            TypedAnswer::from(&Answer::from("CMZ")),
+            TypedAnswer::Text("CMZ".to_string())
+        );
+        assert_eq!(
+            TypedAnswer::from(&Answer::from("# \n #\n")),
+            TypedAnswer::Grid(vec!["# ".to_string(), " #".to_string()])
+        );
+    }
+
+    #[test]
+    fn writes_a_line_per_part() {
+        let params = [Param {
+            name: "row",
+            value: "10".to_string(),
+            about: "",
+        }];
+        let report = PartReport {
+            year: 2022,
+            day: 15,
+            part: 1,
+            answer: (&Answer::Int(26)).into(),
+            parse_secs: 0.5,
+            solve_secs: 0.25,
+            input_sha256: "abc",
+            params: param_values(&params),
+        };
+        assert_eq!(
+            report.to_json(),
+            r#"{"year":2022,"day":15,"part":1,"answer":{"type":"integer","value":26},"parse_secs":0.5,"solve_secs":0.25,"input_sha256":"abc","params":{"row":"10"}}"#
+        );
+    }
+
+    #[test]
+    fn hashes_inputs() {
+        assert_eq!(
+            sha256(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+}
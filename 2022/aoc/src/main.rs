@@ -0,0 +1,957 @@
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use common::{Answer, Overrides, Part, Source};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use std::{panic, process};
+
+mod answers;
+mod attempts;
+mod bench;
+mod fetch;
+mod json;
+mod matrix;
+mod new;
+mod params;
+mod pictures;
+mod play;
+mod profile;
+mod registry;
+mod site;
+mod submit;
+mod verify;
+mod watch;
+
+use registry::Day;
+
+#[cfg(feature = "alloc-profile")]
+#[global_allocator]
+static ALLOCATOR: profile::Counting = profile::Counting;
+
+/// Runs Advent of Code solutions from a single binary.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Show the solutions' trace output at this level, like `debug`, or for
+    /// each crate, like `day19=trace,search=debug`. Defaults to `RUST_LOG`,
+    /// or just warnings and errors.
+    #[arg(long, global = true, value_name = "FILTER")]
+    log: Option<String>,
+
+    /// Write the trace output to this file instead of stderr.
+    #[arg(long, global = true)]
+    log_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for one day, or every day of a year.
+    Run {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day. Required unless `--all` is given.
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+
+        /// Run every registered day for the year.
+        #[arg(long)]
+        all: bool,
+
+        /// Only run this part of the puzzle.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Read the puzzle input from this file, or from stdin if it's `-`.
+        /// Defaults to the input embedded in the day's crate. Parameters in a
+        /// sidecar file next to it (`example.params.toml` for `example.txt`)
+        /// are applied before any given here.
+        #[arg(long, conflicts_with = "all")]
+        input: Option<String>,
+
+        /// Start from one of the day's presets of puzzle parameters, like
+        /// `example`.
+        #[arg(long, conflicts_with = "all")]
+        preset: Option<String>,
+
+        /// Set one of the day's puzzle parameters, like `--param row=10`. Can
+        /// be given more than once.
+        #[arg(long = "param", value_name = "NAME=VALUE", conflicts_with = "all")]
+        params: Vec<String>,
+
+        /// Print a line of JSON for each part instead, with the answer and
+        /// its type, the timings, a hash of the input and the parameters.
+        #[arg(long)]
+        json: bool,
+    },
+    /// List a day's puzzle parameters with their values, and its presets.
+    Params {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day.
+        day: u8,
+
+        /// Show the values of this preset instead of the default.
+        #[arg(long)]
+        preset: Option<String>,
+    },
+    /// Check solutions against their recorded answers, with timings.
+    Verify {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day. Required unless `--all` is given.
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+
+        /// Verify every registered day for the year.
+        #[arg(long)]
+        all: bool,
+
+        /// Only verify this part of the puzzle.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Save answers that haven't been recorded yet. Recorded answers are
+        /// never overwritten; delete them from `answers.toml` to re-record.
+        #[arg(long)]
+        record: bool,
+    },
+    /// Solve a day for its own input and every input in its `inputs/`
+    /// directory, like other accounts' inputs, printing a table of the
+    /// answers and timings. Answers that disagree with the ones recorded for
+    /// an input (`inputs/alice.answers.toml` for `inputs/alice.txt`) are
+    /// marked.
+    Matrix {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day. Required unless `--all` is given.
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+
+        /// Run every registered day for the year.
+        #[arg(long)]
+        all: bool,
+
+        /// Only solve this part of the puzzle.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Save answers that haven't been recorded yet for an input. Recorded
+        /// answers are never overwritten.
+        #[arg(long)]
+        record: bool,
+    },
+    /// Benchmark the parse step and each part separately, comparing the
+    /// timings with a saved baseline.
+    Bench {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day. Required unless `--all` is given.
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+
+        /// Benchmark every registered day for the year.
+        #[arg(long)]
+        all: bool,
+
+        /// Only benchmark parsing and this part of the puzzle.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Save these timings to the baseline, replacing any earlier ones for
+        /// the same stages.
+        #[arg(long)]
+        save_baseline: bool,
+
+        /// The baseline file. Defaults to `target/aoc-bench/baseline.toml`.
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Flag stages that got slower than the baseline by more than this
+        /// percentage.
+        #[arg(long, default_value_t = 5.0)]
+        threshold: f64,
+
+        /// The most samples to take of each stage.
+        #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u64).range(2..))]
+        samples: u64,
+
+        /// Roughly how many seconds to spend sampling each stage. Slow stages
+        /// take fewer samples (but at least 10) to fit.
+        #[arg(long, default_value = "3", value_parser = parse_seconds)]
+        measurement_time: Duration,
+
+        /// Seconds to run each stage before sampling it.
+        #[arg(long, default_value = "0.5", value_parser = parse_seconds)]
+        warm_up: Duration,
+    },
+    /// Count the allocations, bytes allocated and peak heap of the parse step
+    /// and each part, comparing them with a saved baseline. Needs the runner
+    /// built with `--features alloc-profile`.
+    Profile {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day. Required unless `--all` is given.
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+
+        /// Profile every registered day for the year.
+        #[arg(long)]
+        all: bool,
+
+        /// Only profile parsing and this part of the puzzle.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Save these counts to the baseline, replacing any earlier ones for
+        /// the same stages.
+        #[arg(long)]
+        save_baseline: bool,
+
+        /// The baseline file. Defaults to `target/aoc-profile/baseline.toml`.
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+    },
+    /// Download a day's puzzle input into its crate as `input.txt`. Needs the
+    /// session cookie from a logged-in browser, in `AOC_SESSION` or the
+    /// `session` key of `~/.config/aoc/config.toml`.
+    Fetch {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day.
+        day: u8,
+
+        /// Fetch from this site instead of the configured one (or
+        /// https://adventofcode.com).
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Submit the answer to one part of a day's puzzle. Every attempt is
+    /// logged in the day's `attempts.toml`, and answers that earlier attempts
+    /// already rule out aren't sent.
+    Submit {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day.
+        day: u8,
+
+        /// The part of the puzzle to answer.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Submit this answer instead of the one the solution gives for the
+        /// embedded input.
+        #[arg(long)]
+        answer: Option<String>,
+
+        /// Submit to this site instead of the configured one (or
+        /// https://adventofcode.com).
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Watch a day's simulation solve one part of the puzzle in the terminal.
+    /// Space plays and pauses, `.` steps a frame at a time, `+` and `-`
+    /// change the speed, `f` turns following the action on and off, and `q`
+    /// stops watching (the answer is still worked out).
+    Animate {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day.
+        day: u8,
+
+        /// The part of the puzzle to watch.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Read the puzzle input from this file, or from stdin if it's `-`.
+        /// Defaults to the input embedded in the day's crate. Parameters in a
+        /// sidecar file next to it are applied before any given here.
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Start from one of the day's presets of puzzle parameters, like
+        /// `example`.
+        #[arg(long)]
+        preset: Option<String>,
+
+        /// Set one of the day's puzzle parameters, like `--param row=10`. Can
+        /// be given more than once.
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+
+        /// How many frames to show a second, to start with.
+        #[arg(long, default_value_t = 30.0)]
+        fps: f64,
+
+        /// Start paused on the first frame.
+        #[arg(long)]
+        paused: bool,
+    },
+    /// Save a picture of a day's puzzle as a PNG or an SVG, or a part's
+    /// simulation as an animated GIF, picked by the file's extension.
+    Export {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day.
+        day: u8,
+
+        /// The file to write, ending in `.png`, `.svg` or `.gif`.
+        path: PathBuf,
+
+        /// Read the puzzle input from this file, or from stdin if it's `-`.
+        /// Defaults to the input embedded in the day's crate. Parameters in a
+        /// sidecar file next to it are applied before any given here.
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Start from one of the day's presets of puzzle parameters, like
+        /// `example`.
+        #[arg(long)]
+        preset: Option<String>,
+
+        /// Set one of the day's puzzle parameters, like `--param row=10`. Can
+        /// be given more than once.
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+
+        /// Pixels to a cell of the picture. Defaults to whatever makes it
+        /// about 800 pixels wide.
+        #[arg(long)]
+        scale: Option<f64>,
+
+        /// The part of the puzzle whose simulation goes in a GIF.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Put one frame in this many in a GIF.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        every: u64,
+
+        /// The most frames to put in a GIF; the simulation still runs to its
+        /// answer after that.
+        #[arg(long, default_value_t = 500, value_parser = clap::value_parser!(u64).range(1..))]
+        max_frames: u64,
+
+        /// How many frames a GIF shows a second.
+        #[arg(long, default_value_t = 30.0)]
+        fps: f64,
+    },
+    /// Rebuild and re-run a day whenever its source, tests, example or input
+    /// change, showing how the answers and timings moved since the last run
+    /// and how the example tests went.
+    Watch {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day.
+        day: u8,
+
+        /// Only run this part of the puzzle.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Solve this input file instead of the day's `input.txt`, and watch
+        /// it for changes.
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// How many milliseconds to wait between looking for changes.
+        #[arg(long, default_value_t = 250, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+    /// Create the crate for a new day from the template, with a parser
+    /// module, stubs for both parts and a skeleton example test, and register
+    /// it with the runner.
+    New {
+        /// The puzzle year, e.g. 2022.
+        year: u16,
+
+        /// The puzzle day.
+        day: u8,
+    },
+}
+
+/// The parts to run, given the `--part` option.
+fn parts(part: Option<u8>) -> Vec<Part> {
+    match part {
+        Some(part) => vec![Part::try_from(part).expect("clap checks the range")],
+        None => Part::BOTH.to_vec(),
+    }
+}
+
+/// Read a number of seconds given on the command line, like `0.5`.
+fn parse_seconds(arg: &str) -> Result<Duration, String> {
+    let seconds: f64 = arg.parse().map_err(|e| format!("{e}"))?;
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| "expected a number of seconds, not negative and not too big".to_string())
+}
+
+/// The overrides given by `--preset` and `--param`, exiting if a `--param`
+/// isn't `name=value`.
+fn overrides(preset: Option<String>, params: &[String]) -> Overrides {
+    let values = params
+        .iter()
+        .map(|arg| params::parse_override(arg))
+        .collect();
+    match values {
+        Ok(values) => Overrides { preset, values },
+        Err(e) => {
+            eprintln!("{e:#}");
+            process::exit(1);
+        }
+    }
+}
+
+/// The days picked by the command line, exiting if there aren't any.
+fn days(year: u16, day: Option<u8>, all: bool) -> Vec<&'static Day> {
+    let days: Vec<&Day> = if all {
+        registry::year(year).collect()
+    } else {
+        let day = day.expect("clap requires a day unless --all is set");
+        registry::find(year, day).into_iter().collect()
+    };
+
+    if days.is_empty() {
+        eprintln!(
+            "No solutions registered for {year}{}",
+            match day {
+                Some(day) => format!(" day {day}"),
+                None => String::new(),
+            }
+        );
+        process::exit(1);
+    }
+    days
+}
+
+/// Solve `parts` of a day's puzzle and print the answers, either for people
+/// or as a line of JSON each.
+fn run(day: &Day, source: &Source, parts: &[Part], overrides: Overrides, json: bool) -> Result<()> {
+    let overrides = params::sidecar(source)?.then(overrides);
+    let configured;
+    let (solution, params) = match params::find(day.year, day.day) {
+        Some(params) => {
+            configured = params.configure(&overrides)?;
+            (configured.as_ref(), params.params(&overrides)?)
+        }
+        None if overrides.is_empty() => (day.solution, Vec::new()),
+        None => bail!("There are no puzzle parameters to set"),
+    };
+
+    let input = source.read(solution.embedded_input())?;
+    let start = Instant::now();
+    let parsed = match solution.parse(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => bail!("{}", common::diagnose(&e, source, &input)),
+    };
+    let parse_time = start.elapsed();
+    let input_sha256 = json::sha256(&input);
+
+    for &part in parts {
+        let start = Instant::now();
+        let answer = solution
+            .solve_parsed(parsed.as_ref(), part)
+            .with_context(|| format!("part {part} failed"))?;
+        if json {
+            let report = json::PartReport {
+                year: day.year,
+                day: day.day,
+                part: part.into(),
+                answer: (&answer).into(),
+                parse_secs: parse_time.as_secs_f64(),
+                solve_secs: start.elapsed().as_secs_f64(),
+                input_sha256: &input_sha256,
+                params: json::param_values(&params),
+            };
+            println!("{}", report.to_json());
+        } else {
+            let label = format!("{} day {:02} part {}:", day.year, day.day, part);
+            common::print_answer(&label, &answer);
+        }
+    }
+
+    Ok(())
+}
+
+fn list_params(year: u16, day: u8, preset: Option<String>) -> Result<()> {
+    let Some(params) = params::find(year, day) else {
+        let days: Vec<String> = params::days()
+            .filter(|&(y, _)| y == year)
+            .map(|(_, d)| d.to_string())
+            .collect();
+        bail!(
+            "{year} day {day} has no puzzle parameters; days {} do",
+            days.join(", ")
+        );
+    };
+    let presets = params.presets();
+    let shown = preset.clone().unwrap_or_else(|| presets[0].to_string());
+    let values = params.params(&Overrides {
+        preset,
+        values: Vec::new(),
+    })?;
+
+    println!("{year} day {day:02} parameters ({shown}):");
+    let width = values
+        .iter()
+        .map(|param| param.name.len())
+        .max()
+        .unwrap_or(0);
+    for param in &values {
+        println!(
+            "  {:width$} = {:<12} {}",
+            param.name, param.value, param.about
+        );
+    }
+    println!("Presets: {}", presets.join(", "));
+    Ok(())
+}
+
+fn fetch(year: u16, day: u8, base_url: Option<String>) -> Result<()> {
+    let Some(registered) = registry::find(year, day) else {
+        bail!("No crate is registered for {year} day {day} to fetch the input into");
+    };
+    let mut config = site::Config::load()?;
+    if base_url.is_some() {
+        config.base_url = base_url;
+    }
+    let site = site::Site::from_config(&config)?;
+
+    let path = fetch::fetch(&site, year, day, registered.dir())?;
+    println!("Saved {year} day {day:02} input to {}", path.display());
+    Ok(())
+}
+
+fn submit(
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: Option<String>,
+    base_url: Option<String>,
+) -> Result<()> {
+    let Some(registered) = registry::find(year, day) else {
+        bail!("No solution is registered for {year} day {day}");
+    };
+    let answer = match answer {
+        Some(answer) => match answer.trim().parse() {
+            Ok(value) => Answer::Int(value),
+            Err(_) => Answer::from(answer),
+        },
+        None => registered
+            .solution
+            .solve(registered.solution.embedded_input(), part)
+            .with_context(|| format!("part {part} failed"))?,
+    };
+    let mut config = site::Config::load()?;
+    if base_url.is_some() {
+        config.base_url = base_url;
+    }
+    let site = site::Site::from_config(&config)?;
+
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_secs();
+    let mut attempts = attempts::Attempts::load(registered)?;
+    let reply = submit::submit(&site, &mut attempts, year, day, part, &answer, now)?;
+    attempts.save(registered)?;
+
+    println!("{}", reply.message);
+    match reply.outcome {
+        submit::Outcome::Right => {
+            let mut answers = answers::Answers::load(registered)?;
+            if answers.get(part).is_none() {
+                answers.set(part, answer);
+                answers.save(registered)?;
+                println!(
+                    "Recorded the answer in {}",
+                    answers::Answers::path(registered).display()
+                );
+            }
+            Ok(())
+        }
+        outcome => bail!("{year} day {day:02} part {part}: {answer} was {outcome}"),
+    }
+}
+
+fn animate(
+    year: u16,
+    day: u8,
+    part: Part,
+    source: &Source,
+    overrides: Overrides,
+    options: animate::Options,
+) -> Result<()> {
+    let (Some(registered), Some(make)) = (registry::find(year, day), play::find(year, day)) else {
+        let days: Vec<String> = play::days()
+            .filter(|&(y, _)| y == year)
+            .map(|(_, d)| d.to_string())
+            .collect();
+        bail!(
+            "{year} day {day} has no simulation to watch; days {} do",
+            days.join(", ")
+        );
+    };
+
+    let animation = make(&params::sidecar(source)?.then(overrides))?;
+    let input = source.read(registered.solution.embedded_input())?;
+    match play::play(animation.as_ref(), &input, part, options) {
+        Ok((answer, frames)) => {
+            let label = format!("{year} day {day:02} part {part} ({frames} frames):");
+            common::print_answer(&label, &answer);
+            Ok(())
+        }
+        Err(e) => bail!("{}", common::diagnose(&e, source, &input)),
+    }
+}
+
+fn export(
+    year: u16,
+    day: u8,
+    path: &Path,
+    source: &Source,
+    overrides: Overrides,
+    scale: Option<f64>,
+    gif: pictures::GifOptions,
+) -> Result<()> {
+    let Some(registered) = registry::find(year, day) else {
+        bail!("No solution is registered for {year} day {day}");
+    };
+    if scale.is_some_and(|scale| scale <= 0.0 || !scale.is_finite()) {
+        bail!("The scale must be a positive number of pixels");
+    }
+    let overrides = params::sidecar(source)?.then(overrides);
+    let input = source.read(registered.solution.embedded_input())?;
+    let result = if path.extension().is_some_and(|ext| ext == "gif") {
+        let Some(make) = play::find(year, day) else {
+            bail!("{year} day {day} has no simulation to make a GIF of");
+        };
+        if gif.fps <= 0.0 || !gif.fps.is_finite() {
+            bail!("A GIF needs a positive number of frames a second");
+        }
+        let animation = make(&overrides)?;
+        pictures::save_gif(animation.as_ref(), &input, path, &gif).map(|(answer, frames)| {
+            let label = format!("{year} day {day:02} part {} ({frames} frames):", gif.part);
+            common::print_answer(&label, &answer);
+        })
+    } else {
+        let Some(make) = pictures::find(year, day) else {
+            let days: Vec<String> = pictures::days()
+                .filter(|&(y, _)| y == year)
+                .map(|(_, d)| d.to_string())
+                .collect();
+            bail!(
+                "{year} day {day} has no picture to export; days {} do",
+                days.join(", ")
+            );
+        };
+        let picture = make(&overrides)?;
+        pictures::save_picture(picture.as_ref(), &input, path, scale)
+    };
+    match result {
+        Ok(()) => {
+            println!("Saved {}", path.display());
+            Ok(())
+        }
+        Err(e) => bail!("{}", common::diagnose(&e, source, &input)),
+    }
+}
+
+fn new(year: u16, day: u8) -> Result<()> {
+    // The crates are named by day alone, so one workspace can only hold one
+    // year's puzzles
+    if registry::year(year).next().is_none() {
+        bail!("This workspace doesn't hold the puzzles for {year}");
+    }
+
+    let dir = new::new_day(registry::workspace_dir(), year, day)?;
+    println!("Created {}", dir.display());
+    println!("Add the example to example.txt, and fetch the input with `aoc fetch {year} {day}`");
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = common::init_tracing(cli.log.as_deref(), cli.log_file.as_deref()) {
+        eprintln!("{e:#}");
+        process::exit(1);
+    }
+
+    match cli.command {
+        Command::Run {
+            year,
+            day,
+            all,
+            part,
+            input,
+            preset,
+            params,
+            json,
+        } => {
+            let parts = parts(part);
+            let source = Source::from_arg(input.as_deref());
+            let overrides = overrides(preset, &params);
+
+            for day in days(year, day, all) {
+                if let Err(e) = run(day, &source, &parts, overrides.clone(), json) {
+                    eprintln!("{} day {:02}: {e:#}", day.year, day.day);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Params { year, day, preset } => {
+            if let Err(e) = list_params(year, day, preset) {
+                eprintln!("{e:#}");
+                process::exit(1);
+            }
+        }
+        Command::Verify {
+            year,
+            day,
+            all,
+            part,
+            record,
+        } => {
+            let parts = parts(part);
+            // Panics are reported as failures, so keep the default hook from
+            // printing them as well.
+            panic::set_hook(Box::new(|_| {}));
+
+            let mut summary = verify::Summary::default();
+            for day in days(year, day, all) {
+                if let Err(e) = verify::verify(day, &parts, record, &mut summary) {
+                    eprintln!("{} day {:02}: {e:#}", day.year, day.day);
+                    process::exit(1);
+                }
+            }
+
+            println!(
+                "{} passed, {} regressed, {} failed, {} unrecorded",
+                summary.passed, summary.regressed, summary.failed, summary.unrecorded
+            );
+            if !summary.ok() {
+                process::exit(1);
+            }
+        }
+        Command::Matrix {
+            year,
+            day,
+            all,
+            part,
+            record,
+        } => {
+            let parts = parts(part);
+            panic::set_hook(Box::new(|_| {}));
+
+            let mut summary = verify::Summary::default();
+            for day in days(year, day, all) {
+                if let Err(e) = matrix::matrix(day, &parts, record, &mut summary) {
+                    eprintln!("{} day {:02}: {e:#}", day.year, day.day);
+                    process::exit(1);
+                }
+            }
+
+            println!(
+                "{} passed, {} wrong, {} failed, {} unrecorded",
+                summary.passed, summary.regressed, summary.failed, summary.unrecorded
+            );
+            if !summary.ok() {
+                process::exit(1);
+            }
+        }
+        Command::Bench {
+            year,
+            day,
+            all,
+            part,
+            save_baseline,
+            baseline,
+            threshold,
+            samples,
+            measurement_time,
+            warm_up,
+        } => {
+            let stages = bench::Stage::all(&parts(part));
+            let samples = samples as usize;
+            let options = bench::Options {
+                warm_up,
+                measurement_time,
+                samples,
+                min_samples: samples.min(10),
+                threshold: threshold / 100.0,
+            };
+            let path = baseline.unwrap_or_else(bench::Baseline::default_path);
+            let mut baseline = match bench::Baseline::load(&path) {
+                Ok(baseline) => baseline,
+                Err(e) => {
+                    eprintln!("{e:#}");
+                    process::exit(1);
+                }
+            };
+
+            let mut regressions = 0;
+            for day in days(year, day, all) {
+                match bench::bench(day, &stages, &options, &mut baseline) {
+                    Ok(regressed) => regressions += regressed,
+                    Err(e) => {
+                        eprintln!("{} day {:02}: {e:#}", day.year, day.day);
+                        process::exit(1);
+                    }
+                }
+            }
+
+            if save_baseline {
+                if let Err(e) = baseline.save(&path) {
+                    eprintln!("{e:#}");
+                    process::exit(1);
+                }
+                println!("Saved baseline to {}", path.display());
+            }
+            if regressions > 0 {
+                println!("{regressions} stage(s) regressed by more than {threshold}%");
+                process::exit(1);
+            }
+        }
+        Command::Profile {
+            year,
+            day,
+            all,
+            part,
+            save_baseline,
+            baseline,
+        } => {
+            let stages = bench::Stage::all(&parts(part));
+            let path = baseline.unwrap_or_else(profile::default_path);
+            let mut baseline = match bench::Baseline::load(&path) {
+                Ok(baseline) => baseline,
+                Err(e) => {
+                    eprintln!("{e:#}");
+                    process::exit(1);
+                }
+            };
+
+            for day in days(year, day, all) {
+                if let Err(e) = profile::profile(day, &stages, &mut baseline) {
+                    eprintln!("{} day {:02}: {e:#}", day.year, day.day);
+                    process::exit(1);
+                }
+            }
+
+            if save_baseline {
+                if let Err(e) = baseline.save(&path) {
+                    eprintln!("{e:#}");
+                    process::exit(1);
+                }
+                println!("Saved baseline to {}", path.display());
+            }
+        }
+        Command::Fetch {
+            year,
+            day,
+            base_url,
+        } => {
+            if let Err(e) = fetch(year, day, base_url) {
+                eprintln!("{e:#}");
+                process::exit(1);
+            }
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            base_url,
+        } => {
+            let part = Part::try_from(part).expect("clap checks the range");
+            if let Err(e) = submit(year, day, part, answer, base_url) {
+                eprintln!("{e:#}");
+                process::exit(1);
+            }
+        }
+        Command::Animate {
+            year,
+            day,
+            part,
+            input,
+            preset,
+            params,
+            fps,
+            paused,
+        } => {
+            let part = Part::try_from(part).expect("clap checks the range");
+            let source = Source::from_arg(input.as_deref());
+            let overrides = overrides(preset, &params);
+            let options = animate::Options { fps, paused };
+            if let Err(e) = animate(year, day, part, &source, overrides, options) {
+                eprintln!("{e:#}");
+                process::exit(1);
+            }
+        }
+        Command::Export {
+            year,
+            day,
+            path,
+            input,
+            preset,
+            params,
+            scale,
+            part,
+            every,
+            max_frames,
+            fps,
+        } => {
+            let part = Part::try_from(part).expect("clap checks the range");
+            let source = Source::from_arg(input.as_deref());
+            let overrides = overrides(preset, &params);
+            let gif = pictures::GifOptions {
+                part,
+                every: every as usize,
+                max_frames: max_frames as usize,
+                scale: scale.map(|scale| scale.round().max(1.0) as usize),
+                fps,
+            };
+            if let Err(e) = export(year, day, &path, &source, overrides, scale, gif) {
+                eprintln!("{e:#}");
+                process::exit(1);
+            }
+        }
+        Command::Watch {
+            year,
+            day,
+            part,
+            input,
+            interval,
+        } => {
+            let Some(registered) = registry::find(year, day) else {
+                eprintln!("No solution is registered for {year} day {day}");
+                process::exit(1);
+            };
+            let options = watch::Options {
+                input,
+                part,
+                interval: Duration::from_millis(interval),
+            };
+            if let Err(e) = watch::watch(registered, &options) {
+                eprintln!("{e:#}");
+                process::exit(1);
+            }
+        }
+        Command::New { year, day } => {
+            if let Err(e) = new(year, day) {
+                eprintln!("{e:#}");
+                process::exit(1);
+            }
+        }
+    }
+}
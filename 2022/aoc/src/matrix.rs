@@ -0,0 +1,282 @@
+use anyhow::{bail, Context, Result};
+use common::{Answer, DynSolution, Part, Source};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::answers::Answers;
+use crate::params;
+use crate::registry::Day;
+use crate::verify::{self, Status, Summary};
+
+/// A day's other inputs, like those of other accounts: the `.txt` files in
+/// the `inputs/` directory of its crate, in name order.
+pub fn inputs(day: &Day) -> Result<Vec<PathBuf>> {
+    let dir = day.dir().join("inputs");
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut inputs = Vec::new();
+    for entry in fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// One of the inputs to solve a day for, and where its answers are recorded.
+struct Input {
+    name: String,
+    source: Source,
+    recorded: PathBuf,
+}
+
+/// Something about an input that doesn't fit in the table, printed after it.
+enum Note {
+    Wrong {
+        label: String,
+        expected: Answer,
+        got: Answer,
+    },
+    Failed {
+        label: String,
+        error: String,
+    },
+}
+
+impl Note {
+    fn print(&self) {
+        match self {
+            Note::Wrong {
+                label,
+                expected,
+                got,
+            } => {
+                println!("{label} is WRONG");
+                verify::report("    expected", expected);
+                verify::report("    got     ", got);
+            }
+            Note::Failed { label, error } => println!("{label} FAILED: {error}"),
+        }
+    }
+}
+
+/// How an answer fits in a cell of the table: pictures are too tall, so
+/// they're only counted.
+fn cell(answer: &Answer) -> String {
+    let answer = answer.to_string();
+    match answer.lines().count() {
+        0 | 1 => answer,
+        rows => format!("({rows}-row picture)"),
+    }
+}
+
+/// Line up the cells of `rows` in columns.
+fn table(rows: &[Vec<String>]) -> Vec<String> {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:width$}"))
+                .collect();
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Solve `parts` of the day for one input, tallying how the answers compare
+/// with the recorded ones. Returns the input's row of the table; anything
+/// that needs explaining is added to `notes`.
+fn solve(
+    day: &Day,
+    input: &Input,
+    parts: &[Part],
+    record: bool,
+    summary: &mut Summary,
+    notes: &mut Vec<Note>,
+) -> Result<Vec<String>> {
+    let Input {
+        name,
+        source,
+        recorded,
+    } = input;
+    let mut answers = Answers::read(recorded)?;
+    let overrides = params::sidecar(source)?;
+    let configured;
+    let solution: &dyn DynSolution = match params::find(day.year, day.day) {
+        Some(params) => {
+            configured = params.configure(&overrides)?;
+            configured.as_ref()
+        }
+        None if overrides.is_empty() => day.solution,
+        None => bail!("{source} has parameters, but there are none to set"),
+    };
+    let input = source.read(solution.embedded_input())?;
+
+    let mut row = vec![name.clone()];
+    let (parsed, elapsed) = verify::timed(|| solution.parse(&input));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            summary.failed += parts.len();
+            row.push("FAIL".to_string());
+            notes.push(Note::Failed {
+                label: format!("{name} parse"),
+                error,
+            });
+            return Ok(row);
+        }
+    };
+    row.push(format!("{elapsed:.2?}"));
+
+    let mut changed = false;
+    for &part in parts {
+        let (answer, elapsed) = verify::timed(|| solution.solve_parsed(parsed.as_ref(), part));
+        let label = format!("{name} part {part}");
+        let status = match Status::of(&answer, answers.get(part)) {
+            Status::Pass => {
+                summary.passed += 1;
+                "ok"
+            }
+            Status::Regression(expected) => {
+                summary.regressed += 1;
+                let got = answer.clone().expect("regressions have answers");
+                notes.push(Note::Wrong {
+                    label,
+                    expected,
+                    got,
+                });
+                "WRONG"
+            }
+            Status::Unrecorded if record => {
+                summary.unrecorded += 1;
+                answers.set(
+                    part,
+                    answer.clone().expect("only answers can be unrecorded"),
+                );
+                changed = true;
+                "recorded"
+            }
+            Status::Unrecorded => {
+                summary.unrecorded += 1;
+                "unrecorded"
+            }
+            Status::Fail(error) => {
+                summary.failed += 1;
+                notes.push(Note::Failed { label, error });
+                "FAIL"
+            }
+        };
+        row.push(answer.as_ref().map(cell).unwrap_or_default());
+        row.push(format!("{elapsed:.2?}"));
+        row.push(status.to_string());
+    }
+
+    if changed {
+        answers.write(recorded)?;
+    }
+    Ok(row)
+}
+
+/// Solve `parts` of a day for its own input and each of its other inputs,
+/// printing a table of the answers and timings with how each compares with
+/// the answer recorded for that input. With `record`, answers that aren't
+/// recorded yet are saved.
+pub fn matrix(day: &Day, parts: &[Part], record: bool, summary: &mut Summary) -> Result<()> {
+    let mut header = vec![
+        format!("{} day {:02}", day.year, day.day),
+        "parse".to_string(),
+    ];
+    for part in parts {
+        header.extend([format!("part {part}"), String::new(), String::new()]);
+    }
+    let mut rows = vec![header];
+    let mut notes = Vec::new();
+
+    let mut all = vec![Input {
+        name: "input.txt".to_string(),
+        source: Source::Embedded,
+        recorded: Answers::path(day),
+    }];
+    for path in inputs(day)? {
+        all.push(Input {
+            name: path
+                .strip_prefix(day.dir())
+                .unwrap_or(&path)
+                .display()
+                .to_string(),
+            recorded: Answers::path_for(&path),
+            source: Source::Path(path),
+        });
+    }
+    for input in &all {
+        rows.push(solve(day, input, parts, record, summary, &mut notes)?);
+    }
+
+    for line in table(&rows) {
+        println!("{line}");
+    }
+    for note in &notes {
+        note.print();
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_up_columns() {
+        let rows = vec![
+            vec![
+                "2022 day 15".to_string(),
+                "parse".to_string(),
+                "part 1".to_string(),
+            ],
+            vec![
+                "input.txt".to_string(),
+                "1.20ms".to_string(),
+                "5511201".to_string(),
+            ],
+            vec!["inputs/b.txt".to_string(), "FAIL".to_string()],
+        ];
+        assert_eq!(
+            table(&rows),
+            [
+                "2022 day 15   parse   part 1",
+                "input.txt     1.20ms  5511201",
+                "inputs/b.txt  FAIL"
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_the_rows_of_pictures() {
+        assert_eq!(cell(&Answer::Int(26)), "26");
+        assert_eq!(cell(&Answer::from("#.\n.#\n")), "(2-row picture)");
+    }
+
+    #[test]
+    fn finds_other_inputs() {
+        let day = crate::registry::find(2022, 1).unwrap();
+        assert!(inputs(day)
+            .unwrap()
+            .iter()
+            .all(|input| input.starts_with(day.dir().join("inputs"))));
+    }
+}
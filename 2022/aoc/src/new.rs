@@ -0,0 +1,273 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The files of a new day's crate, with `{{crate}}` and `{{Solution}}` to
+/// fill in.
+const TEMPLATES: [(&str, &str); 4] = [
+    ("Cargo.toml", include_str!("../templates/day/Cargo.toml")),
+    ("src/lib.rs", include_str!("../templates/day/lib.rs")),
+    ("src/main.rs", include_str!("../templates/day/main.rs")),
+    (
+        "tests/example.rs",
+        include_str!("../templates/day/example.rs"),
+    ),
+];
+
+/// How many crates the workspace lists on each line of its members.
+const MEMBERS_PER_LINE: usize = 10;
+
+/// Create the crate for a new day in the workspace at `root`, with a parser
+/// module, stubs for both parts and a skeleton example test, then add it to
+/// the workspace, the runner's dependencies and the registry. The example and
+/// input files start out empty. Nothing is written unless every step can be.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        bail!("There's no day {day}; puzzles run from day 1 to 25");
+    }
+    let krate = format!("day{day:02}");
+    let solution = format!("Day{day:02}");
+    let dir = root.join(&krate);
+    if dir.exists() {
+        bail!("{} already exists", dir.display());
+    }
+
+    // Work out every edit before making any of them
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("aoc").join("Cargo.toml");
+    let registry = root.join("aoc").join("src").join("registry.rs");
+    let edits = [
+        (&workspace, add_member(&read(&workspace)?, &krate)),
+        (&runner, add_dependency(&read(&runner)?, &krate)),
+        (
+            &registry,
+            add_registration(&read(&registry)?, year, day, &krate, &solution),
+        ),
+    ];
+    let mut edited = Vec::new();
+    for (path, edit) in edits {
+        let contents = edit.with_context(|| format!("failed to update {}", path.display()))?;
+        edited.push((path, contents));
+    }
+
+    for (file, template) in TEMPLATES {
+        write(&dir.join(file), &render(template, &krate, &solution))?;
+    }
+    write(&dir.join("example.txt"), "")?;
+    write(&dir.join("input.txt"), "")?;
+    for (path, contents) in edited {
+        write(path, &contents)?;
+    }
+    Ok(dir)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn render(template: &str, krate: &str, solution: &str) -> String {
+    template
+        .replace("{{crate}}", krate)
+        .replace("{{Solution}}", solution)
+}
+
+/// The day a `dayNN` crate name is for.
+fn day_of(name: &str) -> Option<u8> {
+    name.strip_prefix("day")?.parse().ok()
+}
+
+/// Insert `line` into `text` after the last line whose key (if it has one)
+/// sorts before `key`, or before the first keyed line if none do.
+fn insert_line<K: Ord>(
+    text: &str,
+    line: &str,
+    key: K,
+    key_of: impl Fn(&str) -> Option<K>,
+) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let keyed: Vec<(usize, K)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, key_of(line)?)))
+        .collect();
+    let at = match keyed.iter().rev().find(|(_, other)| *other < key) {
+        Some(&(idx, _)) => idx + 1,
+        None => keyed.first()?.0,
+    };
+
+    let mut lines = lines;
+    lines.insert(at, line);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Add a crate to the workspace's `members`, after the last day before it.
+/// Days are listed ten to a line, so it starts a new line if that one is
+/// full.
+fn add_member(manifest: &str, krate: &str) -> Result<String> {
+    let Some(day) = day_of(krate) else {
+        bail!("{krate} isn't a day's crate");
+    };
+    let members_of = |line: &str| -> Vec<u8> {
+        line.split(',')
+            .filter_map(|member| day_of(member.trim().trim_matches('"')))
+            .collect()
+    };
+
+    let mut lines: Vec<String> = manifest.lines().map(String::from).collect();
+    if lines.iter().any(|line| members_of(line).contains(&day)) {
+        bail!("{krate} is already a member of the workspace");
+    }
+    let Some(idx) = lines
+        .iter()
+        .rposition(|line| members_of(line).iter().any(|&other| other < day))
+    else {
+        bail!("there are no days in the workspace's members to add {krate} after");
+    };
+
+    if members_of(&lines[idx]).len() < MEMBERS_PER_LINE {
+        lines[idx].push_str(&format!(" \"{krate}\","));
+    } else {
+        let indent = &lines[idx][..lines[idx].len() - lines[idx].trim_start().len()];
+        let line = format!("{indent}\"{krate}\",");
+        lines.insert(idx + 1, line);
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add a day's crate to the runner's dependencies, among the other days.
+fn add_dependency(manifest: &str, krate: &str) -> Result<String> {
+    let dependency_of = |line: &str| day_of(line.split_once(" = ")?.0);
+    if manifest
+        .lines()
+        .any(|line| line.starts_with(&format!("{krate} = ")))
+    {
+        bail!("{krate} is already a dependency");
+    }
+    let day = day_of(krate).context("not a day's crate")?;
+    let line = format!("{krate} = {{ path = \"../{krate}\" }}");
+    insert_line(manifest, &line, day, dependency_of)
+        .with_context(|| format!("there are no days among the dependencies to add {krate} to"))
+}
+
+/// Register a day's solution, in year and day order.
+fn add_registration(
+    registry: &str,
+    year: u16,
+    day: u8,
+    krate: &str,
+    solution: &str,
+) -> Result<String> {
+    // Registrations look like `    2022 / 14 => day14::Day14,`
+    let registration_of = |line: &str| -> Option<(u16, u8)> {
+        let (date, _) = line.trim().split_once(" => ")?;
+        let (year, day) = date.split_once(" / ")?;
+        Some((year.parse().ok()?, day.parse().ok()?))
+    };
+    if registry
+        .lines()
+        .any(|line| registration_of(line) == Some((year, day)))
+    {
+        bail!("{year} day {day} is already registered");
+    }
+    let line = format!("    {year} / {day} => {krate}::{solution},");
+    insert_line(registry, &line, (year, day), registration_of)
+        .context("there are no registered days to add this one to")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const WORKSPACE: &str = r#"[workspace]
+resolver = "2"
+members = [
+    "aoc",
+    "common",
+    "day01", "day02", "day03", "day04", "day05", "day06", "day07", "day08", "day09", "day10",
+    "day11", "day12",
+]
+"#;
+
+    const RUNNER: &str = r#"[dependencies]
+anyhow = "1.0.68"
+common = { path = "../common" }
+day01 = { path = "../day01" }
+day02 = { path = "../day02" }
+day04 = { path = "../day04" }
+serde = { version = "1.0", features = ["derive"] }
+"#;
+
+    const REGISTRY: &str = "register! {
+    2022 / 1 => day01::Day01,
+    2022 / 2 => day02::Day02,
+    2022 / 4 => day04::Day04,
+}
+";
+
+    #[test]
+    fn adds_members_ten_to_a_line() {
+        let manifest = add_member(WORKSPACE, "day13").unwrap();
+        assert!(manifest.contains("\n    \"day11\", \"day12\", \"day13\",\n]"));
+
+        let full = WORKSPACE.replace("    \"day11\", \"day12\",\n", "");
+        let manifest = add_member(&full, "day11").unwrap();
+        assert!(manifest.contains("\"day10\",\n    \"day11\",\n]"));
+
+        assert!(add_member(WORKSPACE, "day12").is_err());
+    }
+
+    #[test]
+    fn adds_dependencies_and_registrations_in_day_order() {
+        let manifest = add_dependency(RUNNER, "day03").unwrap();
+        assert!(manifest
+            .contains("day02 = { path = \"../day02\" }\nday03 = { path = \"../day03\" }\nday04"));
+
+        let registry = add_registration(REGISTRY, 2022, 3, "day03", "Day03").unwrap();
+        assert!(registry.contains("Day02,\n    2022 / 3 => day03::Day03,\n    2022 / 4"));
+
+        assert!(add_dependency(RUNNER, "day04").is_err());
+        assert!(add_registration(REGISTRY, 2022, 4, "day04", "Day04").is_err());
+    }
+
+    #[test]
+    fn scaffolds_a_day_and_wires_it_in() {
+        let root = env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write(&root.join("Cargo.toml"), WORKSPACE).unwrap();
+        write(&root.join("aoc/Cargo.toml"), RUNNER).unwrap();
+        write(&root.join("aoc/src/registry.rs"), REGISTRY).unwrap();
+
+        let dir = new_day(&root, 2022, 13).unwrap();
+        assert_eq!(dir, root.join("day13"));
+        let lib = read(&dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("impl Solution for Day13 {"));
+        assert!(lib.contains("include_str!(\"../input.txt\")"));
+        let main = read(&dir.join("src/main.rs")).unwrap();
+        assert!(main.contains("common::main(&day13::Day13);"));
+        assert!(read(&dir.join("tests/example.rs"))
+            .unwrap()
+            .contains("use day13::Day13;"));
+        assert!(read(&dir.join("Cargo.toml"))
+            .unwrap()
+            .contains("name = \"day13\""));
+        assert_eq!(read(&dir.join("input.txt")).unwrap(), "");
+        assert!(read(&root.join("aoc/src/registry.rs"))
+            .unwrap()
+            .contains("2022 / 13 => day13::Day13,"));
+
+        // A second attempt changes nothing
+        assert!(new_day(&root, 2022, 13).is_err());
+        assert!(new_day(&root, 2022, 26).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::{anyhow, bail, Context, Result};
+use common::{DynConfigure, Overrides, Source};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The days with puzzle parameters, by year and day.
+const CONFIGURABLE: &[(u16, u8, &dyn DynConfigure)] = &[
+    (2022, 7, &day07::Day07::PUZZLE),
+    (2022, 14, &day14::Day14::PUZZLE),
+    (2022, 15, &day15::Day15::PUZZLE),
+    (2022, 16, &day16::Day16::PUZZLE),
+    (2022, 17, &day17::Day17::PUZZLE),
+    (2022, 20, &day20::Day20::PUZZLE),
+];
+
+/// Look up the parameters for a specific day, if it has any.
+pub fn find(year: u16, day: u8) -> Option<&'static dyn DynConfigure> {
+    CONFIGURABLE
+        .iter()
+        .find(|&&(y, d, _)| y == year && d == day)
+        .map(|&(_, _, params)| params)
+}
+
+/// Every day with puzzle parameters, as `(year, day)`.
+pub fn days() -> impl Iterator<Item = (u16, u8)> {
+    CONFIGURABLE.iter().map(|&(year, day, _)| (year, day))
+}
+
+/// `solution`, for a day without puzzle parameters, as long as `overrides`
+/// don't try to set any.
+pub fn unconfigurable<S>(solution: S, overrides: &Overrides) -> Result<S> {
+    if !overrides.is_empty() {
+        bail!("There are no puzzle parameters to set");
+    }
+    Ok(solution)
+}
+
+/// Read a `--param` argument, like `row=10`.
+pub fn parse_override(arg: &str) -> Result<(String, String)> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected a parameter like name=value, found {arg:?}"))?;
+    Ok((name.trim().to_string(), value.to_string()))
+}
+
+/// The sidecar file for an input file, with the parameters it should be
+/// solved with: `example.params.toml` for `example.txt`.
+pub fn sidecar_path(input: &Path) -> PathBuf {
+    input.with_extension("params.toml")
+}
+
+/// The settings in the sidecar of `source`, if it's a file that has one. A
+/// sidecar holds a `preset` to start from and values for single parameters:
+///
+/// ```toml
+/// preset = "example"
+/// row = 10
+/// ```
+pub fn sidecar(source: &Source) -> Result<Overrides> {
+    let Source::Path(input) = source else {
+        return Ok(Overrides::default());
+    };
+    let path = sidecar_path(input);
+    if !path.exists() {
+        return Ok(Overrides::default());
+    }
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    parse_sidecar(&contents).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn parse_sidecar(contents: &str) -> Result<Overrides> {
+    let table: toml::Table = toml::from_str(contents)?;
+    let mut overrides = Overrides::default();
+    for (name, value) in table {
+        let value = match value {
+            toml::Value::String(s) => s,
+            toml::Value::Integer(n) => n.to_string(),
+            toml::Value::Float(x) => x.to_string(),
+            toml::Value::Boolean(b) => b.to_string(),
+            other => bail!("{name} should be a string or a number, not {other}"),
+        };
+        if name == "preset" {
+            overrides.preset = Some(value);
+        } else {
+            overrides.values.push((name, value));
+        }
+    }
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_sidecars() {
+        let overrides = parse_sidecar("preset = \"example\"\nrow = 10\nstart = \"BB\"\n").unwrap();
+        assert_eq!(overrides.preset.as_deref(), Some("example"));
+        assert_eq!(
+            overrides.values,
+            [
+                ("row".to_string(), "10".to_string()),
+                ("start".to_string(), "BB".to_string())
+            ]
+        );
+        assert!(parse_sidecar("row = [10]\n").is_err());
+        assert_eq!(
+            sidecar_path(Path::new("day15/example.txt")),
+            Path::new("day15/example.params.toml")
+        );
+    }
+
+    #[test]
+    fn reads_overrides() {
+        assert_eq!(
+            parse_override("entrypoint=500,0").unwrap(),
+            ("entrypoint".to_string(), "500,0".to_string())
+        );
+        assert!(parse_override("row").is_err());
+    }
+
+    #[test]
+    fn bundled_example_sidecars_configure_their_day() {
+        for (year, day) in days() {
+            let dir = crate::registry::find(year, day).unwrap().dir();
+            let source = Source::Path(dir.join("example.txt"));
+            let overrides = sidecar(&source).unwrap();
+            assert!(find(year, day).unwrap().configure(&overrides).is_ok());
+            // Only day 15's example is solved with different parameters
+            let preset = (day == 15).then_some("example");
+            assert_eq!(overrides.preset.as_deref(), preset, "day {day}");
+        }
+    }
+}
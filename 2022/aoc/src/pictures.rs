@@ -0,0 +1,120 @@
+use crate::params::unconfigurable;
+use animate::{DynAnimate, Frame, Hook};
+use anyhow::{bail, Context, Result};
+use common::{Answer, Configure, Overrides, Part};
+use export::{DynExport, Raster};
+use std::fs::File;
+use std::io::BufWriter;
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::time::Duration;
+
+/// Make a day's picture with overrides applied to its puzzle parameters.
+pub type MakePicture = fn(&Overrides) -> Result<Box<dyn DynExport>>;
+
+/// The days with a picture to export, by year and day.
+const PICTURES: &[(u16, u8, MakePicture)] = &[
+    (2022, 10, |o| Ok(Box::new(unconfigurable(day10::Day10, o)?))),
+    (2022, 12, |o| Ok(Box::new(unconfigurable(day12::Day12, o)?))),
+    (2022, 14, |o| Ok(Box::new(day14::Day14::PUZZLE.apply(o)?))),
+    (2022, 15, |o| Ok(Box::new(day15::Day15::PUZZLE.apply(o)?))),
+    (2022, 17, |o| Ok(Box::new(day17::Day17::PUZZLE.apply(o)?))),
+];
+
+/// Look up the picture for a specific day, if it has one.
+pub fn find(year: u16, day: u8) -> Option<MakePicture> {
+    PICTURES
+        .iter()
+        .find(|&&(y, d, _)| y == year && d == day)
+        .map(|&(_, _, make)| make)
+}
+
+/// Every day with a picture to export, as `(year, day)`.
+pub fn days() -> impl Iterator<Item = (u16, u8)> {
+    PICTURES.iter().map(|&(year, day, _)| (year, day))
+}
+
+/// Which part's simulation to put in a GIF, which of its frames, and how to
+/// draw them.
+pub struct GifOptions {
+    pub part: Part,
+    /// Keep one frame in this many.
+    pub every: usize,
+    /// Stop after keeping this many frames.
+    pub max_frames: usize,
+    /// Pixels to a character, or enough for a GIF about 800 pixels wide.
+    pub scale: Option<usize>,
+    pub fps: f64,
+}
+
+/// Draw the picture of `input` and save it to `path`, `scale` pixels to the
+/// unit (or the picture's default).
+pub fn save_picture(
+    picture: &dyn DynExport,
+    input: &str,
+    path: &Path,
+    scale: Option<f64>,
+) -> Result<()> {
+    let image = picture.picture(input)?;
+    let scale = scale.unwrap_or_else(|| image.default_scale());
+    image.save(path, scale)
+}
+
+/// Watch one part of a day's simulation solve `input`, saving its frames to
+/// `path` as a GIF. Returns the answer and how many frames were kept.
+pub fn save_gif(
+    animation: &dyn DynAnimate,
+    input: &str,
+    path: &Path,
+    options: &GifOptions,
+) -> Result<(Answer, usize)> {
+    let part = options.part;
+    let mut frames: Vec<Frame> = Vec::new();
+    let answer = {
+        let mut seen = 0;
+        let mut watcher = |frame: Frame| {
+            if seen % options.every == 0 {
+                frames.push(frame);
+            }
+            seen += 1;
+            if frames.len() < options.max_frames {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        };
+        animation.animate(input, part, &mut Hook::new(&mut watcher))?
+    };
+    if frames.is_empty() {
+        bail!("Part {part} didn't draw any frames");
+    }
+
+    // Every frame is drawn on a canvas big enough for the biggest
+    let size = (
+        frames.iter().map(Frame::width).max().unwrap_or(0).max(1),
+        frames
+            .iter()
+            .map(|frame| frame.rows.len())
+            .max()
+            .unwrap_or(0)
+            .max(1),
+    );
+    let scale = options
+        .scale
+        .unwrap_or_else(|| (800 / size.0).min(800 / size.1).max(1));
+    // The frames are drawn one at a time as they're written, and all the
+    // same size, so only the first can turn out too big to draw
+    let mut rasters = frames
+        .iter()
+        .map(|frame| Raster::from_chars(&frame.rows, size, scale, export::glyph_colour));
+    let first = rasters.next().expect("there are frames")?;
+    let rasters = std::iter::once(first)
+        .chain(rasters.map(|raster| raster.expect("every frame is as big as the first")));
+
+    let file =
+        File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    let delay = Duration::from_secs_f64(1.0 / options.fps);
+    export::write_gif(rasters, delay, BufWriter::new(file))
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok((answer, frames.len()))
+}
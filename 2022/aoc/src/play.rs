@@ -0,0 +1,46 @@
+use crate::params::unconfigurable;
+use animate::{DynAnimate, Hook, Options, Player};
+use anyhow::Result;
+use common::{Answer, Configure, Overrides, Part};
+
+/// Make a day's simulation with overrides applied to its puzzle parameters.
+pub type MakeAnimation = fn(&Overrides) -> Result<Box<dyn DynAnimate>>;
+
+/// The days with a simulation to watch, by year and day.
+const ANIMATIONS: &[(u16, u8, MakeAnimation)] = &[
+    (2022, 5, |o| Ok(Box::new(unconfigurable(day05::Day05, o)?))),
+    (2022, 9, |o| Ok(Box::new(unconfigurable(day09::Day09, o)?))),
+    (2022, 10, |o| Ok(Box::new(unconfigurable(day10::Day10, o)?))),
+    (2022, 14, |o| Ok(Box::new(day14::Day14::PUZZLE.apply(o)?))),
+    (2022, 17, |o| Ok(Box::new(day17::Day17::PUZZLE.apply(o)?))),
+];
+
+/// Look up the simulation for a specific day, if it has one.
+pub fn find(year: u16, day: u8) -> Option<MakeAnimation> {
+    ANIMATIONS
+        .iter()
+        .find(|&&(y, d, _)| y == year && d == day)
+        .map(|&(_, _, make)| make)
+}
+
+/// Every day with a simulation to watch, as `(year, day)`.
+pub fn days() -> impl Iterator<Item = (u16, u8)> {
+    ANIMATIONS.iter().map(|&(year, day, _)| (year, day))
+}
+
+/// Watch one part of a day's simulation solve `input` in the terminal,
+/// returning the answer it comes to and how many frames it took. The
+/// terminal is back to normal by the time it returns.
+pub fn play(
+    animation: &dyn DynAnimate,
+    input: &str,
+    part: Part,
+    options: Options,
+) -> Result<(Answer, usize)> {
+    let mut player = Player::start(options)?;
+    let answer = {
+        let mut watcher = |frame| player.show(frame);
+        animation.animate(input, part, &mut Hook::new(&mut watcher))
+    };
+    Ok((answer?, player.frames()))
+}
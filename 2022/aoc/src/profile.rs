@@ -0,0 +1,239 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use crate::bench::{Baseline, Stage};
+use crate::registry::Day;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+/// The bytes allocated and not yet freed.
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+/// The most bytes that have been live at once since `measure` last reset it.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting what goes through it. The runner only
+/// uses it when it's built with the `alloc-profile` feature, so that the
+/// counting doesn't slow everything else down.
+#[cfg_attr(not(feature = "alloc-profile"), allow(dead_code))]
+pub struct Counting;
+
+#[cfg_attr(not(feature = "alloc-profile"), allow(dead_code))]
+impl Counting {
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+// Safety: every call is passed straight on to the system allocator.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Counting::freed(layout.size());
+    }
+
+    /// A reallocation counts as allocating a block of the new size and
+    /// freeing the old one, since that's what growing a `Vec` costs.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            Counting::allocated(new_size);
+            Counting::freed(layout.size());
+        }
+        new
+    }
+}
+
+/// Whether the runner was built to count its allocations.
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-profile")
+}
+
+/// What a stage allocated on the heap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    /// How many allocations (and reallocations) it made.
+    pub allocations: u64,
+    /// How many bytes those asked for in all.
+    pub bytes: u64,
+    /// The most bytes it had live at once, over what was live before it.
+    pub peak: u64,
+}
+
+/// Run `f`, counting what it allocates.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let result = f();
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live) as u64,
+    };
+    (result, usage)
+}
+
+/// Where allocation baselines live unless another path is given.
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/aoc-profile/baseline.toml")
+}
+
+/// A number of bytes in the largest binary unit that keeps it above 1.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// How `usage` differs from the baseline's, by the measures that changed.
+fn change(baseline: Option<&Usage>, usage: &Usage) -> String {
+    let Some(baseline) = baseline else {
+        return String::new();
+    };
+    let measures = [
+        ("allocations", baseline.allocations, usage.allocations),
+        ("bytes", baseline.bytes, usage.bytes),
+        ("peak", baseline.peak, usage.peak),
+    ];
+    let changes: Vec<String> = measures
+        .iter()
+        .filter(|(_, before, now)| before != now)
+        .map(|&(name, before, now)| {
+            if before == 0 {
+                format!("{name} from 0")
+            } else {
+                format!("{name} {:+.1}%", (now as f64 / before as f64 - 1.0) * 100.0)
+            }
+        })
+        .collect();
+    if changes.is_empty() {
+        "unchanged".to_string()
+    } else {
+        changes.join(", ")
+    }
+}
+
+/// Count the allocations of the stages of a day against its embedded input,
+/// printing each with how it compares with `baseline`. The new counts are
+/// written into `baseline` but it's up to the caller whether to save them.
+pub fn profile(day: &Day, stages: &[Stage], baseline: &mut Baseline<Usage>) -> Result<()> {
+    if !enabled() {
+        bail!("Counting allocations needs the runner built with `--features alloc-profile`");
+    }
+    let input = day.solution.embedded_input();
+    let (parsed, parse) = measure(|| day.solution.parse(input));
+    let parsed = parsed?;
+
+    for &stage in stages {
+        let usage = match stage {
+            Stage::Parse => parse,
+            Stage::Part(part) => {
+                let (answer, usage) = measure(|| day.solution.solve_parsed(parsed.as_ref(), part));
+                answer.with_context(|| format!("part {part} failed"))?;
+                usage
+            }
+        };
+
+        let line = format!(
+            "{} day {:02} {:<6} {:>10} allocations {:>10} {:>10} peak  {}",
+            day.year,
+            day.day,
+            stage.to_string(),
+            usage.allocations,
+            format_bytes(usage.bytes),
+            format_bytes(usage.peak),
+            change(baseline.get(day, stage), &usage),
+        );
+        println!("{}", line.trim_end());
+
+        baseline.set(day, stage, usage);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_what_goes_through_it() {
+        let layout = Layout::array::<u64>(16).unwrap();
+        let ((), usage) = measure(|| unsafe {
+            let ptr = Counting.alloc(layout);
+            let ptr = Counting.realloc(ptr, layout, 256);
+            Counting.dealloc(ptr, Layout::from_size_align(256, layout.align()).unwrap());
+        });
+        // Only the test's own calls count unless the runner was built to
+        // count everything
+        if !enabled() {
+            assert_eq!(
+                usage,
+                Usage {
+                    allocations: 2,
+                    bytes: 384,
+                    peak: 384,
+                }
+            );
+        }
+        assert!(usage.allocations >= 2 && usage.peak >= 256);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(1000), "1000 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn describes_changes_from_the_baseline() {
+        let before = Usage {
+            allocations: 100,
+            bytes: 4096,
+            peak: 1024,
+        };
+        let after = Usage {
+            allocations: 50,
+            ..before
+        };
+        assert_eq!(change(None, &after), "");
+        assert_eq!(change(Some(&before), &before), "unchanged");
+        assert_eq!(change(Some(&before), &after), "allocations -50.0%");
+    }
+}
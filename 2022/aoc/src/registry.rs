@@ -0,0 +1,69 @@
+use common::DynSolution;
+use std::path::Path;
+
+/// A single puzzle solution the runner knows how to invoke.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub solution: &'static dyn DynSolution,
+    dir: &'static str,
+}
+
+impl Day {
+    /// The day's crate directory, where its recorded answers live.
+    pub fn dir(&self) -> &'static Path {
+        Path::new(self.dir)
+    }
+}
+
+/// Registers each day's `Solution` under its year and day.
+macro_rules! register {
+    ($($year:literal / $day:literal => $krate:ident :: $($solution:ident)::+),* $(,)?) => {
+        const DAYS: &[Day] = &[
+            $(Day {
+                year: $year,
+                day: $day,
+                solution: &$krate::$($solution)::+,
+                dir: concat!(env!("CARGO_MANIFEST_DIR"), "/../", stringify!($krate)),
+            },)*
+        ];
+    };
+}
+
+register! {
+    2022 / 1 => day01::Day01,
+    2022 / 2 => day02::Day02,
+    2022 / 3 => day03::Day03,
+    2022 / 4 => day04::Day04,
+    2022 / 5 => day05::Day05,
+    2022 / 6 => day06::Day06,
+    2022 / 7 => day07::Day07::PUZZLE,
+    2022 / 8 => day08::Day08,
+    2022 / 9 => day09::Day09,
+    2022 / 10 => day10::Day10,
+    2022 / 11 => day11::Day11,
+    2022 / 12 => day12::Day12,
+    2022 / 13 => day13::Day13,
+    2022 / 14 => day14::Day14::PUZZLE,
+    2022 / 15 => day15::Day15::PUZZLE,
+    2022 / 16 => day16::Day16::PUZZLE,
+    2022 / 17 => day17::Day17::PUZZLE,
+    2022 / 18 => day18::Day18,
+    2022 / 19 => day19::Day19,
+    2022 / 20 => day20::Day20::PUZZLE,
+}
+
+/// The workspace the days' crates live in.
+pub fn workspace_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+}
+
+/// Look up the solution for a specific day.
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+/// Every registered solution for a year, in day order.
+pub fn year(year: u16) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(move |d| d.year == year)
+}
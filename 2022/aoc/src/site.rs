@@ -0,0 +1,278 @@
+use anyhow::{bail, Context, Result};
+use common::Part;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Where the puzzles are served from, unless configured otherwise.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to the Advent of Code site, read from
+/// `~/.config/aoc/config.toml` (or `$AOC_CONFIG`):
+///
+/// ```toml
+/// session = "53616c746564..."
+/// base_url = "http://localhost:8080"   # optional
+/// ```
+///
+/// `AOC_SESSION` and `AOC_BASE_URL` override the file.
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// The `session` cookie from a logged-in browser.
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl Config {
+    /// The config file's location, if there's anywhere to look for one.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("AOC_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_home.join("aoc").join("config.toml"))
+    }
+
+    /// Read the config file, if there is one, then apply the environment
+    /// variables over it.
+    pub fn load() -> Result<Config> {
+        let mut config = match Config::path() {
+            Some(path) if path.exists() => {
+                let contents = fs::read_to_string(&path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                toml::from_str(&contents)
+                    .with_context(|| format!("failed to parse {}", path.display()))?
+            }
+            _ => Config::default(),
+        };
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = Some(base_url);
+        }
+        Ok(config)
+    }
+}
+
+/// A logged-in client for the Advent of Code site.
+pub struct Site {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Site {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Site {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(concat!("aoc-runner/", env!("CARGO_PKG_VERSION")))
+                .build(),
+        }
+    }
+
+    /// Connect with the configured session, failing with a hint on where to
+    /// put one if there isn't any.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let Some(session) = config.session.as_deref().filter(|s| !s.trim().is_empty()) else {
+            let file = Config::path().map_or("a config file".to_string(), |path| {
+                path.display().to_string()
+            });
+            bail!(
+                "No session cookie configured. Set AOC_SESSION, or add \
+                 `session = \"...\"` to {file}, using the `session` cookie from \
+                 a browser that's logged in to Advent of Code"
+            );
+        };
+        let base_url = config.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL);
+        Ok(Site::new(base_url, session))
+    }
+
+    /// Download the puzzle input for a day.
+    pub fn input(&self, year: u16, day: u8) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        let input = self.body(response, &url, year, day)?;
+        if input.is_empty() {
+            bail!("{url} sent back an empty input");
+        }
+        Ok(input)
+    }
+
+    /// Submit an answer to one part of a day's puzzle, returning the page the
+    /// site replies with.
+    pub fn answer(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        self.body(response, &url, year, day)
+    }
+
+    fn body(
+        &self,
+        response: Result<ureq::Response, ureq::Error>,
+        url: &str,
+        year: u16,
+        day: u8,
+    ) -> Result<String> {
+        match response {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("failed to read the response from {url}")),
+            // The site answers a missing or stale session with 400 ("Puzzle
+            // inputs differ by user. Please log in to get your puzzle input.")
+            Err(ureq::Error::Status(400 | 401 | 403, _)) => bail!(
+                "The session cookie was rejected, so it has probably expired. \
+                 Log in again and update it"
+            ),
+            Err(ureq::Error::Status(404, _)) => {
+                bail!("{year} day {day} isn't unlocked yet (or doesn't exist)")
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                bail!("{url} answered {status} {}", response.status_text())
+            }
+            Err(e) => Err(e).with_context(|| format!("failed to reach {}", self.base_url)),
+        }
+    }
+}
+
+/// A stand-in for the site, serving one canned response per connection.
+#[cfg(test)]
+pub(crate) mod stand_in {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    /// A request the stand-in received: the request line, the headers, and
+    /// the body.
+    #[derive(Debug)]
+    pub struct Request {
+        pub line: String,
+        pub headers: Vec<String>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers.iter().find_map(|header| {
+                let (key, value) = header.split_once(':')?;
+                key.eq_ignore_ascii_case(name).then(|| value.trim())
+            })
+        }
+    }
+
+    /// Serve `responses` in order, as `(status, body)`, returning the base
+    /// URL to send requests to and the requests as they arrive.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (requests, received) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut headers = Vec::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end().to_string();
+                    if header.is_empty() {
+                        break;
+                    }
+                    headers.push(header);
+                }
+                let mut request = Request {
+                    line: line.trim_end().to_string(),
+                    headers,
+                    body: String::new(),
+                };
+                let length = request
+                    .header("Content-Length")
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.body = String::from_utf8(content).unwrap();
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Canned\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+                requests.send(request).unwrap();
+            }
+        });
+
+        (base_url, received)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downloads_the_input_with_the_session_cookie() {
+        let (base_url, requests) = stand_in::serve(vec![(200, "1\n2\n3\n")]);
+        let site = Site::new(&format!("{base_url}/"), "abc123\n");
+
+        assert_eq!(site.input(2022, 1).unwrap(), "1\n2\n3\n");
+        let request = requests.recv().unwrap();
+        assert_eq!(request.line, "GET /2022/day/1/input HTTP/1.1");
+        assert_eq!(request.header("Cookie"), Some("session=abc123"));
+    }
+
+    #[test]
+    fn explains_expired_sessions_and_locked_days() {
+        let (base_url, _requests) = stand_in::serve(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!\n",
+            ),
+        ]);
+        let site = Site::new(&base_url, "stale");
+
+        let expired = site.input(2022, 1).unwrap_err().to_string();
+        assert!(expired.contains("expired"), "{expired}");
+        let locked = site.input(2022, 25).unwrap_err().to_string();
+        assert_eq!(locked, "2022 day 25 isn't unlocked yet (or doesn't exist)");
+    }
+
+    #[test]
+    fn posts_answers_as_a_form() {
+        let (base_url, requests) = stand_in::serve(vec![(200, "<article>ok</article>")]);
+        let site = Site::new(&base_url, "abc123");
+
+        let page = site.answer(2022, 3, Part::Two, "2609").unwrap();
+        assert_eq!(page, "<article>ok</article>");
+        let request = requests.recv().unwrap();
+        assert_eq!(request.line, "POST /2022/day/3/answer HTTP/1.1");
+        assert_eq!(request.header("Cookie"), Some("session=abc123"));
+        assert_eq!(request.body, "level=2&answer=2609");
+    }
+}
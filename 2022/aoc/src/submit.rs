@@ -0,0 +1,299 @@
+use anyhow::{bail, Result};
+use common::{Answer, Part};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+use crate::attempts::{Attempt, Attempts};
+use crate::site::Site;
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer came too soon after the last one, so it wasn't checked.
+    Wait,
+    /// The part was already solved, or isn't open yet.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Whether the site checked the answer and turned it down.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = match self {
+            Outcome::Right => "right",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wait => "too soon",
+            Outcome::WrongLevel => "for a part that's solved or still locked",
+        };
+        write!(f, "{outcome}")
+    }
+}
+
+/// The site's reply to an answer.
+#[derive(Debug, PartialEq)]
+pub struct Reply {
+    pub outcome: Outcome,
+    /// How many seconds to wait before the next answer, if the site said.
+    pub wait: Option<u64>,
+    /// The text of the reply, without the markup.
+    pub message: String,
+}
+
+impl Reply {
+    /// Read the reply out of the page the site answers with. The verdict is
+    /// the `<article>` in the page, in a handful of known phrasings.
+    pub fn parse(page: &str) -> Result<Reply> {
+        let article = match page.split_once("<article>") {
+            Some((_, rest)) => rest.split_once("</article>").map_or(rest, |(text, _)| text),
+            None => page,
+        };
+        let message = text_of(article);
+        let message = match message.split_once(" [Return to") {
+            Some((message, _)) => message.to_string(),
+            None => message,
+        };
+
+        let outcome = if message.starts_with("That's the right answer") {
+            Outcome::Right
+        } else if message.starts_with("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if message.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if message.starts_with("You gave an answer too recently") {
+            Outcome::Wait
+        } else if message.starts_with("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            bail!("The site's reply wasn't one this understands: {message:?}");
+        };
+
+        let wait = match outcome {
+            Outcome::Wait => between(&message, "You have ", " left to wait").and_then(duration),
+            _ => between(&message, "wait ", " before trying again").and_then(minutes),
+        };
+        Ok(Reply {
+            outcome,
+            wait,
+            message,
+        })
+    }
+}
+
+/// The text of some HTML, with the tags dropped and the whitespace collapsed.
+fn text_of(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn between<'a>(text: &'a str, before: &str, after: &str) -> Option<&'a str> {
+    let (_, rest) = text.split_once(before)?;
+    Some(rest.split_once(after)?.0)
+}
+
+/// Seconds in a wait like "1m 5s" or "34s".
+fn duration(text: &str) -> Option<u64> {
+    text.split_whitespace()
+        .map(|part| {
+            let unit = match part.chars().last()? {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+            Some(part[..part.len() - 1].parse::<u64>().ok()? * unit)
+        })
+        .sum()
+}
+
+/// Seconds in a wait like "one minute" or "5 minutes".
+fn minutes(text: &str) -> Option<u64> {
+    let (count, unit) = text.split_once(' ')?;
+    if !unit.starts_with("minute") {
+        return None;
+    }
+    let count = match count {
+        "one" => 1,
+        count => count.parse().ok()?,
+    };
+    Some(count * 60)
+}
+
+/// Submit an answer unless the earlier attempts already rule it out, and log
+/// the attempt along with the site's reply.
+pub fn submit(
+    site: &Site,
+    attempts: &mut Attempts,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &Answer,
+    now: u64,
+) -> Result<Reply> {
+    if let Some(refusal) = attempts.refusal(part, answer, now) {
+        bail!("Not submitting {answer}: {refusal}");
+    }
+    if answer.to_string().contains('\n') {
+        bail!(
+            "The answer is a picture, not the text in it; read the letters off it and \
+             submit those with --answer:\n{answer}"
+        );
+    }
+
+    let page = site.answer(year, day, part, &answer.to_string())?;
+    let reply = Reply::parse(&page)?;
+    attempts.record(Attempt {
+        part: part.into(),
+        answer: answer.clone(),
+        outcome: reply.outcome,
+        at: now,
+        retry_at: reply.wait.map(|wait| now + wait),
+    });
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::site::stand_in;
+
+    const RIGHT: &str = r#"<main>
+<article><p>That's the right answer!  You are <span class="day-success">one gold star</span> closer to collecting enough star fruit.  [<a href="/2022/day/1#part2">Continue to Part Two</a>]</p></article>
+</main>"#;
+    const TOO_HIGH: &str = r#"<main>
+<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href="/2022/about">about page</a>, or you can ask for hints on the <a href="https://www.reddit.com/r/adventofcode/" target="_blank">subreddit</a>.  Please wait one minute before trying again. [<a href="/2022/day/1">Return to Day 1</a>]</p></article>
+</main>"#;
+    const WRONG: &str = r#"<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.  Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again. [<a href="/2022/day/1">Return to Day 1</a>]</p></article>"#;
+    const WAIT: &str = r#"<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. [<a href="/2022/day/1">Return to Day 1</a>]</p></article>"#;
+    const WRONG_LEVEL: &str = r#"<article><p>You don't seem to be solving the right level.  Did you already complete it? [<a href="/2022/day/1">Return to Day 1</a>]</p></article>"#;
+
+    #[test]
+    fn reads_the_verdict_and_the_wait() {
+        let reply = |page| {
+            let reply = Reply::parse(page).unwrap();
+            (reply.outcome, reply.wait)
+        };
+        assert_eq!(reply(RIGHT), (Outcome::Right, None));
+        assert_eq!(reply(TOO_HIGH), (Outcome::TooHigh, Some(60)));
+        assert_eq!(reply(WRONG), (Outcome::Wrong, Some(300)));
+        assert_eq!(reply(WAIT), (Outcome::Wait, Some(65)));
+        assert_eq!(reply(WRONG_LEVEL), (Outcome::WrongLevel, None));
+
+        assert_eq!(
+            Reply::parse(WAIT).unwrap().message,
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again. You have 1m 5s left to wait."
+        );
+        assert!(Reply::parse("<article><p>Huh?</p></article>").is_err());
+    }
+
+    #[test]
+    fn logs_attempts_and_refuses_ruled_out_answers() {
+        let (base_url, requests) = stand_in::serve(vec![(200, TOO_HIGH), (200, RIGHT)]);
+        let site = Site::new(&base_url, "abc123");
+        let mut attempts = Attempts::default();
+
+        let reply = submit(
+            &site,
+            &mut attempts,
+            2022,
+            1,
+            Part::One,
+            &Answer::Int(900),
+            1000,
+        );
+        assert_eq!(reply.unwrap().outcome, Outcome::TooHigh);
+        assert_eq!(requests.recv().unwrap().body, "level=1&answer=900");
+
+        // Too soon, and then too high, without asking the site
+        let error = submit(
+            &site,
+            &mut attempts,
+            2022,
+            1,
+            Part::One,
+            &Answer::Int(800),
+            1030,
+        );
+        assert!(error.unwrap_err().to_string().contains("wait another 30s"));
+        let error = submit(
+            &site,
+            &mut attempts,
+            2022,
+            1,
+            Part::One,
+            &Answer::Int(901),
+            2000,
+        );
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Not submitting 901: it's too high, since 900 already was"
+        );
+        assert!(requests.try_recv().is_err());
+
+        let reply = submit(
+            &site,
+            &mut attempts,
+            2022,
+            1,
+            Part::One,
+            &Answer::Int(800),
+            2000,
+        );
+        assert_eq!(reply.unwrap().outcome, Outcome::Right);
+        assert_eq!(requests.recv().unwrap().body, "level=1&answer=800");
+        let error = submit(
+            &site,
+            &mut attempts,
+            2022,
+            1,
+            Part::One,
+            &Answer::Int(800),
+            3000,
+        );
+        assert!(error.unwrap_err().to_string().contains("already solved"));
+    }
+
+    #[test]
+    fn refuses_pictures() {
+        let (base_url, requests) = stand_in::serve(vec![]);
+        let site = Site::new(&base_url, "abc123");
+        let crt = Answer::from("#..#\n####\n#..#");
+
+        let error = submit(
+            &site,
+            &mut Attempts::default(),
+            2022,
+            10,
+            Part::Two,
+            &crt,
+            0,
+        );
+        assert!(error.unwrap_err().to_string().contains("--answer"));
+        assert!(requests.try_recv().is_err());
+    }
+}
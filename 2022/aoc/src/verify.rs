@@ -0,0 +1,146 @@
+use anyhow::Result;
+use common::{Answer, Part};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::answers::Answers;
+use crate::registry::Day;
+
+/// How a part's answer compares with the recorded one.
+pub enum Status {
+    Pass,
+    /// The solution ran, but gave a different answer from the recorded one.
+    Regression(Answer),
+    /// There's no recorded answer to compare against.
+    Unrecorded,
+    /// The solution failed to produce an answer at all.
+    Fail(String),
+}
+
+impl Status {
+    /// Compare what a part gave with the answer recorded for it, if any.
+    pub fn of(answer: &Result<Answer, String>, expected: Option<&Answer>) -> Status {
+        match (answer, expected) {
+            (Err(e), _) => Status::Fail(e.clone()),
+            (Ok(answer), Some(expected)) if answer == expected => Status::Pass,
+            (Ok(_), Some(expected)) => Status::Regression(expected.clone()),
+            (Ok(_), None) => Status::Unrecorded,
+        }
+    }
+}
+
+/// The tally of statuses over everything that has been verified.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub passed: usize,
+    pub regressed: usize,
+    pub unrecorded: usize,
+    pub failed: usize,
+}
+
+impl Summary {
+    pub fn ok(&self) -> bool {
+        self.regressed == 0 && self.failed == 0
+    }
+}
+
+/// Run `f`, turning a panic into an error message rather than unwinding
+/// through the runner.
+pub fn timed<T>(f: impl FnOnce() -> Result<T>) -> (Result<T, String>, Duration) {
+    let start = Instant::now();
+    let result = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result.map_err(|e| format!("{e:#}")),
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+    };
+    (result, start.elapsed())
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Print a line of the report followed by an answer. Multi-line answers
+/// start on their own line and are indented, so they stay readable.
+pub fn report(line: &str, answer: &Answer) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("{}", line.trim_end());
+        for row in answer.lines() {
+            println!("    {row}");
+        }
+    } else {
+        println!("{line} {answer}");
+    }
+}
+
+/// Solve both parts of a day against its embedded input and compare them
+/// with the recorded answers, printing a line per part. With `record`, any
+/// answer that isn't recorded yet is saved; recorded answers are never
+/// overwritten.
+pub fn verify(day: &Day, parts: &[Part], record: bool, summary: &mut Summary) -> Result<()> {
+    let mut answers = Answers::load(day)?;
+    let label = format!("{} day {:02}", day.year, day.day);
+
+    let (parsed, elapsed) = timed(|| day.solution.parse(day.solution.embedded_input()));
+    let parsed = match parsed {
+        Ok(parsed) => {
+            println!("{label} parse  {elapsed:>10.2?}");
+            parsed
+        }
+        Err(e) => {
+            println!("{label} parse  {elapsed:>10.2?}  FAIL        {e}");
+            summary.failed += parts.len();
+            return Ok(());
+        }
+    };
+
+    let mut changed = false;
+    for &part in parts {
+        let (answer, elapsed) = timed(|| day.solution.solve_parsed(parsed.as_ref(), part));
+        let status = Status::of(&answer, answers.get(part));
+
+        let prefix = format!("{label} part {part} {elapsed:>10.2?}");
+        match status {
+            Status::Pass => {
+                summary.passed += 1;
+                println!("{prefix}  pass");
+            }
+            Status::Regression(expected) => {
+                summary.regressed += 1;
+                println!("{prefix}  REGRESSION");
+                report("    expected", &expected);
+                report(
+                    "    got     ",
+                    answer.as_ref().expect("regressions have answers"),
+                );
+            }
+            Status::Unrecorded => {
+                summary.unrecorded += 1;
+                let answer = answer.expect("only answers can be unrecorded");
+                if record {
+                    report(&format!("{prefix}  recorded   "), &answer);
+                    answers.set(part, answer);
+                    changed = true;
+                } else {
+                    report(&format!("{prefix}  unrecorded "), &answer);
+                }
+            }
+            Status::Fail(e) => {
+                summary.failed += 1;
+                println!("{prefix}  FAIL        {e}");
+            }
+        }
+    }
+
+    if changed {
+        answers.save(day)?;
+    }
+    Ok(())
+}
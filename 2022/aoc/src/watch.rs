@@ -0,0 +1,441 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::json::TypedAnswer;
+use crate::params;
+use crate::registry::{self, Day};
+
+/// How to run a day each time it changes.
+pub struct Options {
+    /// The input file to solve, instead of the day's `input.txt`.
+    pub input: Option<PathBuf>,
+    pub part: Option<u8>,
+    /// How often to look for changes.
+    pub interval: Duration,
+}
+
+/// When each watched file was last modified.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// The files at `paths`, and everything under the ones that are
+    /// directories. Hidden files and editor backups are left out, and so are
+    /// missing files, so that creating one counts as a change.
+    fn take(paths: &[PathBuf]) -> Snapshot {
+        let mut files = BTreeMap::new();
+        let mut pending = paths.to_vec();
+        while let Some(path) = pending.pop() {
+            let Ok(metadata) = fs::metadata(&path) else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if let Ok(entries) = fs::read_dir(&path) {
+                    pending.extend(entries.filter_map(|entry| Some(entry.ok()?.path())));
+                }
+            } else if !is_scratch(&path) {
+                if let Ok(modified) = metadata.modified() {
+                    files.insert(path, modified);
+                }
+            }
+        }
+        Snapshot(files)
+    }
+
+    /// The files that were added, removed or modified since `earlier`.
+    fn changed_since<'a>(&'a self, earlier: &'a Snapshot) -> Vec<&'a Path> {
+        let mut changed: Vec<&Path> = self
+            .0
+            .iter()
+            .filter(|&(path, modified)| earlier.0.get(path) != Some(modified))
+            .map(|(path, _)| path.as_path())
+            .collect();
+        changed.extend(
+            earlier
+                .0
+                .keys()
+                .filter(|path| !self.0.contains_key(*path))
+                .map(PathBuf::as_path),
+        );
+        changed.sort();
+        changed
+    }
+}
+
+/// Whether a file is one an editor keeps while it works, like `.main.rs.swp`
+/// or `main.rs~`.
+fn is_scratch(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    name.starts_with('.') || name.ends_with('~')
+}
+
+/// A part's line of `aoc run --json`, read back.
+#[derive(Debug, Clone, Deserialize)]
+struct PartRun {
+    part: u8,
+    answer: TypedAnswer,
+    parse_secs: f64,
+    solve_secs: f64,
+}
+
+/// Cargo, pointed at this workspace wherever it's run from.
+fn cargo(args: &[&str]) -> Command {
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command
+        .args(args)
+        .arg("--manifest-path")
+        .arg(registry::workspace_dir().join("Cargo.toml"));
+    command
+}
+
+/// Rebuild the runner, quietly unless it doesn't compile.
+fn build() -> Result<bool> {
+    let status = cargo(&["build", "--release", "--quiet", "-p", "aoc"])
+        .status()
+        .context("failed to run cargo")?;
+    Ok(status.success())
+}
+
+/// Solve the day with the freshly built runner, or `None` if it failed, in
+/// which case it has already said why.
+fn solve(day: &Day, options: &Options) -> Result<Option<Vec<PartRun>>> {
+    let (year, number) = (day.year.to_string(), day.day.to_string());
+    let mut command = cargo(&["run", "--release", "--quiet", "-p", "aoc"]);
+    command.args(["--", "run", &year, &number, "--json"]);
+    if let Some(input) = &options.input {
+        command.arg("--input").arg(input);
+    }
+    if let Some(part) = options.part {
+        command.args(["--part", &part.to_string()]);
+    }
+
+    let output = command
+        .stderr(Stdio::inherit())
+        .output()
+        .context("failed to run cargo")?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("unexpected output from aoc run: {line}"))
+        })
+        .collect::<Result<_>>()
+        .map(Some)
+}
+
+/// How a day's example tests went.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct TestResults {
+    passed: usize,
+    failed: usize,
+}
+
+impl TestResults {
+    /// Add up the `test result:` lines of the test harness's output.
+    fn from_output(output: &str) -> TestResults {
+        let mut results = TestResults::default();
+        for line in output.lines() {
+            let Some(counts) = line.strip_prefix("test result: ") else {
+                continue;
+            };
+            let words: Vec<&str> = counts.split_whitespace().collect();
+            for pair in words.windows(2) {
+                let Ok(count) = pair[0].parse::<usize>() else {
+                    continue;
+                };
+                if pair[1].starts_with("passed") {
+                    results.passed += count;
+                } else if pair[1].starts_with("failed") {
+                    results.failed += count;
+                }
+            }
+        }
+        results
+    }
+}
+
+/// Run the day's example tests, or `None` if they didn't compile. Failures
+/// are printed as the test harness describes them.
+fn examples(day: &Day) -> Result<Option<TestResults>> {
+    let krate = day.dir().file_name().unwrap_or_default().to_string_lossy();
+    let output = cargo(&[
+        "test",
+        "--release",
+        "--quiet",
+        "-p",
+        &krate,
+        "--test",
+        "example",
+    ])
+    .output()
+    .context("failed to run cargo")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let results = TestResults::from_output(&stdout);
+    if output.status.success() {
+        Ok(Some(results))
+    } else if results.failed > 0 {
+        print!("{stdout}");
+        Ok(Some(results))
+    } else {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        Ok(None)
+    }
+}
+
+/// An answer's rows: one for a number or some text, several for a picture.
+fn rows(answer: &TypedAnswer) -> Vec<String> {
+    match answer {
+        TypedAnswer::Integer(value) => vec![value.to_string()],
+        TypedAnswer::Text(text) => vec![text.clone()],
+        TypedAnswer::Grid(rows) => rows.clone(),
+    }
+}
+
+/// An answer next to the last run's. A one-line answer says what it was
+/// before if it changed; a picture is followed by its rows, with the ones
+/// that changed marked `-` (before) and `+` (now).
+fn answer_change(answer: &TypedAnswer, last: Option<&TypedAnswer>) -> (String, Vec<String>) {
+    let now = rows(answer);
+    let before = last.map(rows);
+    if let ([row], None | Some([_])) = (now.as_slice(), before.as_deref()) {
+        let summary = match before.as_deref() {
+            Some([was]) if was != row => format!("{row} (was {was})"),
+            Some(_) => format!("{row} (unchanged)"),
+            None => row.clone(),
+        };
+        return (summary, Vec::new());
+    }
+
+    let Some(before) = before else {
+        return (
+            String::new(),
+            now.iter().map(|row| format!("    {row}")).collect(),
+        );
+    };
+    let summary = if before == now {
+        "(unchanged)"
+    } else {
+        "(changed)"
+    };
+    let mut lines = Vec::new();
+    for i in 0..now.len().max(before.len()) {
+        match (before.get(i), now.get(i)) {
+            (Some(was), Some(row)) if was == row => lines.push(format!("    {row}")),
+            (was, row) => {
+                lines.extend(was.map(|was| format!("  - {was}")));
+                lines.extend(row.map(|row| format!("  + {row}")));
+            }
+        }
+    }
+    (summary.to_string(), lines)
+}
+
+/// How long something took, and how much longer or shorter than last time.
+fn timing(secs: f64, last: Option<f64>) -> String {
+    let took = format!("{:.2?}", Duration::from_secs_f64(secs));
+    match last {
+        Some(last) => {
+            let sign = if secs < last { '-' } else { '+' };
+            let delta = Duration::from_secs_f64((secs - last).abs());
+            format!("{took} ({sign}{delta:.2?})")
+        }
+        None => took,
+    }
+}
+
+/// Rebuild, solve and test the day once, comparing the answers with the
+/// `last` run's, and keeping these for next time if it worked.
+fn cycle(day: &Day, options: &Options, last: &mut Vec<PartRun>) -> Result<()> {
+    let label = format!("{} day {:02}", day.year, day.day);
+    if !build()? {
+        println!("{label} doesn't build");
+        return Ok(());
+    }
+
+    match solve(day, options)? {
+        Some(runs) => {
+            if let Some(run) = runs.first() {
+                let parse = timing(run.parse_secs, last.first().map(|last| last.parse_secs));
+                println!("{label} parse  {parse}");
+            }
+            for run in &runs {
+                let previous = last.iter().find(|last| last.part == run.part);
+                let solve = timing(run.solve_secs, previous.map(|last| last.solve_secs));
+                let (summary, lines) =
+                    answer_change(&run.answer, previous.map(|last| &last.answer));
+                let line = format!("{label} part {} {solve}: {summary}", run.part);
+                println!("{}", line.trim_end());
+                for line in lines {
+                    println!("{line}");
+                }
+            }
+            *last = runs;
+        }
+        None => println!("{label} failed"),
+    }
+
+    match examples(day)? {
+        Some(results) => println!(
+            "{label} examples: {} passed, {} failed",
+            results.passed, results.failed
+        ),
+        None => println!("{label} examples don't build"),
+    }
+    Ok(())
+}
+
+/// Wait until some of the files at `paths` change from `snapshot`, and then
+/// stop changing for a moment, since editors and formatters often write a
+/// file more than once. Returns the files that changed and how they are now.
+fn wait_for_changes(
+    paths: &[PathBuf],
+    snapshot: &Snapshot,
+    interval: Duration,
+) -> (Vec<PathBuf>, Snapshot) {
+    loop {
+        thread::sleep(interval);
+        let mut latest = Snapshot::take(paths);
+        let changed: Vec<PathBuf> = latest
+            .changed_since(snapshot)
+            .into_iter()
+            .map(Path::to_path_buf)
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+        loop {
+            thread::sleep(interval);
+            let again = Snapshot::take(paths);
+            if again == latest {
+                return (changed, latest);
+            }
+            latest = again;
+        }
+    }
+}
+
+/// Rebuild and re-run a day whenever its source, tests, example or input
+/// change, until interrupted. Each run shows the answers and timings next to
+/// the last run's, and how the example tests went.
+pub fn watch(day: &Day, options: &Options) -> Result<()> {
+    let dir = day.dir();
+    let input = options
+        .input
+        .clone()
+        .unwrap_or_else(|| dir.join("input.txt"));
+    let paths = vec![
+        dir.join("src"),
+        dir.join("tests"),
+        dir.join("Cargo.toml"),
+        dir.join("example.txt"),
+        params::sidecar_path(&dir.join("example.txt")),
+        params::sidecar_path(&input),
+        input,
+    ];
+
+    println!(
+        "Watching {} day {:02} for changes; press Ctrl-C to stop",
+        day.year, day.day
+    );
+    let mut snapshot = Snapshot::take(&paths);
+    let mut last = Vec::new();
+    loop {
+        cycle(day, options, &mut last)?;
+        let (changed, latest) = wait_for_changes(&paths, &snapshot, options.interval);
+        snapshot = latest;
+        let changed: Vec<String> = changed
+            .iter()
+            .map(|path| path.strip_prefix(dir).unwrap_or(path).display().to_string())
+            .collect();
+        println!("\n{} changed", changed.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(files: &[(&str, u64)]) -> Snapshot {
+        Snapshot(
+            files
+                .iter()
+                .map(|&(path, secs)| {
+                    let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+                    (PathBuf::from(path), modified)
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn notices_added_removed_and_modified_files() {
+        let before = snapshot(&[("src/main.rs", 1), ("input.txt", 1), ("example.txt", 1)]);
+        let after = snapshot(&[("src/main.rs", 2), ("src/parser.rs", 2), ("input.txt", 1)]);
+        assert_eq!(
+            after.changed_since(&before),
+            [
+                Path::new("example.txt"),
+                Path::new("src/main.rs"),
+                Path::new("src/parser.rs")
+            ]
+        );
+        assert!(after.changed_since(&after).is_empty());
+        assert!(is_scratch(Path::new("src/.main.rs.swp")));
+        assert!(!is_scratch(Path::new("src/main.rs")));
+    }
+
+    #[test]
+    fn adds_up_test_results() {
+        let output = "\
+running 2 tests
+..
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+running 1 test
+F
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+        assert_eq!(
+            TestResults::from_output(output),
+            TestResults {
+                passed: 2,
+                failed: 1
+            }
+        );
+    }
+
+    #[test]
+    fn compares_answers_with_the_last_run() {
+        let (summary, lines) = answer_change(&TypedAnswer::Integer(26), None);
+        assert_eq!((summary.as_str(), lines.len()), ("26", 0));
+        let was = TypedAnswer::Integer(25);
+        assert_eq!(
+            answer_change(&TypedAnswer::Integer(26), Some(&was)).0,
+            "26 (was 25)"
+        );
+        assert_eq!(answer_change(&was, Some(&was)).0, "25 (unchanged)");
+
+        let grid =
+            |rows: &[&str]| TypedAnswer::Grid(rows.iter().map(|row| row.to_string()).collect());
+        let (summary, lines) = answer_change(&grid(&["#.", ".#"]), Some(&grid(&["#.", "##"])));
+        assert_eq!(summary, "(changed)");
+        assert_eq!(lines, ["    #.", "  - ##", "  + .#"]);
+    }
+
+    #[test]
+    fn times_against_the_last_run() {
+        assert_eq!(timing(0.5, None), "500.00ms");
+        assert_eq!(timing(0.5, Some(0.75)), "500.00ms (-250.00ms)");
+        assert_eq!(timing(0.002, Some(0.001)), "2.00ms (+1.00ms)");
+    }
+}
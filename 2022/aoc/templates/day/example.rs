@@ -0,0 +1,18 @@
+use common::Solution;
+use {{crate}}::{{Solution}};
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+#[ignore = "fill in example.txt and the expected answer"]
+fn part1() {
+    let input = {{Solution}}.parse(EXAMPLE).unwrap();
+    assert_eq!({{Solution}}.part1(&input).unwrap(), 0);
+}
+
+#[test]
+#[ignore = "fill in example.txt and the expected answer"]
+fn part2() {
+    let input = {{Solution}}.parse(EXAMPLE).unwrap();
+    assert_eq!({{Solution}}.part2(&input).unwrap(), 0);
+}
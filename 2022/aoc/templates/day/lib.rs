@@ -0,0 +1,51 @@
+use anyhow::{bail, Result};
+use common::Solution;
+
+/// The usual module wrapping the nom parsers for today's input.
+mod parser {
+    use anyhow::Result;
+    use nom::{character::complete::u32, IResult};
+    use parsing::{finish, lines1};
+
+    /// Nom parser for a single line of the input
+    fn line(s: &str) -> IResult<&str, u32> {
+        u32(s)
+    }
+
+    /// Entrypoint for the parsing functions, one line at a time
+    pub(crate) fn parse(s: &str) -> Result<Vec<u32>> {
+        Ok(finish(s, "a number", lines1(line))?)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn parses_one_line_at_a_time() {
+            assert_eq!(parse("1\n2\n3\n").unwrap(), vec![1, 2, 3]);
+        }
+    }
+}
+
+pub struct {{Solution}};
+
+impl Solution for {{Solution}} {
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parser::parse(input)
+    }
+
+    fn part1(&self, _input: &Self::Input) -> Result<u32> {
+        bail!("Part 1 isn't solved yet")
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Result<u32> {
+        bail!("Part 2 isn't solved yet")
+    }
+}
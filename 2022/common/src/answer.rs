@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// A puzzle answer, normalised so that answers from every day can be printed,
+/// compared and recorded the same way.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+}
+
+macro_rules! int_answer {
+    ($($int:ty),*) => {
+        $(impl From<$int> for Answer {
+            fn from(value: $int) -> Self {
+                match i64::try_from(value) {
+                    Ok(value) => Answer::Int(value),
+                    Err(_) => Answer::Text(value.to_string()),
+                }
+            }
+        })*
+    };
+}
+
+int_answer!(i32, u32, i64, u64, usize);
+
+impl From<&str> for Answer {
+    /// Text answers ignore trailing whitespace, both at the end of each line
+    /// and at the end of the answer, since it's invisible when printed and
+    /// tends to get stripped by editors.
+    fn from(value: &str) -> Self {
+        let lines: Vec<&str> = value.lines().map(str::trim_end).collect();
+        Answer::Text(lines.join("\n").trim_end().to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::from(value.as_str())
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// A problem with the puzzle input, located by line and column (both
+/// counted from 1, columns in characters) with the text found there and a
+/// description of what should have been.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, never more than the rest of its line. Empty if
+    /// the input ran out.
+    pub found: String,
+    /// The shape the input should have had at this point, like "a move
+    /// (A, B or C)".
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, found: &str, expected: impl Display) -> Self {
+        ParseError {
+            line,
+            column,
+            found: found.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// An error at `found`, which should be a slice of `input` (anything
+    /// else is looked for in `input`, or put at its end). Only the first line
+    /// of `found` is kept, so the rest of the input can be passed straight
+    /// from a parser that gave up.
+    pub fn at(input: &str, found: &str, expected: impl Display) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (found.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + found.len() <= input.len())
+            .or_else(|| input.find(found))
+            .unwrap_or(input.len());
+
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        let found = found.lines().next().unwrap_or_default();
+
+        ParseError::new(line, column, found, expected)
+    }
+
+    /// The error with a caret pointing at it under the offending line of
+    /// `input`, read from `source`. The first line is the message on its own.
+    pub fn diagnostic(&self, source: impl Display, input: &str) -> String {
+        let text = input.lines().nth(self.line - 1).unwrap_or_default();
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let indent = " ".repeat(self.column - 1);
+        let carets = "^".repeat(self.found.chars().count().max(1));
+
+        format!(
+            "{message}\n\
+             {gutter}--> {source}:{line}:{column}\n\
+             {gutter} |\n\
+             {number} | {text}\n\
+             {gutter} | {indent}{carets}",
+            message = self.message(),
+            line = self.line,
+            column = self.column,
+        )
+    }
+
+    fn message(&self) -> String {
+        if self.found.is_empty() {
+            format!("expected {}, found the end of the line", self.expected)
+        } else {
+            format!("expected {}, found {:?}", self.expected, self.found)
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line,
+            self.column,
+            self.message()
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse every line of `input` with `f`, failing on the first line it
+/// rejects. The whole line is reported as the offending text.
+pub fn parse_lines<T>(
+    input: &str,
+    expected: impl Display,
+    mut f: impl FnMut(&str) -> Option<T>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| f(line).ok_or_else(|| ParseError::at(input, line, &expected)))
+        .collect()
+}
+
+/// Describe an error for the person who supplied `input` from `source`:
+/// parse errors get a diagnostic pointing into the input, anything else is
+/// printed with its causes.
+pub fn diagnose(error: &anyhow::Error, source: impl Display, input: &str) -> String {
+    match error.chain().find_map(|e| e.downcast_ref::<ParseError>()) {
+        Some(parse_error) => parse_error.diagnostic(source, input),
+        None => format!("{error:#}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "A Y\nB X\nC Q\n";
+
+    #[test]
+    fn locates_a_slice_of_the_input() {
+        let found = &INPUT[10..];
+        let error = ParseError::at(INPUT, found, "a move (X, Y or Z)");
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.found, "Q");
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 3: expected a move (X, Y or Z), found \"Q\""
+        );
+    }
+
+    #[test]
+    fn reports_running_out_of_input() {
+        let error = ParseError::at(INPUT, &INPUT[INPUT.len()..], "another round");
+        assert_eq!((error.line, error.column), (4, 1));
+        assert!(error.to_string().ends_with("found the end of the line"));
+    }
+
+    #[test]
+    fn points_a_caret_at_the_offending_text() {
+        let error = ParseError::at(INPUT, &INPUT[10..], "a move (X, Y or Z)");
+        assert_eq!(
+            error.diagnostic("input.txt", INPUT),
+            "expected a move (X, Y or Z), found \"Q\"\n \
+             --> input.txt:3:3\n  \
+             |\n\
+             3 | C Q\n  \
+             |   ^"
+        );
+    }
+
+    #[test]
+    fn parse_lines_reports_the_rejected_line() {
+        let error = parse_lines("1\n2\nx\n", "a number", |l| l.parse::<u32>().ok()).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (3, 1, "x")
+        );
+    }
+}
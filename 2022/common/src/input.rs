@@ -0,0 +1,54 @@
+use anyhow::{Context, Result};
+use std::fmt::{self, Display};
+use std::io::Read;
+use std::path::PathBuf;
+
+/// Where to read a puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// A file on disk.
+    Path(PathBuf),
+    /// Everything piped into standard input.
+    Stdin,
+    /// The input compiled into the solution crate.
+    Embedded,
+}
+
+impl Source {
+    /// Interpret a command line argument as an input source: no argument
+    /// means the embedded input and `-` means stdin. Anything else is a path.
+    pub fn from_arg(arg: Option<&str>) -> Self {
+        match arg {
+            None => Source::Embedded,
+            Some("-") => Source::Stdin,
+            Some(path) => Source::Path(PathBuf::from(path)),
+        }
+    }
+
+    /// Read the whole input, falling back to `embedded` for
+    /// `Source::Embedded`.
+    pub fn read(&self, embedded: &str) -> Result<String> {
+        match self {
+            Source::Path(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display())),
+            Source::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("failed to read stdin")?;
+                Ok(input)
+            }
+            Source::Embedded => Ok(embedded.to_string()),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+            Source::Embedded => write!(f, "the embedded input"),
+        }
+    }
+}
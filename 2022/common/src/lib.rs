@@ -0,0 +1,226 @@
+//! Shared plumbing for the daily puzzle solutions: the `Solution` trait every
+//! day implements, the `Configure` trait for days with puzzle parameters, and
+//! the helpers for loading and tidying puzzle input and showing trace output
+//! at runtime.
+
+use anyhow::Result;
+use std::any::Any;
+use std::fmt::{self, Display};
+
+mod answer;
+mod error;
+mod input;
+mod logging;
+mod params;
+mod text;
+
+pub use answer::Answer;
+pub use error::{diagnose, parse_lines, ParseError};
+pub use input::Source;
+pub use logging::init_tracing;
+pub use params::{parse_param, Configure, DynConfigure, Overrides, Param};
+pub use text::Text;
+
+/// One of the two halves of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(anyhow::anyhow!("there is no part {value}, only 1 and 2")),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A day's puzzle, split into a parsing step and the two parts that work on
+/// the parsed input.
+///
+/// Input that doesn't have the expected shape should fail to parse with a
+/// [`ParseError`] saying where. Parts fail when the input parses but has no
+/// answer.
+pub trait Solution {
+    /// Whatever `parse` turns the raw puzzle text into.
+    type Input;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    /// The puzzle input bundled into the crate, used when no other input is
+    /// given.
+    const INPUT: &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+/// Parse `input` with `solution` once its line endings and trailing
+/// whitespace have been tidied up, as a [`Text`]. Every way of running a
+/// solution parses its input through here.
+pub fn parse_tidied<S: Solution + ?Sized>(solution: &S, input: &str) -> Result<S::Input> {
+    solution.parse(&Text::new(input))
+}
+
+/// An object-safe view of a `Solution`, so that days with different input
+/// and answer types can sit side by side in a registry.
+pub trait DynSolution {
+    fn embedded_input(&self) -> &'static str;
+
+    /// Parse `input` with [`parse_tidied`].
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    fn solve_parsed(&self, input: &dyn Any, part: Part) -> Result<Answer>;
+
+    /// Parse `input` and solve a single part of the puzzle with it.
+    fn solve(&self, input: &str, part: Part) -> Result<Answer> {
+        let parsed = self.parse(input)?;
+        self.solve_parsed(parsed.as_ref(), part)
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn embedded_input(&self) -> &'static str {
+        S::INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(parse_tidied(self, input)?))
+    }
+
+    fn solve_parsed(&self, input: &dyn Any, part: Part) -> Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input was parsed by a different solution");
+        Ok(match part {
+            Part::One => self.part1(input)?.into(),
+            Part::Two => self.part2(input)?.into(),
+        })
+    }
+}
+
+/// Load and parse the input for a day's standalone binary. The first command
+/// line argument picks the source: a file path, `-` for stdin, or nothing for
+/// the embedded input. Exits with a message if the input can't be used.
+pub fn load<S: Solution>(solution: &S) -> S::Input {
+    let source = Source::from_arg(std::env::args().nth(1).as_deref());
+    let input = match source.read(S::INPUT) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Couldn't load input from {source}: {e:#}");
+            std::process::exit(1);
+        }
+    };
+
+    match parse_tidied(solution, &input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Couldn't parse {source}: {}", diagnose(&e, &source, &input));
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Print an answer after a label, moving it onto its own lines if it spans
+/// several (like a picture drawn on a screen).
+pub fn print_answer(label: &str, answer: &Answer) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("{label}\n{answer}");
+    } else {
+        println!("{label} {answer}");
+    }
+}
+
+/// The standalone binary for a day: load its input and print both answers.
+/// Trace output goes to stderr, filtered by `RUST_LOG`.
+pub fn main<S>(solution: &S)
+where
+    S: Solution,
+{
+    if let Err(e) = init_tracing(None, None) {
+        eprintln!("{e:#}");
+        std::process::exit(1);
+    }
+    let input = load(solution);
+    print_part(Part::One, solution.part1(&input).map(Into::into));
+    print_part(Part::Two, solution.part2(&input).map(Into::into));
+}
+
+/// Print a part's answer for a standalone binary, or exit if it failed.
+fn print_part(part: Part, answer: Result<Answer>) {
+    match answer {
+        Ok(answer) => print_answer(&format!("Part {part}:"), &answer),
+        Err(e) => {
+            eprintln!("Part {part} failed: {e:#}");
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Numbers one per line, which trip over spaces at the ends of lines.
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Input = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        const INPUT: &'static str = "";
+
+        fn parse(&self, input: &str) -> Result<Vec<u32>> {
+            Ok(input.lines().map(str::parse).collect::<Result<_, _>>()?)
+        }
+
+        fn part1(&self, input: &Vec<u32>) -> Result<u32> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(&self, input: &Vec<u32>) -> Result<u32> {
+            Ok(input.iter().product())
+        }
+    }
+
+    #[test]
+    fn tidies_the_input_before_parsing_it() {
+        assert!(Solution::parse(&Numbers, "1 \r\n2\r\n").is_err());
+        assert_eq!(parse_tidied(&Numbers, "1 \r\n2\r\n\n").unwrap(), [1, 2]);
+        let answer = DynSolution::solve(&Numbers, "3\r\n4 \r\n", Part::Two);
+        assert_eq!(answer.unwrap(), Answer::Int(12));
+    }
+}
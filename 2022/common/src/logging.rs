@@ -0,0 +1,43 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing_subscriber::EnvFilter;
+
+/// Show the spans and events the solutions emit, on stderr or in `file`.
+/// The `filter` is written as for `RUST_LOG`, like `debug` or
+/// `day19=trace,search=debug`. Without one, `RUST_LOG` is used, and without
+/// that only warnings and errors are shown.
+pub fn init_tracing(filter: Option<&str>, file: Option<&Path>) -> Result<()> {
+    let filter = match filter {
+        Some(filter) => EnvFilter::try_new(filter)
+            .with_context(|| format!("{filter:?} isn't a valid log filter"))?,
+        None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+    };
+    let subscriber = tracing_subscriber::fmt().with_env_filter(filter);
+    let result = match file {
+        Some(path) => {
+            let file = File::create(path)
+                .with_context(|| format!("failed to create {}", path.display()))?;
+            subscriber
+                .with_ansi(false)
+                .with_writer(Mutex::new(file))
+                .try_init()
+        }
+        None => subscriber.with_writer(std::io::stderr).try_init(),
+    };
+    result.map_err(|e| anyhow!("couldn't start logging: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_filters() {
+        let error = init_tracing(Some("day19=loud"), None).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("\"day19=loud\" isn't a valid log filter"));
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{DynSolution, Solution};
+use anyhow::{anyhow, bail, Result};
+use std::any::Any;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// One of a puzzle's parameters, with its value written out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub value: String,
+    /// What the parameter means, from its doc comment.
+    pub about: &'static str,
+}
+
+/// A solution with constants from the puzzle text that can be changed at
+/// runtime, like the row day 15 counts along. The fields of the solution are
+/// the parameters, and its named presets (`"puzzle"`, `"example"`) are
+/// constants of it. Implement it with [`configure!`](crate::configure).
+pub trait Configure: Solution + Clone + 'static {
+    /// The presets by name. The first is the one the solution runs with
+    /// unless told otherwise.
+    const PRESETS: &'static [(&'static str, Self)];
+
+    fn params(&self) -> Vec<Param>;
+
+    /// Set the parameter called `name` from its written out `value`.
+    fn set(&mut self, name: &str, value: &str) -> Result<()>;
+
+    /// The preset called `name`.
+    fn preset(name: &str) -> Result<Self> {
+        match Self::PRESETS.iter().find(|&&(preset, _)| preset == name) {
+            Some((_, solution)) => Ok(solution.clone()),
+            None => {
+                let names: Vec<&str> = Self::PRESETS.iter().map(|&(name, _)| name).collect();
+                bail!("There's no {name:?} preset, only {}", names.join(", "))
+            }
+        }
+    }
+
+    /// A copy of the solution with `overrides` applied to it.
+    fn apply(&self, overrides: &Overrides) -> Result<Self> {
+        let mut solution = match &overrides.preset {
+            Some(preset) => Self::preset(preset)?,
+            None => self.clone(),
+        };
+        for (name, value) in &overrides.values {
+            solution.set(name, value)?;
+        }
+        Ok(solution)
+    }
+}
+
+/// Parse a parameter's value, saying which parameter it was for if it's no
+/// good.
+pub fn parse_param<T>(name: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|e| anyhow!("{value:?} isn't a valid {name}: {e}"))
+}
+
+/// Settings for a solution's parameters: a preset to start from, and then
+/// values for single parameters, applied in order so later ones win.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overrides {
+    pub preset: Option<String>,
+    pub values: Vec<(String, String)>,
+}
+
+impl Overrides {
+    pub fn is_empty(&self) -> bool {
+        self.preset.is_none() && self.values.is_empty()
+    }
+
+    /// These overrides, then `later` over the top of them.
+    pub fn then(mut self, later: Overrides) -> Overrides {
+        if later.preset.is_some() {
+            self.preset = later.preset;
+        }
+        self.values.extend(later.values);
+        self
+    }
+}
+
+/// An object-safe view of a `Configure`, so that the runner can keep the
+/// days side by side, like `DynSolution`.
+pub trait DynConfigure {
+    /// The parameters once `overrides` have been applied.
+    fn params(&self, overrides: &Overrides) -> Result<Vec<Param>>;
+    fn presets(&self) -> Vec<&'static str>;
+
+    /// A copy of the solution with `overrides` applied to it.
+    fn configure(&self, overrides: &Overrides) -> Result<Box<dyn DynSolution>>;
+}
+
+impl<S> DynConfigure for S
+where
+    S: Configure,
+    S::Input: Any,
+{
+    fn params(&self, overrides: &Overrides) -> Result<Vec<Param>> {
+        Ok(Configure::params(&self.apply(overrides)?))
+    }
+
+    fn presets(&self) -> Vec<&'static str> {
+        S::PRESETS.iter().map(|&(name, _)| name).collect()
+    }
+
+    fn configure(&self, overrides: &Overrides) -> Result<Box<dyn DynSolution>> {
+        Ok(Box::new(self.apply(overrides)?))
+    }
+}
+
+/// Define a day's solution as a struct of its parameters, and implement
+/// [`Configure`] for it with the given presets. Each field's value is written
+/// with `Display` and read back with `FromStr`, and its doc comment says what
+/// it means. The day's crate needs `anyhow` as well:
+///
+/// ```ignore
+/// common::configure! {
+///     pub struct Day15 {
+///         /// The row to count the positions that can't contain a beacon on.
+///         pub row: i64,
+///     }
+///     presets: ["puzzle" => Day15::PUZZLE, "example" => Day15::EXAMPLE]
+/// }
+/// ```
+#[macro_export]
+macro_rules! configure {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $day:ident {
+            $($(#[doc = $about:literal])+ $field_vis:vis $field:ident: $ty:ty,)+
+        }
+        presets: [$($preset:literal => $value:expr),+ $(,)?]
+    ) => {
+        $(#[$meta])*
+        $vis struct $day {
+            $($(#[doc = $about])+ $field_vis $field: $ty,)+
+        }
+
+        impl $crate::Configure for $day {
+            const PRESETS: &'static [(&'static str, Self)] = &[$(($preset, $value)),+];
+
+            fn params(&self) -> Vec<$crate::Param> {
+                vec![$($crate::Param {
+                    name: stringify!($field),
+                    value: self.$field.to_string(),
+                    about: concat!($($about),+).trim_start(),
+                }),+]
+            }
+
+            fn set(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+                match name {
+                    $(stringify!($field) => self.$field = $crate::parse_param(name, value)?,)+
+                    _ => anyhow::bail!(
+                        "There's no parameter called {name:?}, only {}",
+                        [$(stringify!($field)),+].join(", ")
+                    ),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::configure! {
+        /// A made-up puzzle, counting to `limit` in steps of `step`.
+        #[derive(Debug, Clone, PartialEq)]
+        struct Counting {
+            /// How far to count.
+            limit: u32,
+            /// How far each step goes.
+            step: u32,
+        }
+        presets: ["puzzle" => Counting::PUZZLE, "example" => Counting::EXAMPLE]
+    }
+
+    impl Counting {
+        const PUZZLE: Counting = Counting {
+            limit: 100,
+            step: 1,
+        };
+        const EXAMPLE: Counting = Counting { limit: 10, step: 2 };
+    }
+
+    impl Solution for Counting {
+        type Input = ();
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        const INPUT: &'static str = "";
+
+        fn parse(&self, _: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(&self, _: &()) -> Result<u32> {
+            Ok(self.limit / self.step)
+        }
+
+        fn part2(&self, _: &()) -> Result<u32> {
+            Ok(self.limit)
+        }
+    }
+
+    #[test]
+    fn lists_and_sets_params() {
+        let mut counting = Counting::PUZZLE;
+        assert_eq!(
+            Configure::params(&counting)[0],
+            Param {
+                name: "limit",
+                value: "100".to_string(),
+                about: "How far to count.",
+            }
+        );
+
+        counting.set("step", " 5").unwrap();
+        assert_eq!(counting.step, 5);
+        let error = counting.set("step", "five").unwrap_err();
+        assert!(error.to_string().starts_with("\"five\" isn't a valid step"));
+        let error = counting.set("stride", "5").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "There's no parameter called \"stride\", only limit, step"
+        );
+    }
+
+    #[test]
+    fn overrides_apply_over_the_preset() {
+        let overrides = Overrides {
+            preset: Some("example".to_string()),
+            values: vec![("limit".to_string(), "30".to_string())],
+        };
+        let counting = Counting::PUZZLE.configure(&overrides).unwrap();
+        assert_eq!(counting.solve("", crate::Part::One).unwrap(), 15.into());
+
+        let later = Overrides {
+            preset: None,
+            values: vec![("limit".to_string(), "4".to_string())],
+        };
+        let counting = Counting::PUZZLE
+            .configure(&overrides.clone().then(later))
+            .unwrap();
+        assert_eq!(counting.solve("", crate::Part::Two).unwrap(), 4.into());
+
+        let unknown = Overrides {
+            preset: Some("sample".to_string()),
+            values: Vec::new(),
+        };
+        assert!(Counting::PUZZLE.configure(&unknown).is_err());
+        assert_eq!(
+            DynConfigure::presets(&Counting::PUZZLE),
+            ["puzzle", "example"]
+        );
+        let params = DynConfigure::params(&Counting::PUZZLE, &overrides).unwrap();
+        assert_eq!(
+            (params[0].value.as_str(), params[1].value.as_str()),
+            ("30", "2")
+        );
+    }
+}
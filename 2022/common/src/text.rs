@@ -0,0 +1,180 @@
+use crate::ParseError;
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::ops::Deref;
+
+/// Puzzle input with its line endings and trailing whitespace tidied up:
+/// CRLF becomes LF, spaces at the ends of lines and blank lines at the end
+/// of the input are dropped, and the last line ends in a single newline.
+/// Lines and columns stay where they were in the raw input, so errors
+/// located in the text point at the right place in it.
+///
+/// On top of the text itself, it has views of its lines grouped the ways
+/// the puzzles group them, which check the input has the right shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Text<'a>(Cow<'a, str>);
+
+impl<'a> Text<'a> {
+    pub fn new(raw: &'a str) -> Self {
+        let body = raw.trim_end();
+        if body.is_empty() {
+            return Text(Cow::Borrowed(""));
+        }
+        let tidy = raw.len() == body.len() + 1
+            && raw.ends_with('\n')
+            && body.lines().all(|line| line.len() == line.trim_end().len());
+        if tidy {
+            Text(Cow::Borrowed(raw))
+        } else {
+            let lines: Vec<&str> = body.lines().map(str::trim_end).collect();
+            Text(Cow::Owned(format!("{}\n", lines.join("\n"))))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// An error at `found`, which should be a slice of the text.
+    pub fn error(&self, found: &str, expected: impl Display) -> ParseError {
+        ParseError::at(self.as_str(), found, expected)
+    }
+
+    /// An error where the text runs out.
+    fn error_at_end(&self, expected: impl Display) -> ParseError {
+        self.error(&self.as_str()[self.len()..], expected)
+    }
+
+    /// Runs of lines separated by one or more blank lines, like day 1's elves
+    /// or day 11's monkeys, each without its last newline.
+    pub fn paragraphs(&self) -> Vec<&str> {
+        let text = self.as_str();
+        let mut paragraphs = Vec::new();
+        let (mut start, mut offset) = (None, 0);
+        for line in text.split_inclusive('\n') {
+            if line == "\n" {
+                if let Some(start) = start.take() {
+                    paragraphs.push(&text[start..offset - 1]);
+                }
+            } else if start.is_none() {
+                start = Some(offset);
+            }
+            offset += line.len();
+        }
+        if let Some(start) = start {
+            paragraphs.push(text[start..].trim_end_matches('\n'));
+        }
+        paragraphs
+    }
+
+    /// Exactly `N` paragraphs, like day 5's drawing of the stacks and the
+    /// procedure after it. `expected` describes the paragraph that's missing
+    /// if there aren't enough.
+    pub fn sections<const N: usize>(
+        &self,
+        expected: impl Display,
+    ) -> Result<[&str; N], ParseError> {
+        let paragraphs = self.paragraphs();
+        if let Some(&extra) = paragraphs.get(N) {
+            return Err(self.error(extra, "the end of the input"));
+        }
+        paragraphs
+            .try_into()
+            .map_err(|_| self.error_at_end(expected))
+    }
+
+    /// The lines in groups of `N`, like day 3's elves in threes. `expected`
+    /// describes the line that's missing if the last group is short.
+    pub fn groups<const N: usize>(
+        &self,
+        expected: impl Display,
+    ) -> Result<Vec<[&str; N]>, ParseError> {
+        let lines: Vec<&str> = self.lines().collect();
+        let groups = lines.chunks_exact(N);
+        if !groups.remainder().is_empty() {
+            return Err(self.error_at_end(expected));
+        }
+        Ok(groups
+            .map(|group| group.try_into().expect("chunks are N lines long"))
+            .collect())
+    }
+
+    /// The lines as the rows of a grid, failing on the first that isn't as
+    /// wide (in characters) as the first row.
+    pub fn rows(&self) -> Result<Vec<&str>, ParseError> {
+        let rows: Vec<&str> = self.lines().collect();
+        let Some(first) = rows.first() else {
+            return Err(self.error_at_end("a grid"));
+        };
+        let width = first.chars().count();
+        match rows.iter().find(|row| row.chars().count() != width) {
+            Some(row) => Err(self.error(row, format!("a row {width} cells wide"))),
+            None => Ok(rows),
+        }
+    }
+}
+
+impl Deref for Text<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tidies_line_endings_and_trailing_whitespace() {
+        assert_eq!(Text::new("a \r\nb\t\r\n\r\n\n").as_str(), "a\nb\n");
+        assert_eq!(Text::new("a\n  b").as_str(), "a\n  b\n");
+        assert_eq!(Text::new("\n \n").as_str(), "");
+        assert!(matches!(Text::new("a\nb\n").0, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn splits_paragraphs_on_any_number_of_blank_lines() {
+        let text = Text::new("a\nb\n\n\n c\n\nd\r\n\r\n");
+        assert_eq!(text.paragraphs(), ["a\nb", " c", "d"]);
+        assert_eq!(Text::new("").paragraphs(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn counts_sections() {
+        let text = Text::new("a\n\nb\n\nc\n");
+        let error = text.sections::<2>("two").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (5, 1, "c")
+        );
+        let error = text.sections::<4>("a fourth paragraph").unwrap_err();
+        assert_eq!((error.line, error.column), (6, 1));
+        assert_eq!(text.sections::<3>("three").unwrap(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn groups_lines() {
+        let text = Text::new("a\nb\nc\nd\ne\nf\n");
+        assert_eq!(
+            text.groups::<3>("").unwrap(),
+            [["a", "b", "c"], ["d", "e", "f"]]
+        );
+        let error = text.groups::<4>("the rest of a group").unwrap_err();
+        assert_eq!((error.line, error.column), (7, 1));
+    }
+
+    #[test]
+    fn rows_must_all_be_as_wide() {
+        let text = Text::new("abc\nde\r\nfgh\n");
+        let error = text.rows().unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (2, 1, "de")
+        );
+        assert_eq!(error.expected, "a row 3 cells wide");
+        assert_eq!(Text::new("ab\ncd\n").rows().unwrap(), ["ab", "cd"]);
+        assert!(Text::new("\n").rows().is_err());
+    }
+}
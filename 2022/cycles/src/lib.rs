@@ -0,0 +1,226 @@
+//! Cycle detection for the simulations that have to run for far longer than
+//! they could step by step, like day 17's trillion rocks.
+//!
+//! A simulation is a state and a step function. Each state can have a key
+//! that identifies it as far as the future is concerned (or none, if it's not
+//! worth remembering); once a key comes round again, every step after it
+//! repeats the ones since it was first seen. A [`Metric`] of the states that
+//! grows by the same amount each time round the cycle can then be worked out
+//! for any number of steps without taking them.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a simulation starts repeating itself: the states after `start`
+/// steps and after `start + length` steps have the same key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The metric after `steps` steps, from `history`, the metric after each
+    /// step from none to at least the end of the first time round the cycle.
+    pub fn extrapolate<M: Metric>(&self, history: &[M], steps: usize) -> M {
+        let end = self.start + self.length;
+        assert!(
+            history.len() > end,
+            "the history has to go once round the cycle"
+        );
+        if steps < history.len() {
+            return history[steps].clone();
+        }
+        let (turns, rest) = (
+            (steps - self.start) / self.length,
+            (steps - self.start) % self.length,
+        );
+        history[self.start + rest].plus_turns(&history[self.start], &history[end], turns)
+    }
+}
+
+/// Something measured from the states of a simulation that grows by the
+/// same amount each time round a cycle, like the height of a tower or the
+/// number of items each monkey has inspected.
+pub trait Metric: Clone {
+    /// This value plus `turns` times what it grows by from `from` to `to`.
+    fn plus_turns(&self, from: &Self, to: &Self, turns: usize) -> Self;
+}
+
+macro_rules! integer_metric {
+    ($($t:ty),*) => {
+        $(impl Metric for $t {
+            fn plus_turns(&self, from: &Self, to: &Self, turns: usize) -> Self {
+                self + (to - from) * turns as $t
+            }
+        })*
+    };
+}
+
+integer_metric!(i32, i64, i128, u32, u64, u128, usize);
+
+impl<A: Metric, B: Metric> Metric for (A, B) {
+    fn plus_turns(&self, from: &Self, to: &Self, turns: usize) -> Self {
+        (
+            self.0.plus_turns(&from.0, &to.0, turns),
+            self.1.plus_turns(&from.1, &to.1, turns),
+        )
+    }
+}
+
+impl<M: Metric> Metric for Vec<M> {
+    fn plus_turns(&self, from: &Self, to: &Self, turns: usize) -> Self {
+        self.iter()
+            .zip(from.iter().zip(to))
+            .map(|(value, (from, to))| value.plus_turns(from, to, turns))
+            .collect()
+    }
+}
+
+/// Remembers the keys of the states seen so far, and when each was seen.
+#[derive(Debug, Clone)]
+pub struct Detector<K> {
+    seen: HashMap<K, usize>,
+}
+
+impl<K: Eq + Hash> Detector<K> {
+    pub fn new() -> Self {
+        Detector {
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Note the key of the state after `steps` steps, returning the cycle
+    /// back to when it was first seen, if it has been.
+    pub fn see(&mut self, key: K, steps: usize) -> Option<Cycle> {
+        match self.seen.insert(key, steps) {
+            Some(start) if start < steps => Some(Cycle {
+                start,
+                length: steps - start,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl<K: Eq + Hash> Default for Detector<K> {
+    fn default() -> Self {
+        Detector::new()
+    }
+}
+
+/// Step a simulation on from `state` until the key of a state comes round
+/// again, or `limit` steps have been taken without one doing so. Returns the
+/// cycle and the metric after each step taken.
+pub fn find<S, K, M>(
+    mut state: S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> Option<K>,
+    mut metric: impl FnMut(&S) -> M,
+) -> (Option<Cycle>, Vec<M>)
+where
+    K: Eq + Hash,
+{
+    let mut detector = Detector::new();
+    let mut history = vec![metric(&state)];
+    if let Some(key) = key(&state) {
+        detector.see(key, 0);
+    }
+    for steps in 1..=limit {
+        step(&mut state);
+        history.push(metric(&state));
+        if let Some(cycle) = key(&state).and_then(|key| detector.see(key, steps)) {
+            return (Some(cycle), history);
+        }
+    }
+    (None, history)
+}
+
+/// The metric of a simulation after `steps` steps from `state`, only taking
+/// as many of them as it takes to find a cycle.
+pub fn simulate<S, K, M>(
+    state: S,
+    steps: usize,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> Option<K>,
+    metric: impl FnMut(&S) -> M,
+) -> M
+where
+    K: Eq + Hash,
+    M: Metric,
+{
+    let (cycle, mut history) = find(state, steps, step, key, metric);
+    match cycle {
+        Some(cycle) => cycle.extrapolate(&history, steps),
+        None => history
+            .pop()
+            .expect("the history starts with the first state"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A pseudo-random walk through 0..100 that falls into a cycle, counting
+    /// how many times it's landed on an odd number.
+    fn walk(state: &mut (u64, u64)) {
+        state.0 = (state.0 * state.0 + 7) % 100;
+        state.1 += state.0 % 2;
+    }
+
+    fn brute_force(steps: usize) -> u64 {
+        let mut state = (3, 0);
+        (0..steps).for_each(|_| walk(&mut state));
+        state.1
+    }
+
+    #[test]
+    fn finds_where_the_cycle_starts() {
+        let (cycle, history) = find((3, 0), 1000, walk, |&(x, _)| Some(x), |&(x, _)| x);
+        let Cycle { start, length } = cycle.unwrap();
+        assert_eq!(history.len(), start + length + 1);
+        assert_eq!(history[start], history[start + length]);
+        // Nothing before the end of the cycle repeats
+        let distinct: std::collections::HashSet<u64> =
+            history[..start + length].iter().copied().collect();
+        assert_eq!(distinct.len(), start + length);
+    }
+
+    #[test]
+    fn extrapolates_to_any_number_of_steps() {
+        for steps in [0, 1, 5, 50, 99, 1000, 12345] {
+            let odd = simulate((3, 0), steps, walk, |&(x, _)| Some(x), |&(_, odd)| odd);
+            assert_eq!(odd, brute_force(steps), "after {steps} steps");
+        }
+    }
+
+    #[test]
+    fn steps_through_states_without_keys() {
+        // Only even states are remembered, so the cycle is found later but
+        // still extrapolates correctly
+        let key = |&(x, _): &(u64, u64)| (x % 2 == 0).then_some(x);
+        let odd = simulate((3, 0), 12345, walk, key, |&(_, odd)| odd);
+        assert_eq!(odd, brute_force(12345));
+        let (cycle, _) = find((3, 0), 1000, walk, |_| None::<u64>, |&(x, _)| x);
+        assert_eq!(cycle, None);
+    }
+
+    #[test]
+    fn extrapolates_several_metrics_at_once() {
+        let history: Vec<(i64, Vec<u32>)> = vec![
+            (0, vec![0, 0]),
+            (5, vec![1, 0]),
+            (7, vec![1, 2]),
+            (10, vec![2, 3]),
+        ];
+        let cycle = Cycle {
+            start: 1,
+            length: 2,
+        };
+        assert_eq!(cycle.extrapolate(&history, 2), (7, vec![1, 2]));
+        assert_eq!(cycle.extrapolate(&history, 5), (15, vec![3, 6]));
+        assert_eq!(cycle.extrapolate(&history, 6), (17, vec![3, 8]));
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::{anyhow, bail, Result};
+use common::{Solution, Text};
+use tracing::debug;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    /// The calories carried by each elf, most first.
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let text = Text::new(input);
+        // Each elf's items are a paragraph of their own
+        let mut sums = text
+            .paragraphs()
+            .into_iter()
+            .map(|elf| {
+                elf.lines()
+                    .map(|item| {
+                        item.parse::<i32>()
+                            .map_err(|_| text.error(item, "a number of calories"))
+                    })
+                    .sum::<Result<i32, _>>()
+            })
+            .collect::<Result<Vec<i32>, _>>()?;
+
+        sums.sort_by(|a, b| b.cmp(a));
+        Ok(sums)
+    }
+
+    fn part1(&self, sums: &Self::Input) -> Result<i32> {
+        sums.first()
+            .copied()
+            .ok_or_else(|| anyhow!("There are no elves"))
+    }
+
+    fn part2(&self, sums: &Self::Input) -> Result<i32> {
+        if sums.len() < 3 {
+            bail!("Expected at least three elves, found {}", sums.len());
+        }
+        debug!(top_three = ?&sums[0..3], "calories carried by the top three elves");
+        Ok(sums[0..3].iter().sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_each_elf_most_first() {
+        let sums = Day01.parse("1000\n2000\n\n4000\n\n500\n500\n").unwrap();
+        assert_eq!(sums, vec![4000, 3000, 1000]);
+    }
+
+    #[test]
+    fn counts_the_last_elf_without_a_blank_line() {
+        let sums = Day01.parse("1000\n\n2000\n3000").unwrap();
+        assert_eq!(sums, vec![5000, 1000]);
+    }
+
+    #[test]
+    fn copes_with_crlf_and_extra_blank_lines() {
+        let sums = Day01.parse("1000\r\n\r\n\r\n2000\r\n3000\r\n\r\n").unwrap();
+        assert_eq!(sums, vec![5000, 1000]);
+    }
+}
@@ -0,0 +1,16 @@
+use common::Solution;
+use day01::Day01;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day01.parse(EXAMPLE).unwrap();
+    assert_eq!(Day01.part1(&input).unwrap(), 24000);
+}
+
+#[test]
+fn part2() {
+    let input = Day01.parse(EXAMPLE).unwrap();
+    assert_eq!(Day01.part2(&input).unwrap(), 45000);
+}
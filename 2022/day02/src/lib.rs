@@ -0,0 +1,159 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+
+#[derive(Debug, Clone, Copy)]
+pub enum MovePoints {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+#[derive(Clone, Copy)]
+enum OutcomePoints {
+    Win = 6,
+    Draw = 3,
+    Lose = 0,
+}
+
+/// The second column of the strategy guide. Part one reads it as our move,
+/// part two as the outcome we need.
+#[derive(Debug, Clone, Copy)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<(MovePoints, Column)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut rounds = Vec::new();
+
+        for line in input.lines() {
+            let mut moves = line.split_whitespace();
+            let end_of_line = &line[line.len()..];
+            let p1 = moves.next().unwrap_or(end_of_line);
+            let p2 = moves.next().unwrap_or(end_of_line);
+
+            let p1_points = match p1 {
+                "A" => MovePoints::Rock,
+                "B" => MovePoints::Paper,
+                "C" => MovePoints::Scissors,
+                _ => return Err(ParseError::at(input, p1, "a move (A, B or C)").into()),
+            };
+
+            let column = match p2 {
+                "X" => Column::X,
+                "Y" => Column::Y,
+                "Z" => Column::Z,
+                _ => return Err(ParseError::at(input, p2, "a column (X, Y or Z)").into()),
+            };
+
+            rounds.push((p1_points, column));
+        }
+
+        Ok(rounds)
+    }
+
+    fn part1(&self, rounds: &Self::Input) -> Result<i32> {
+        let mut points = 0;
+
+        for &(p1_points, column) in rounds {
+            let p2_points = match column {
+                Column::X => MovePoints::Rock,
+                Column::Y => MovePoints::Paper,
+                Column::Z => MovePoints::Scissors,
+            };
+
+            let outcome = match p2_points {
+                MovePoints::Rock => match p1_points {
+                    MovePoints::Rock => OutcomePoints::Draw,
+                    MovePoints::Paper => OutcomePoints::Lose,
+                    MovePoints::Scissors => OutcomePoints::Win,
+                },
+                MovePoints::Paper => match p1_points {
+                    MovePoints::Rock => OutcomePoints::Win,
+                    MovePoints::Paper => OutcomePoints::Draw,
+                    MovePoints::Scissors => OutcomePoints::Lose,
+                },
+                MovePoints::Scissors => match p1_points {
+                    MovePoints::Rock => OutcomePoints::Lose,
+                    MovePoints::Paper => OutcomePoints::Win,
+                    MovePoints::Scissors => OutcomePoints::Draw,
+                },
+            };
+
+            points += p2_points as i32;
+            points += outcome as i32;
+        }
+
+        Ok(points)
+    }
+
+    fn part2(&self, rounds: &Self::Input) -> Result<i32> {
+        let mut points_part_2 = 0;
+
+        for &(p1_points, column) in rounds {
+            let p2_points_2: OutcomePoints = match column {
+                Column::X => OutcomePoints::Lose,
+                Column::Y => OutcomePoints::Draw,
+                Column::Z => OutcomePoints::Win,
+            };
+
+            let outcome_2 = match p2_points_2 {
+                OutcomePoints::Win => match p1_points {
+                    MovePoints::Rock => MovePoints::Paper,
+                    MovePoints::Paper => MovePoints::Scissors,
+                    MovePoints::Scissors => MovePoints::Rock,
+                },
+                OutcomePoints::Draw => match p1_points {
+                    MovePoints::Rock => MovePoints::Rock,
+                    MovePoints::Paper => MovePoints::Paper,
+                    MovePoints::Scissors => MovePoints::Scissors,
+                },
+                OutcomePoints::Lose => match p1_points {
+                    MovePoints::Rock => MovePoints::Scissors,
+                    MovePoints::Paper => MovePoints::Rock,
+                    MovePoints::Scissors => MovePoints::Paper,
+                },
+            };
+
+            points_part_2 += p2_points_2 as i32;
+            points_part_2 += outcome_2 as i32;
+        }
+
+        Ok(points_part_2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_each_round() {
+        let rounds = Day02.parse("A Y\nC Z\n").unwrap();
+        assert!(matches!(rounds[0], (MovePoints::Rock, Column::Y)));
+        assert!(matches!(rounds[1], (MovePoints::Scissors, Column::Z)));
+    }
+
+    #[test]
+    fn rejects_a_round_missing_a_move() {
+        assert!(Day02.parse("A\n").is_err());
+    }
+
+    #[test]
+    fn locates_an_invalid_move() {
+        let error = Day02.parse("A Y\nB Q\n").unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.found, "Q");
+    }
+}
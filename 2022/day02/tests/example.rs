@@ -0,0 +1,16 @@
+use common::Solution;
+use day02::Day02;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day02.parse(EXAMPLE).unwrap();
+    assert_eq!(Day02.part1(&input).unwrap(), 15);
+}
+
+#[test]
+fn part2() {
+    let input = Day02.parse(EXAMPLE).unwrap();
+    assert_eq!(Day02.part2(&input).unwrap(), 12);
+}
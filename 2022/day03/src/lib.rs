@@ -0,0 +1,140 @@
+use anyhow::{anyhow, Result};
+use common::{Solution, Text};
+use std::collections::HashSet;
+
+fn char_to_code(c: char) -> i32 {
+    let code = c as i32;
+    if (97..=122).contains(&code) {
+        code - 96
+    } else {
+        code - 38
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    /// One rucksack per line.
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let text = Text::new(input);
+        for line in text.lines() {
+            if let Some(at) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+                return Err(text.error(&line[at..], "an item (a-z or A-Z)").into());
+            }
+            if line.len() % 2 != 0 {
+                let expected = "a rucksack with the same number of items in each compartment";
+                return Err(text.error(line, expected).into());
+            }
+        }
+        // The elves are in groups of three
+        text.groups::<3>("another rucksack to finish the last group of three elves")?;
+
+        Ok(text.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, lines: &Self::Input) -> Result<i32> {
+        let mut sum: i32 = 0;
+
+        for line in lines {
+            // skip empty lines
+            if line.is_empty() {
+                continue;
+            }
+            // split the line in half
+            let (left, right) = line.split_at(line.len() / 2);
+
+            // create a set of chars for both halves
+            let left_chars: HashSet<_> = left.chars().collect();
+            let right_chars: HashSet<_> = right.chars().collect();
+
+            // find the intersection of the two sets
+            let intersection: HashSet<_> = left_chars.intersection(&right_chars).collect();
+
+            let c = intersection
+                .iter()
+                .next()
+                .ok_or_else(|| anyhow!("No item is in both compartments of {line}"))?;
+
+            sum += char_to_code(**c);
+        }
+
+        Ok(sum)
+    }
+
+    fn part2(&self, lines: &Self::Input) -> Result<i32> {
+        let mut sum_2: i32 = 0;
+
+        for (group, elves) in lines.chunks_exact(3).enumerate() {
+            let [line_1, line_2, line_3] = elves else {
+                unreachable!("chunks are three elves long");
+            };
+
+            let badge = line_1
+                .chars()
+                .find(|&c| line_2.contains(c) && line_3.contains(c))
+                .ok_or_else(|| {
+                    anyhow!("The group starting at elf {} has no badge", group * 3 + 1)
+                })?;
+            sum_2 += char_to_code(badge);
+        }
+
+        Ok(sum_2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::ParseError;
+
+    #[test]
+    fn prioritises_lowercase_before_uppercase() {
+        assert_eq!(char_to_code('a'), 1);
+        assert_eq!(char_to_code('z'), 26);
+        assert_eq!(char_to_code('A'), 27);
+        assert_eq!(char_to_code('Z'), 52);
+    }
+
+    #[test]
+    fn parses_one_rucksack_per_line() {
+        let rucksacks = Day03
+            .parse("vJrwpWtwJgWrhcsFMMfFFhFp\nttgJtRGJQctTZtZT\r\nPmmdzqPrVv\n\n")
+            .unwrap();
+        assert_eq!(
+            rucksacks,
+            vec!["vJrwpWtwJgWrhcsFMMfFFhFp", "ttgJtRGJQctTZtZT", "PmmdzqPrVv"]
+        );
+    }
+
+    #[test]
+    fn rejects_an_incomplete_group_of_elves() {
+        let error = Day03.parse("abAB\ncdCD\n").unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.found.as_str()), (3, ""));
+    }
+
+    #[test]
+    fn rejects_rucksacks_that_do_not_split_evenly() {
+        let error = Day03
+            .parse(
+                "abAB
+abc
+",
+            )
+            .unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.found.as_str()), (2, "abc"));
+        assert!(Day03
+            .parse(
+                "ab1c
+"
+            )
+            .is_err());
+    }
+}
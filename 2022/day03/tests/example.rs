@@ -0,0 +1,16 @@
+use common::Solution;
+use day03::Day03;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day03.parse(EXAMPLE).unwrap();
+    assert_eq!(Day03.part1(&input).unwrap(), 157);
+}
+
+#[test]
+fn part2() {
+    let input = Day03.parse(EXAMPLE).unwrap();
+    assert_eq!(Day03.part2(&input).unwrap(), 70);
+}
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use common::{ParseError, Solution, Text};
+
+/// The first and last section an elf is assigned to clean.
+type Assignment = (i32, i32);
+
+fn range_contains_range(range1: Assignment, range2: Assignment) -> bool {
+    let (r1, r2) = range1;
+    let (r3, r4) = range2;
+
+    r1 <= r3 && r2 >= r4
+}
+
+fn range_overlaps_range(range1: Assignment, range2: Assignment) -> bool {
+    let (r1, r2) = range1;
+    let (r3, r4) = range2;
+
+    r1 <= r3 && r2 >= r3 || r1 <= r4 && r2 >= r4
+}
+
+/// Parse a range like `2-4`, which is part of the line `input`.
+fn assignment(input: &str, range: &str) -> Result<Assignment, ParseError> {
+    range
+        .split_once('-')
+        .and_then(|(r1, r2)| Some((r1.parse::<i32>().ok()?, r2.parse::<i32>().ok()?)))
+        .ok_or_else(|| ParseError::at(input, range, "a range like 2-4"))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<(Assignment, Assignment)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let text = Text::new(input);
+        text.lines()
+            .map(|line| {
+                let (r1, r2) = line
+                    .split_once(',')
+                    .ok_or_else(|| text.error(line, "a pair of ranges like 2-4,6-8"))?;
+                Ok((assignment(&text, r1)?, assignment(&text, r2)?))
+            })
+            .collect()
+    }
+
+    fn part1(&self, pairs: &Self::Input) -> Result<i32> {
+        let mut result_p1 = 0;
+
+        for &(r1, r2) in pairs {
+            // if r1 contains r2 or if r2 contains r1, increment result_p1
+            if range_contains_range(r1, r2) || range_contains_range(r2, r1) {
+                result_p1 += 1;
+            }
+        }
+
+        Ok(result_p1)
+    }
+
+    fn part2(&self, pairs: &Self::Input) -> Result<i32> {
+        let mut result_p2 = 0;
+
+        for &(r1, r2) in pairs {
+            // if r1 and r2 overlap, increment result_p2
+            if range_overlaps_range(r1, r2) || range_overlaps_range(r2, r1) {
+                result_p2 += 1;
+            }
+        }
+
+        Ok(result_p2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_assignment_pairs() {
+        let pairs = Day04.parse("2-4,6-8\n12-80,3-3\n").unwrap();
+        assert_eq!(pairs, vec![((2, 4), (6, 8)), ((12, 80), (3, 3))]);
+        assert!(assignment("2:4", "2:4").is_err());
+
+        let error = Day04.parse("2-4,6-8\n2-4,x-8\n").unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.found, "x-8");
+    }
+
+    #[test]
+    fn compares_ranges() {
+        assert!(range_contains_range((2, 8), (3, 7)));
+        assert!(!range_contains_range((3, 7), (2, 8)));
+        assert!(range_overlaps_range((5, 7), (7, 9)));
+        assert!(!range_overlaps_range((2, 4), (6, 8)));
+    }
+}
@@ -0,0 +1,16 @@
+use common::Solution;
+use day04::Day04;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day04.parse(EXAMPLE).unwrap();
+    assert_eq!(Day04.part1(&input).unwrap(), 2);
+}
+
+#[test]
+fn part2() {
+    let input = Day04.parse(EXAMPLE).unwrap();
+    assert_eq!(Day04.part2(&input).unwrap(), 4);
+}
@@ -0,0 +1,271 @@
+use animate::{Animate, Frame, Hook};
+use anyhow::{anyhow, Result};
+use common::{Answer, Part, Solution, Text};
+use regex::Regex;
+
+#[derive(Debug)]
+pub struct Procedure {
+    n: i32,
+    from: usize,
+    to: usize,
+}
+
+/// The starting crate stacks, bottom to top, and the rearrangement
+/// procedure to apply to them.
+#[derive(Debug)]
+pub struct Input {
+    stacks: Vec<Vec<char>>,
+    procedures: Vec<Procedure>,
+}
+
+/// Parse a line like `move 1 from 2 to 1`, checking both stacks exist.
+fn procedure(line: &str, num_stacks: usize) -> Option<Procedure> {
+    let words: Vec<&str> = line.split(' ').collect();
+    let ["move", n, "from", from, "to", to] = words[..] else {
+        return None;
+    };
+    let stack = |s: &str| {
+        s.parse::<usize>()
+            .ok()
+            .filter(|&stack| (1..=num_stacks).contains(&stack))
+            .map(|stack| stack - 1)
+    };
+
+    Some(Procedure {
+        n: n.parse().ok()?,
+        from: stack(from)?,
+        to: stack(to)?,
+    })
+}
+
+fn parse(input: &str) -> Result<Input> {
+    let text = Text::new(input);
+
+    // build stacks
+    let [stacks_input, procedures_input] =
+        text.sections::<2>("a blank line and the procedure after the stacks")?;
+    let stacks_str: Vec<&str> = stacks_input.lines().collect();
+    let stacks_height = stacks_str.len() - 1;
+    // Count the numbers under the stacks rather than measure the line, which
+    // has lost its trailing spaces
+    let num_stacks = stacks_str.last().unwrap().split_whitespace().count();
+    let mut stacks: Vec<Vec<char>> = vec![vec![]; num_stacks];
+    let stack_regex = Regex::new(r"\[[A-Z]\]|    ").unwrap();
+    let crate_regex = Regex::new(r"[A-Z]").unwrap();
+    for stack_height_index in (0..stacks_height).rev() {
+        let stack_height = stacks_str[stack_height_index];
+        let mut s_index = 0;
+        for capture in stack_regex.captures_iter(stack_height) {
+            if capture[0].starts_with(' ') {
+                s_index += 1;
+                continue;
+            }
+            let container = &crate_regex.captures(&capture[0]).unwrap()[0];
+            if s_index >= num_stacks {
+                let found = capture.get(0).unwrap().as_str();
+                return Err(text.error(found, "no more stacks than are numbered").into());
+            }
+            stacks[s_index].push(container.chars().next().unwrap());
+            s_index += 1;
+        }
+    }
+
+    // build procedures
+    let procedures: Vec<Procedure> = procedures_input
+        .lines()
+        .map(|line| {
+            procedure(line, num_stacks).ok_or_else(|| {
+                let expected = format!(
+                    "a procedure like \"move 1 from 2 to 1\" between stacks 1 to {num_stacks}"
+                );
+                text.error(line, expected)
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Input { stacks, procedures })
+}
+
+/// Read the crate on top of each stack, left to right.
+fn tops(stacks: Vec<Vec<char>>) -> Result<String> {
+    let mut solution = "".to_owned();
+    for (i, stack) in stacks.iter().enumerate() {
+        solution.push(
+            *stack
+                .last()
+                .ok_or_else(|| anyhow!("Stack {} ends up empty", i + 1))?,
+        );
+    }
+    Ok(solution)
+}
+
+/// Take the crate off the top of stack `from`.
+fn take(stacks: &mut [Vec<char>], from: usize) -> Result<char> {
+    stacks[from]
+        .pop()
+        .ok_or_else(|| anyhow!("Stack {} runs out of crates", from + 1))
+}
+
+/// The stacks drawn the way the puzzle does, with the procedure that was
+/// just carried out. The frame follows the top of the stack moved to.
+fn frame(stacks: &[Vec<char>], procedure: &Procedure) -> Frame {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut picture = String::new();
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(container) => format!("[{container}]"),
+                None => "   ".to_string(),
+            })
+            .collect();
+        picture.push_str(&row.join(" "));
+        picture.push('\n');
+    }
+    let numbers: Vec<String> = (1..=stacks.len()).map(|n| format!(" {n} ")).collect();
+    picture.push_str(&numbers.join(" "));
+
+    let Procedure { n, from, to } = procedure;
+    Frame::new(picture)
+        .focus((to * 4 + 1, height - stacks[*to].len()))
+        .caption(format!("move {n} from {} to {}", from + 1, to + 1))
+}
+
+/// Carry out the procedures one crate at a time, sending `hook` a frame
+/// after each crate moves.
+fn crate_mover_9000(input: &Input, hook: &mut Hook) -> Result<String> {
+    let mut stacks = input.stacks.clone();
+
+    // execute procedures
+    for procedure in &input.procedures {
+        for _ in 0..procedure.n {
+            let container = take(&mut stacks, procedure.from)?;
+            stacks[procedure.to].push(container);
+            hook.frame(|| frame(&stacks, procedure));
+        }
+    }
+
+    tops(stacks)
+}
+
+/// Carry out the procedures moving all their crates at once, sending `hook`
+/// a frame after each procedure.
+fn crate_mover_9001(input: &Input, hook: &mut Hook) -> Result<String> {
+    let mut stacks = input.stacks.clone();
+
+    for procedure in &input.procedures {
+        let mut container_group: Vec<char> = vec![];
+        for _ in 0..procedure.n {
+            container_group.insert(0, take(&mut stacks, procedure.from)?);
+        }
+        stacks[procedure.to].append(&mut container_group);
+        hook.frame(|| frame(&stacks, procedure));
+    }
+
+    tops(stacks)
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Input;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<String> {
+        crate_mover_9000(input, &mut Hook::none())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<String> {
+        crate_mover_9001(input, &mut Hook::none())
+    }
+}
+
+impl Animate for Day05 {
+    fn animate(&self, input: &Self::Input, part: Part, hook: &mut Hook) -> Result<Answer> {
+        Ok(match part {
+            Part::One => crate_mover_9000(input, hook)?,
+            Part::Two => crate_mover_9001(input, hook)?,
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::ParseError;
+
+    #[test]
+    fn parses_stacks_bottom_to_top() {
+        let input = parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(
+            input.stacks,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(input.procedures.len(), 4);
+
+        let Procedure { n, from, to } = input.procedures[1];
+        assert_eq!((n, from, to), (3, 0, 2));
+    }
+
+    #[test]
+    fn copes_with_crlf_and_trimmed_lines() {
+        let example = include_str!("../example.txt").replace('\n', " \r\n");
+        let input = parse(&example).unwrap();
+        assert_eq!(input.stacks.len(), 3);
+        assert_eq!(input.procedures.len(), 4);
+    }
+
+    #[test]
+    fn needs_a_procedure_after_the_stacks() {
+        let error = parse("[A]\n 1\n").unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+
+    #[test]
+    fn locates_a_move_to_a_missing_stack() {
+        let example = include_str!("../example.txt").replace("to 1", "to 4");
+        let error = parse(&example).unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (6, 1));
+        assert_eq!(error.found, "move 1 from 2 to 4");
+    }
+
+    #[test]
+    fn frames_draw_the_stacks_like_the_puzzle() {
+        let input = parse(include_str!("../example.txt")).unwrap();
+        let mut frames = Vec::new();
+        let mut watcher = |frame: Frame| {
+            frames.push(frame);
+            std::ops::ControlFlow::Continue(())
+        };
+        crate_mover_9001(&input, &mut Hook::new(&mut watcher)).unwrap();
+
+        assert_eq!(frames.len(), 4);
+        let rows: Vec<String> = frames[0]
+            .rows
+            .iter()
+            .map(|row| row.iter().collect())
+            .collect();
+        assert_eq!(
+            rows,
+            ["[D]        ", "[N] [C]    ", "[Z] [M] [P]", " 1   2   3 "]
+        );
+        assert_eq!(frames[0].focus, Some((1, 0)));
+        assert_eq!(frames[0].caption, "move 1 from 2 to 1");
+    }
+
+    #[test]
+    fn fails_when_a_stack_runs_out() {
+        let input = parse(" 1 \n\nmove 1 from 1 to 1\n").unwrap();
+        assert!(Day05.part1(&input).is_err());
+    }
+}
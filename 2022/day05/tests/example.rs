@@ -0,0 +1,16 @@
+use common::Solution;
+use day05::Day05;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day05.parse(EXAMPLE).unwrap();
+    assert_eq!(Day05.part1(&input).unwrap(), "CMZ");
+}
+
+#[test]
+fn part2() {
+    let input = Day05.parse(EXAMPLE).unwrap();
+    assert_eq!(Day05.part2(&input).unwrap(), "MCD");
+}
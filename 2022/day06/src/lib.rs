@@ -0,0 +1,82 @@
+use anyhow::{anyhow, Result};
+use common::{Solution, Text};
+
+/// How many characters have been read once the last `n` are all different,
+/// or `None` if that never happens.
+fn count_until(input: &str, n: usize) -> Option<usize> {
+    // skip past every window of n characters with any duplicate characters
+    (0..(input.len() + 1).saturating_sub(n))
+        .find(|&i| {
+            !input[i..i + n]
+                .chars()
+                .any(|c| input[i..i + n].matches(c).count() > 1)
+        })
+        .map(|i| n + i)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    /// The datastream buffer.
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let text = Text::new(input);
+        let buffer = text.trim_end();
+        if let Some(at) = buffer.find(|c: char| !c.is_ascii_lowercase()) {
+            return Err(text.error(&buffer[at..], "a letter (a-z)").into());
+        }
+        Ok(buffer.to_string())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize> {
+        count_until(input, 4).ok_or_else(|| anyhow!("There's no start-of-packet marker"))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize> {
+        count_until(input, 14).ok_or_else(|| anyhow!("There's no start-of-message marker"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_start_of_packet_marker() {
+        assert_eq!(count_until("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4), Some(7));
+        assert_eq!(count_until("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), Some(5));
+        assert_eq!(count_until("nppdvjthqldpwncqszvftbrmjlhg", 4), Some(6));
+        assert_eq!(
+            count_until("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4),
+            Some(10)
+        );
+        assert_eq!(count_until("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4), Some(11));
+    }
+
+    #[test]
+    fn finds_the_start_of_message_marker() {
+        assert_eq!(count_until("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), Some(19));
+        assert_eq!(count_until("bvwbjplbgvbhsrlpgdmjqwftvncz", 14), Some(23));
+        assert_eq!(count_until("nppdvjthqldpwncqszvftbrmjlhg", 14), Some(23));
+        assert_eq!(
+            count_until("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14),
+            Some(29)
+        );
+        assert_eq!(
+            count_until("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14),
+            Some(26)
+        );
+    }
+
+    #[test]
+    fn finds_no_marker_in_a_short_buffer() {
+        assert_eq!(count_until("abcd", 4), Some(4));
+        assert_eq!(count_until("abca", 4), None);
+        assert_eq!(count_until("ab", 4), None);
+    }
+}
@@ -0,0 +1,16 @@
+use common::Solution;
+use day06::Day06;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day06.parse(EXAMPLE).unwrap();
+    assert_eq!(Day06.part1(&input).unwrap(), 7);
+}
+
+#[test]
+fn part2() {
+    let input = Day06.parse(EXAMPLE).unwrap();
+    assert_eq!(Day06.part2(&input).unwrap(), 19);
+}
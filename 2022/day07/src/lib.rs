@@ -0,0 +1,218 @@
+use anyhow::{anyhow, Result};
+use common::{ParseError, Solution};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct File {
+    size: usize,
+}
+
+type Path = Vec<String>;
+
+/// The directory tree rebuilt from the terminal output: the sub-directories
+/// and files found in each directory.
+#[derive(Debug)]
+pub struct Filesystem {
+    sub_dirs: HashMap<Path, Vec<Path>>,
+    files: HashMap<Path, Vec<File>>,
+}
+
+impl Filesystem {
+    fn dir_sizes(&self, root: Path) -> Result<HashMap<Path, usize>> {
+        let mut dir_size: HashMap<Path, usize> = HashMap::new();
+        let cursor: &mut Path = &mut root.clone();
+
+        'map_sub_dir: while !dir_size.contains_key(&root) {
+            let mut sum: usize = 0;
+
+            // If there are sub_dirs, get the size of each sub_dir. If the size has
+            // not been calculated, move the cursor into the map and restart the
+            // count.
+            let unlisted = || anyhow!("Directory {} was never listed", cursor.join("/"));
+            for sub_dir in self.sub_dirs.get(cursor).ok_or_else(unlisted)? {
+                if let Some(size) = dir_size.get(sub_dir) {
+                    sum += size;
+                } else {
+                    cursor.push(sub_dir.last().unwrap().clone());
+                    continue 'map_sub_dir;
+                }
+            }
+
+            // Compute files
+            for file in self.files.get(cursor).ok_or_else(unlisted)? {
+                sum += file.size;
+            }
+            dir_size.insert(cursor.clone(), sum);
+
+            // Now that we've mapped this dir, let's continue mapping the
+            // parent.
+            cursor.pop();
+        }
+
+        Ok(dir_size)
+    }
+}
+
+fn root() -> Path {
+    vec!["/".to_string()]
+}
+
+common::configure! {
+    /// The sizes of the device's disk, which are the same for the example
+    /// and the real puzzle.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Day07 {
+        /// The size of the whole disk.
+        pub disk_size: usize,
+        /// The unused space the update needs.
+        pub update_size: usize,
+    }
+    presets: ["puzzle" => Day07::PUZZLE]
+}
+
+impl Day07 {
+    pub const PUZZLE: Day07 = Day07 {
+        disk_size: 70_000_000,
+        update_size: 30_000_000,
+    };
+}
+
+impl Solution for Day07 {
+    type Input = Filesystem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        let mut dirs: HashMap<Path, Vec<Path>> = HashMap::new();
+        let mut files: HashMap<Path, Vec<File>> = HashMap::new();
+        let mut path: Path = vec![];
+
+        // Split the input "$". Each "$" represents an executed command.
+        let command_and_outputs: Vec<&str> = input.split("$ ").collect();
+
+        for command_and_output in command_and_outputs {
+            // The split token will be blank, because the input starts with "$ ",
+            // so we skip it.
+            if command_and_output.is_empty() {
+                continue;
+            }
+
+            let (command, output) = command_and_output
+                .split_once('\n')
+                .ok_or_else(|| anyhow!("Unterminated command {command_and_output:?}"))?;
+
+            let command_split: Vec<&str> = command.split(' ').collect();
+            match command_split[..] {
+                ["cd", new_dir_name] => {
+                    if new_dir_name == ".." {
+                        path.pop();
+                        continue;
+                    }
+
+                    path.push(new_dir_name.to_string());
+                }
+
+                ["ls"] => {
+                    let mut dir_dirs = vec![];
+                    let mut dir_files = vec![];
+                    for item in output.lines() {
+                        let (info, name) = item.split_once(' ').unwrap_or((item, ""));
+                        if info == "dir" {
+                            let mut path_clone = path.clone();
+                            path_clone.push(name.to_string());
+                            dir_dirs.push(path_clone);
+                        } else if let Ok(file_size) = info.parse::<usize>() {
+                            let file = File { size: file_size };
+                            dir_files.push(file);
+                        } else {
+                            let expected = "a listing like \"dir a\" or \"14848514 b.txt\"";
+                            return Err(ParseError::at(input, item, expected).into());
+                        }
+                    }
+
+                    dirs.insert(path.clone(), dir_dirs);
+                    files.insert(path.clone(), dir_files);
+                }
+
+                _ => {
+                    let expected = "a command like \"cd a\" or \"ls\"";
+                    return Err(ParseError::at(input, command, expected).into());
+                }
+            };
+        }
+
+        Ok(Filesystem {
+            sub_dirs: dirs,
+            files,
+        })
+    }
+
+    fn part1(&self, filesystem: &Self::Input) -> Result<usize> {
+        let dir_size = filesystem.dir_sizes(root())?;
+
+        let mut sum: usize = 0;
+        for d in &dir_size {
+            if *d.1 <= 100_000 {
+                sum += *d.1;
+            }
+        }
+
+        Ok(sum)
+    }
+
+    fn part2(&self, filesystem: &Self::Input) -> Result<usize> {
+        let dir_size = filesystem.dir_sizes(root())?;
+
+        let used_space = dir_size[&root()];
+        let unused_space = self
+            .disk_size
+            .checked_sub(used_space)
+            .ok_or_else(|| anyhow!("{used_space} is more than fits on the disk"))?;
+        let to_free = self.update_size.saturating_sub(unused_space);
+        let mut min: usize = usize::MAX;
+        for d in &dir_size {
+            if *d.1 < min && *d.1 >= to_free {
+                min = *d.1;
+            }
+        }
+
+        Ok(min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(dirs: &[&str]) -> Path {
+        dirs.iter().map(|dir| dir.to_string()).collect()
+    }
+
+    #[test]
+    fn sizes_include_sub_directories() {
+        let filesystem = Day07::PUZZLE.parse(include_str!("../example.txt")).unwrap();
+        let sizes = filesystem.dir_sizes(root()).unwrap();
+        assert_eq!(sizes[&path(&["/", "a", "e"])], 584);
+        assert_eq!(sizes[&path(&["/", "a"])], 94853);
+        assert_eq!(sizes[&path(&["/", "d"])], 24933642);
+        assert_eq!(sizes[&root()], 48381165);
+    }
+
+    #[test]
+    fn locates_an_unknown_command() {
+        let error = Day07::PUZZLE
+            .parse("$ cd /\n$ ls\ndir a\n$ rm a\n")
+            .unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (4, 3));
+        assert_eq!(error.found, "rm a");
+    }
+
+    #[test]
+    fn fails_on_a_directory_that_was_never_listed() {
+        let filesystem = Day07::PUZZLE.parse("$ cd /\n$ ls\ndir a\n").unwrap();
+        assert!(filesystem.dir_sizes(root()).is_err());
+    }
+}
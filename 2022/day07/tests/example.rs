@@ -0,0 +1,16 @@
+use common::Solution;
+use day07::Day07;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day07::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day07::PUZZLE.part1(&input).unwrap(), 95437);
+}
+
+#[test]
+fn part2() {
+    let input = Day07::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day07::PUZZLE.part2(&input).unwrap(), 24933642);
+}
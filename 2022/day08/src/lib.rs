@@ -0,0 +1,92 @@
+use anyhow::Result;
+use common::Solution;
+use grid::{Grid, Pos, ORTHOGONAL};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    /// Tree heights.
+    type Input = Grid<u32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Grid::parse(input, |c| c.to_digit(10))
+            .map_err(|e| e.locate(input, "a tree height (0-9)").into())
+    }
+
+    fn part1(&self, grid: &Self::Input) -> Result<i32> {
+        Ok(visible_trees(grid))
+    }
+
+    fn part2(&self, grid: &Self::Input) -> Result<i32> {
+        Ok(max_visibility(grid))
+    }
+}
+
+fn visible_trees(grid: &Grid<u32>) -> i32 {
+    // A tree is visible if, looking in from any direction, every tree in the
+    // way is shorter
+    let is_visible = |pos: Pos| {
+        let tree_height = grid[pos];
+        ORTHOGONAL
+            .iter()
+            .any(|&step| grid.ray(pos, step).all(|(_, &height)| height < tree_height))
+    };
+
+    grid.positions().filter(|&pos| is_visible(pos)).count() as i32
+}
+
+fn max_visibility(grid: &Grid<u32>) -> i32 {
+    // How many trees can be seen looking out from `pos`: every tree up to and
+    // including the first one that's at least as tall
+    let viewing_distance = |pos: Pos, step| {
+        let tree_height = grid[pos];
+        let mut distance = 0;
+        for (_, &height) in grid.ray(pos, step) {
+            distance += 1;
+            if height >= tree_height {
+                break;
+            }
+        }
+        distance
+    };
+
+    grid.positions()
+        .map(|pos| {
+            ORTHOGONAL
+                .iter()
+                .map(|&step| viewing_distance(pos, step))
+                .product()
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::ParseError;
+
+    #[test]
+    fn parses_tree_heights() {
+        let grid = Day08.parse("303\n255\n").unwrap();
+        assert_eq!(
+            grid,
+            Grid::from_rows(vec![vec![3, 0, 3], vec![2, 5, 5]]).unwrap()
+        );
+
+        let error = Day08.parse("303\n2x5\n").unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.found, "x");
+    }
+
+    #[test]
+    fn edge_trees_are_always_visible() {
+        let grid = Grid::from_rows(vec![vec![1, 1, 1], vec![1, 0, 1], vec![1, 1, 1]]).unwrap();
+        assert_eq!(visible_trees(&grid), 8);
+    }
+}
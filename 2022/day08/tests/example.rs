@@ -0,0 +1,16 @@
+use common::Solution;
+use day08::Day08;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day08.parse(EXAMPLE).unwrap();
+    assert_eq!(Day08.part1(&input).unwrap(), 21);
+}
+
+#[test]
+fn part2() {
+    let input = Day08.parse(EXAMPLE).unwrap();
+    assert_eq!(Day08.part2(&input).unwrap(), 8);
+}
@@ -0,0 +1,184 @@
+use animate::{Animate, Frame, Hook};
+use anyhow::Result;
+use common::{Answer, ParseError, Part, Solution};
+use geometry::{Dir4, Vec2};
+use std::collections::HashSet;
+
+/// A direction to move the head of the rope, and how many steps to take.
+type Command = (Dir4, u32);
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Command>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                let (dir, steps) = l.split_once(' ').unwrap_or((l, &l[l.len()..]));
+                let dir = match dir {
+                    "U" => Dir4::Up,
+                    "D" => Dir4::Down,
+                    "L" => Dir4::Left,
+                    "R" => Dir4::Right,
+                    _ => return Err(ParseError::at(input, dir, "a direction (U, D, L or R)")),
+                };
+                let steps = steps
+                    .parse()
+                    .map_err(|_| ParseError::at(input, steps, "a number of steps"))?;
+                Ok((dir, steps))
+            })
+            .collect::<Result<_, _>>()
+            .map_err(Into::into)
+    }
+
+    fn part1(&self, cmds: &Self::Input) -> Result<usize> {
+        Ok(tail_positions(cmds, &mut Hook::none()))
+    }
+
+    fn part2(&self, cmds: &Self::Input) -> Result<usize> {
+        Ok(long_tail_positions(cmds, &mut Hook::none()))
+    }
+}
+
+impl Animate for Day09 {
+    fn animate(&self, cmds: &Self::Input, part: Part, hook: &mut Hook) -> Result<Answer> {
+        Ok(match part {
+            Part::One => tail_positions(cmds, hook),
+            Part::Two => long_tail_positions(cmds, hook),
+        }
+        .into())
+    }
+}
+
+fn tail_positions(cmds: &[Command], hook: &mut Hook) -> usize {
+    rope_positions::<2>(cmds, hook)
+}
+
+fn long_tail_positions(cmds: &[Command], hook: &mut Hook) -> usize {
+    rope_positions::<10>(cmds, hook)
+}
+
+/// Pull a rope of `N` knots around and count the positions its tail visits,
+/// sending `hook` a frame at each step the head takes.
+fn rope_positions<const N: usize>(cmds: &[Command], hook: &mut Hook) -> usize {
+    let (mut knots, mut seen): ([Vec2<i32>; N], HashSet<_>) = ([Vec2::zero(); N], HashSet::new());
+    seen.insert(Vec2::zero());
+
+    for &(d, l) in cmds {
+        for _ in 0..l {
+            knots[0] += d.step();
+
+            for i in 1..N {
+                let h = knots[i - 1];
+                let t = &mut knots[i];
+                if h.chebyshev(*t) <= 1 {
+                    break;
+                }
+                // Each knot moves at most one step along each axis towards
+                // the one ahead of it.
+                *t += (h - *t).signum();
+                if i == N - 1 {
+                    seen.insert(*t);
+                }
+            }
+            hook.frame(|| frame(&knots, &seen));
+        }
+    }
+
+    seen.len()
+}
+
+/// The rope, drawn as the puzzle does with `H` for the head and the tail as
+/// `T` (or the knots numbered from 1), over the positions the tail visited.
+/// The frame follows the head.
+fn frame<const N: usize>(knots: &[Vec2<i32>; N], seen: &HashSet<Vec2<i32>>) -> Frame {
+    let cells = || seen.iter().chain(knots);
+    let min = Vec2::new(
+        cells().map(|cell| cell.x).min().unwrap_or(0),
+        cells().map(|cell| cell.y).min().unwrap_or(0),
+    );
+    let max = Vec2::new(
+        cells().map(|cell| cell.x).max().unwrap_or(0),
+        cells().map(|cell| cell.y).max().unwrap_or(0),
+    );
+    let at = |cell: Vec2<i32>| ((cell.x - min.x) as usize, (cell.y - min.y) as usize);
+
+    let row = ".".repeat((max.x - min.x + 1) as usize);
+    let mut frame = Frame::new(vec![row; (max.y - min.y + 1) as usize].join("\n"));
+    for &cell in seen {
+        frame = frame.plot(at(cell), '#');
+    }
+    // The knots ahead are drawn over the ones behind
+    for (i, &knot) in knots.iter().enumerate().rev() {
+        let glyph = match i {
+            0 => 'H',
+            _ if N == 2 => 'T',
+            i => char::from_digit(i as u32, 10).unwrap_or('*'),
+        };
+        frame = frame.plot(at(knot), glyph);
+    }
+    frame
+        .focus(at(knots[0]))
+        .caption(format!("{} positions visited", seen.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_directions_and_steps() {
+        let cmds = Day09.parse("R 4\nU 12\nL 3\nD 1\n").unwrap();
+        assert_eq!(
+            cmds,
+            vec![
+                (Dir4::Right, 4),
+                (Dir4::Up, 12),
+                (Dir4::Left, 3),
+                (Dir4::Down, 1)
+            ]
+        );
+
+        let error = Day09.parse("R 4\nR x\n").unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.found, "x");
+        assert!(Day09.parse("Q 4\n").is_err());
+    }
+
+    #[test]
+    fn long_tail_follows_the_larger_example() {
+        let cmds = Day09
+            .parse("R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n")
+            .unwrap();
+        assert_eq!(long_tail_positions(&cmds, &mut Hook::none()), 36);
+    }
+
+    #[test]
+    fn frames_follow_the_head() {
+        let cmds = Day09.parse("R 2\nU 1\n").unwrap();
+        let mut frames = Vec::new();
+        let mut watcher = |frame: Frame| {
+            frames.push(frame);
+            std::ops::ControlFlow::Continue(())
+        };
+        tail_positions(&cmds, &mut Hook::new(&mut watcher));
+
+        assert_eq!(frames.len(), 3);
+        let rows: Vec<String> = frames[2]
+            .rows
+            .iter()
+            .map(|row| row.iter().collect())
+            .collect();
+        assert_eq!(rows, ["..H", "#T."]);
+        assert_eq!(frames[2].focus, Some((2, 0)));
+        assert_eq!(frames[2].caption, "2 positions visited");
+    }
+}
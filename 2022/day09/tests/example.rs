@@ -0,0 +1,16 @@
+use common::Solution;
+use day09::Day09;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day09.parse(EXAMPLE).unwrap();
+    assert_eq!(Day09.part1(&input).unwrap(), 13);
+}
+
+#[test]
+fn part2() {
+    let input = Day09.parse(EXAMPLE).unwrap();
+    assert_eq!(Day09.part2(&input).unwrap(), 1);
+}
@@ -0,0 +1,256 @@
+use animate::{Animate, Frame, Hook};
+use common::{Answer, Part, Solution, Text};
+use export::{colours, Export, Image};
+use std::fmt::{Display, Formatter, Result};
+use tracing::{debug, trace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    fn parse(line: &str) -> Option<Self> {
+        match line.split_once(' ') {
+            None if line == "noop" => Some(Instruction::Noop),
+            Some(("addx", x)) => Some(Instruction::Addx(x.parse().ok()?)),
+            _ => None,
+        }
+    }
+}
+
+struct Device {
+    register: i32,
+    cycle: usize,
+    signal_strength: i32,
+    signal_strengths: Vec<i32>,
+    pixels: [bool; 240],
+}
+
+struct DeviceDisplay([bool; 240]);
+
+impl Display for DeviceDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for (idx, pixel) in self.0.iter().enumerate() {
+            // Wrap the pixel lines to a width of 40 characters
+            if (idx % 40 == 0) && idx > 0 {
+                writeln!(f)?;
+            }
+
+            // If the pixel is lit, print a '#', other wise print a space
+            let glyph = if *pixel { "#" } else { " " };
+            write!(f, "{glyph}")?;
+        }
+
+        write!(f, "") // Finish the print results
+    }
+}
+
+impl Device {
+    fn new() -> Self {
+        Self {
+            register: 1,
+            cycle: 1,
+            signal_strength: 0,
+            signal_strengths: Vec::new(),
+            pixels: [false; 240],
+        }
+    }
+
+    /// The screen as drawn so far, with the sprite's position under it.
+    fn frame(&self) -> Frame {
+        let sprite: String = (0..40)
+            .map(|x| {
+                if (x - self.register).abs() <= 1 {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let position = self.cycle - 1;
+        Frame::new(format!("{}\n\n{sprite}", DeviceDisplay(self.pixels)))
+            .focus((position % 40, position / 40))
+            .caption(format!("cycle {}, X = {}", self.cycle, self.register))
+    }
+
+    fn exc_noop(&mut self, hook: &mut Hook) {
+        trace!(cycle = self.cycle, register = self.register, "cycle");
+        // The CRT draws pixel `cycle - 1` during each cycle
+        let position = self.cycle - 1;
+        let sprite_range = (self.register - 1)..=(self.register + 1);
+        let line_pos = (position % 40) as i32;
+        // The screen only has 240 pixels, so a longer program draws nothing
+        // more
+        if sprite_range.contains(&line_pos) && position < self.pixels.len() {
+            self.pixels[position] = true;
+        }
+
+        let cycle_checkpoint = self.cycle.is_multiple_of(20);
+        let odd_multiple = (self.cycle / 20) % 2 == 1;
+
+        if cycle_checkpoint && odd_multiple {
+            self.signal_strength = (self.cycle as i32) * self.register;
+            self.signal_strengths.push(self.signal_strength);
+            debug!(
+                cycle = self.cycle,
+                register = self.register,
+                signal_strength = self.signal_strength,
+                "signal strength checkpoint"
+            );
+        }
+
+        hook.frame(|| self.frame());
+        self.cycle += 1;
+    }
+
+    fn exc_addx(&mut self, x: i32, hook: &mut Hook) {
+        // The register only changes once both cycles are done
+        self.exc_noop(hook);
+        self.exc_noop(hook);
+        self.register += x;
+    }
+
+    fn exc(&mut self, instr: Instruction, hook: &mut Hook) {
+        match instr {
+            Instruction::Noop => self.exc_noop(hook),
+            Instruction::Addx(x) => self.exc_addx(x, hook),
+        }
+    }
+}
+
+/// Run the program, sending `hook` a frame at each cycle.
+fn run(program: &[Instruction], hook: &mut Hook) -> Device {
+    let mut device = Device::new();
+    program
+        .iter()
+        .for_each(|&instruction| device.exc(instruction, hook));
+    device
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    /// The program, one instruction per line.
+    type Input = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        let expected = "an instruction like \"noop\" or \"addx -5\"";
+        Ok(common::parse_lines(
+            &Text::new(input),
+            expected,
+            Instruction::parse,
+        )?)
+    }
+
+    fn part1(&self, program: &Self::Input) -> anyhow::Result<i32> {
+        Ok(signal_strength(&run(program, &mut Hook::none())))
+    }
+
+    fn part2(&self, program: &Self::Input) -> anyhow::Result<String> {
+        Ok(DeviceDisplay(run(program, &mut Hook::none()).pixels).to_string())
+    }
+}
+
+impl Animate for Day10 {
+    fn animate(
+        &self,
+        program: &Self::Input,
+        part: Part,
+        hook: &mut Hook,
+    ) -> anyhow::Result<Answer> {
+        let device = run(program, hook);
+        Ok(match part {
+            Part::One => signal_strength(&device).into(),
+            Part::Two => DeviceDisplay(device.pixels).to_string().into(),
+        })
+    }
+}
+
+impl Export for Day10 {
+    /// The CRT once the program has run, a cell to a pixel.
+    fn picture(&self, program: &Self::Input) -> anyhow::Result<Image> {
+        let pixels = run(program, &mut Hook::none()).pixels;
+        Ok(Image::grid(40, 6, colours::BACKGROUND, |col, row| {
+            pixels[row * 40 + col].then_some(colours::GOLD)
+        }))
+    }
+}
+
+fn signal_strength(device: &Device) -> i32 {
+    device.signal_strengths.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addx_takes_two_cycles() {
+        let program = Day10.parse("noop\naddx 3\naddx -5\n").unwrap();
+        let device = run(&program, &mut Hook::none());
+        assert_eq!(device.cycle, 6);
+        assert_eq!(device.register, -1);
+    }
+
+    #[test]
+    fn draws_the_sprite_position_each_cycle() {
+        let program = Day10.parse("addx 15\naddx -11\naddx 6\n").unwrap();
+        let device = run(&program, &mut Hook::none());
+        assert_eq!(device.pixels[..6], [true, true, false, false, true, true]);
+    }
+
+    #[test]
+    fn frames_show_each_cycle() {
+        let program = Day10.parse("noop\naddx 3\n").unwrap();
+        let mut frames = Vec::new();
+        let mut watcher = |frame: Frame| {
+            frames.push(frame);
+            std::ops::ControlFlow::Continue(())
+        };
+        run(&program, &mut Hook::new(&mut watcher));
+
+        assert_eq!(frames.len(), 3);
+        let last = &frames[2];
+        assert_eq!(last.caption, "cycle 3, X = 1");
+        assert_eq!(last.focus, Some((2, 0)));
+        assert_eq!(last.rows[0][..3], ['#', '#', '#']);
+        assert_eq!(last.rows[7][..4], ['#', '#', '#', '.']);
+    }
+
+    #[test]
+    fn locates_an_unknown_instruction() {
+        let error = Day10.parse("noop\naddx 3\nmulx 2\n").unwrap_err();
+        let error = error.downcast_ref::<common::ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.found, "mulx 2");
+        assert!(Day10.parse("addx\n").is_err());
+    }
+
+    #[test]
+    fn picture_lights_a_pixel_per_lit_cell() {
+        let program = Day10.parse(include_str!("../example.txt")).unwrap();
+        let image = Day10.picture(&program).unwrap();
+        assert_eq!((image.width, image.height), (40.0, 6.0));
+        let raster = image.rasterize(1.0).unwrap();
+        // The example draws runs of two, three, four... lit pixels down the rows
+        let row = |y| -> String {
+            (0..8)
+                .map(|x| {
+                    if raster.get(x, y) == colours::GOLD {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        };
+        assert_eq!(row(0), "##..##..");
+        assert_eq!(row(1), "###...##");
+    }
+}
@@ -0,0 +1,27 @@
+use common::Solution;
+use day10::Day10;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+const SCREEN: &str = "\
+##  ##  ##  ##  ##  ##  ##  ##  ##  ##
+###   ###   ###   ###   ###   ###   ###
+####    ####    ####    ####    ####
+#####     #####     #####     #####
+######      ######      ######      ####
+#######       #######       #######";
+
+#[test]
+fn part1() {
+    let program = Day10.parse(EXAMPLE).unwrap();
+    assert_eq!(Day10.part1(&program).unwrap(), 13140);
+}
+
+#[test]
+fn part2() {
+    let program = Day10.parse(EXAMPLE).unwrap();
+    assert_eq!(
+        common::Answer::from(Day10.part2(&program).unwrap()),
+        SCREEN.into()
+    );
+}
@@ -0,0 +1,678 @@
+use anyhow::{anyhow, Context, Result};
+use common::Solution;
+use std::fmt::{self, Display};
+use tracing::{debug, debug_span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monkey {
+    /// The number in the `Monkey 0:` line heading its notes, which is also
+    /// its place in the list.
+    id: usize,
+    items: Vec<u64>,
+    operation: Operation,
+    rule: Rule,
+    inspected: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operation {
+    Add(u64),
+    Mult(u64),
+    Square,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    divisor: u64,
+    success: usize,
+    fail: usize,
+}
+
+struct Game {
+    monkeys: Vec<Monkey>,
+    items: Vec<(u64, usize)>,
+}
+
+impl Operation {
+    /// Apply an operation to an item's worry score, failing if the new
+    /// score is too big to keep track of.
+    fn apply(&self, item: u64) -> Result<u64> {
+        match self {
+            Operation::Add(n) => item.checked_add(*n),
+            Operation::Mult(n) => item.checked_mul(*n),
+            Operation::Square => item.checked_mul(item),
+        }
+        .ok_or_else(|| anyhow!("A worry level of {item} overflows new = {self}"))
+    }
+}
+
+impl Rule {
+    /// Check an item's worry score and return which monkey ID to throw
+    /// the item to.
+    fn check(&self, item: u64) -> usize {
+        if item.is_multiple_of(self.divisor) {
+            self.success
+        } else {
+            self.fail
+        }
+    }
+}
+
+/// The right-hand side of the operation's formula, like `old * 19`.
+impl Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Add(n) => write!(f, "old + {n}"),
+            Operation::Mult(n) => write!(f, "old * {n}"),
+            Operation::Square => write!(f, "old * old"),
+        }
+    }
+}
+
+/// The three lines of the test, indented as in the notes.
+impl Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  Test: divisible by {}", self.divisor)?;
+        writeln!(f, "    If true: throw to monkey {}", self.success)?;
+        write!(f, "    If false: throw to monkey {}", self.fail)
+    }
+}
+
+/// The notes on a monkey, headed by its number.
+impl Display for Monkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Monkey {}:", self.id)?;
+        let items: Vec<String> = self.items.iter().map(u64::to_string).collect();
+        writeln!(f, "  Starting items: {}", items.join(", "))?;
+        writeln!(f, "  Operation: new = {}", self.operation)?;
+        write!(f, "{}", self.rule)
+    }
+}
+
+impl Game {
+    fn from(monkeys: Vec<Monkey>) -> Self {
+        let items = Vec::new();
+        Game { items, monkeys }
+    }
+
+    fn play(&mut self) -> Result<()> {
+        for id in 0..self.monkeys.len() {
+            self.monkeys[id]
+                .handle_items(&mut self.items)
+                .with_context(|| format!("Monkey {id} can't inspect its items"))?;
+            while let Some((item, target)) = self.items.pop() {
+                self.monkeys[target].catch(item);
+            }
+        }
+        Ok(())
+    }
+
+    fn max_monkey_biz(&self) -> u64 {
+        // find the top two monkeys with the most inspected items and multiply those values together
+        let mut top_two = self.monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>();
+        top_two.sort();
+        top_two.reverse();
+        top_two[0] as u64 * top_two[1] as u64
+    }
+}
+
+/// Trace how many items each monkey inspected over a game.
+fn trace_inspections(monkeys: &[Monkey]) {
+    for (id, monkey) in monkeys.iter().enumerate() {
+        debug!(
+            monkey = id,
+            inspected = monkey.inspected,
+            "inspection count"
+        );
+    }
+}
+
+impl Monkey {
+    fn handle_items(&mut self, items: &mut Vec<(u64, usize)>) -> Result<()> {
+        // For each item the monkey has...
+        while let Some(mut item) = self.items.pop() {
+            // Increase your worry over that item according to the puzzle rules.
+            item = self.operation.apply(item)?;
+
+            // Calm down a bit since the monkey didn't break it (this time).
+            item /= 3;
+
+            // Have the monkey decide on a target with a mischievous gleam in
+            // its beady monkey eyes.
+            let target = self.rule.check(item);
+
+            // Toss the item to its intended target.
+            items.push((item, target));
+
+            // Increment the number of items this monkey has inspected
+            self.inspected += 1;
+        }
+        Ok(())
+    }
+
+    /// Catch an item thrown from another monkey. Probably pretend to fumble it
+    /// or something just to get that human even more riled up.
+    fn catch(&mut self, item: u64) {
+        self.items.push(item);
+    }
+
+    fn handle_items_roughly(
+        &mut self,
+        absolute_limit: u64,
+        items: &mut Vec<(u64, usize)>,
+    ) -> Result<()> {
+        while let Some(mut item) = self.items.pop() {
+            // Increase your worry over that item according to the puzzle rules.
+            item = self.operation.apply(item)?;
+
+            // Black out for a moment from the stress caused by these monkeys
+            // tossing your precious things about, experiencing an odd form of
+            // amnesia and "resetting" your stress levels a bit.
+            item %= absolute_limit;
+
+            // Have the monkey decide on a target with a malicious glint in
+            // its beady monkey eyes.
+            let target = self.rule.check(item);
+
+            // Toss the item to its intended target.
+            items.push((item, target));
+
+            // Increment the number of items this monkey has inspected
+            self.inspected += 1;
+        }
+        Ok(())
+    }
+}
+
+mod parser {
+    use super::*;
+    use anyhow::{bail, Result};
+    use common::Text;
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{newline, u64},
+        combinator::{cut, map, value},
+        sequence::{delimited, preceded},
+        IResult,
+    };
+    use parsing::{blocks, commas, field, finish};
+
+    /// Nom parser for "Monkey 3:" -> 3usize
+    fn id(s: &str) -> IResult<&str, usize> {
+        map(delimited(tag("Monkey "), u64, tag(":")), |n| n as usize)(s)
+    }
+
+    /// Nom parser for "Starting items: 1, 2, 3" -> vec![1, 2, 3]
+    fn items(s: &str) -> IResult<&str, Vec<u64>> {
+        field("Starting items", commas(u64))(s)
+    }
+
+    /// Nom parser for "+ 5" -> Operation::Add(5)
+    fn add_op(s: &str) -> IResult<&str, Operation> {
+        map(preceded(tag("+ "), u64), Operation::Add)(s)
+    }
+
+    /// Nom parser for "* 5" -> Operation::Mult(5)
+    fn mult_op(s: &str) -> IResult<&str, Operation> {
+        map(preceded(tag("* "), u64), Operation::Mult)(s)
+    }
+
+    /// Nom parser for "* old" -> Operation::Square
+    fn square_op(s: &str) -> IResult<&str, Operation> {
+        value(Operation::Square, tag("* old"))(s)
+    }
+
+    /// Nom parser for:
+    /// - "Operation: new = old + 5" -> Operation::Add(5)
+    /// - "Operation: new = old * 5" -> Operation::Mult(5)
+    /// - "Operation: new = old * old" -> Operation::Square
+    fn op(s: &str) -> IResult<&str, Operation> {
+        let operator = alt((add_op, mult_op, square_op));
+        field("Operation", preceded(tag("new = old "), operator))(s)
+    }
+
+    /// Nom parser for extracting the relevant values from the three
+    /// lines that describe the rules the monkey uses to determine where
+    /// to throw your item, used ton construct a `Rule`. For example:
+    ///
+    ///   Test: divisible by 17
+    ///     If true: throw to monkey 0
+    ///     If false: throw to monkey 5
+    ///
+    /// becomes
+    ///
+    /// Rule { divisor: 17, success: 0, fail: 5 }
+    fn test_rule(s: &str) -> IResult<&str, Rule> {
+        let target = || map(preceded(tag("throw to monkey "), u64), |n| n as usize);
+        let (s, divisor) = field("Test", preceded(tag("divisible by "), u64))(s)?;
+        let (s, success) = preceded(newline, field("If true", target()))(s)?;
+        let (s, fail) = preceded(newline, field("If false", target()))(s)?;
+        let rule = Rule {
+            divisor,
+            success,
+            fail,
+        };
+        Ok((s, rule))
+    }
+
+    /// Nom parser for converting a chunk of the input into a `Monkey`.
+    fn monkey(s: &str) -> IResult<&str, Monkey> {
+        let (s, id) = id(s)?;
+        let (s, items) = preceded(newline, items)(s)?;
+        let (s, operation) = preceded(newline, op)(s)?;
+        let (s, rule) = preceded(newline, test_rule)(s)?;
+
+        let monkey = Monkey {
+            id,
+            items,
+            operation,
+            rule,
+            inspected: 0,
+        };
+        Ok((s, monkey))
+    }
+
+    /// Parses the monkeys, which are separated by blank lines. Returns the
+    /// list of `Monkey`s if successful or a `ParseError` pointing at the
+    /// first thing that isn't part of a monkey if not. The monkeys must be
+    /// numbered from 0 in order, every monkey must throw to monkeys that
+    /// exist, and there must be at least two of them to score the game.
+    pub(crate) fn parse(s: &str) -> Result<Vec<Monkey>> {
+        let text = Text::new(s);
+        let expected = "notes on a monkey like the example's";
+        let monkeys = finish(&text, expected, blocks(cut(monkey)))?;
+
+        // The blocks of notes are the text's paragraphs, so the header of a
+        // monkey out of order is the first line of its paragraph
+        let paragraphs = text.paragraphs();
+        for (position, (monkey, notes)) in monkeys.iter().zip(paragraphs).enumerate() {
+            if monkey.id != position {
+                let header = notes.lines().next().unwrap_or_default();
+                let expected = format!("the notes on monkey {position}");
+                return Err(text.error(header, expected).into());
+            }
+        }
+
+        if monkeys.len() < 2 {
+            bail!("Expected at least two monkeys, found {}", monkeys.len());
+        }
+        for (id, monkey) in monkeys.iter().enumerate() {
+            let Rule {
+                divisor,
+                success,
+                fail,
+            } = monkey.rule;
+            if divisor == 0 {
+                bail!("Monkey {id} tests for divisibility by 0");
+            }
+            if let Some(target) = [success, fail].into_iter().find(|&t| t >= monkeys.len()) {
+                bail!("Monkey {id} throws to monkey {target}, which doesn't exist");
+            }
+        }
+        Ok(monkeys)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use common::ParseError;
+        use proptest::prelude::*;
+
+        fn any_operation() -> impl Strategy<Value = Operation> {
+            prop_oneof![
+                any::<u64>().prop_map(Operation::Add),
+                any::<u64>().prop_map(Operation::Mult),
+                Just(Operation::Square),
+            ]
+        }
+
+        /// A monkey with at least one item, testing for a non-zero divisor,
+        /// that throws to one of `count` monkeys.
+        fn any_monkey(count: usize) -> impl Strategy<Value = Monkey> {
+            let items = prop::collection::vec(any::<u64>(), 1..8);
+            let rule =
+                (1..=u64::MAX, 0..count, 0..count).prop_map(|(divisor, success, fail)| Rule {
+                    divisor,
+                    success,
+                    fail,
+                });
+            (items, any_operation(), rule).prop_map(|(items, operation, rule)| Monkey {
+                id: 0,
+                items,
+                operation,
+                rule,
+                inspected: 0,
+            })
+        }
+
+        /// Two or more monkeys that only throw to each other.
+        fn any_monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+            (2..10usize)
+                .prop_flat_map(|count| prop::collection::vec(any_monkey(count), count))
+                .prop_map(|monkeys| {
+                    let numbered = monkeys.into_iter().enumerate();
+                    numbered
+                        .map(|(id, monkey)| Monkey { id, ..monkey })
+                        .collect()
+                })
+        }
+
+        /// The notes on `monkeys`, separated by blank lines.
+        fn notes(monkeys: &[Monkey]) -> String {
+            let notes: Vec<String> = monkeys.iter().map(|monkey| format!("{monkey}\n")).collect();
+            notes.join("\n")
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(2000))]
+
+            #[test]
+            fn monkeys_round_trip(expected in any_monkeys()) {
+                prop_assert_eq!(parse(&notes(&expected)).unwrap(), expected);
+            }
+        }
+
+        #[test]
+        fn writes_the_example_back_out() {
+            let example = include_str!("../example.txt");
+            assert_eq!(notes(&parse(example).unwrap()), example);
+        }
+
+        #[test]
+        fn parses_a_monkey() {
+            let input = "\
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3";
+
+            let (rest, parsed) = monkey(input).unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(
+                parsed,
+                Monkey {
+                    id: 2,
+                    items: vec![79, 60, 97],
+                    operation: Operation::Square,
+                    rule: Rule {
+                        divisor: 13,
+                        success: 1,
+                        fail: 3,
+                    },
+                    inspected: 0,
+                }
+            );
+        }
+
+        #[test]
+        fn parses_each_operation() {
+            let parse = |s| op(s).unwrap().1;
+            assert_eq!(parse("  Operation: new = old + 6"), Operation::Add(6));
+            assert_eq!(parse("  Operation: new = old * 19"), Operation::Mult(19));
+            assert_eq!(parse("  Operation: new = old * old"), Operation::Square);
+        }
+
+        #[test]
+        fn parses_monkeys_separated_by_blank_lines() {
+            let monkeys = parse(include_str!("../example.txt")).unwrap();
+            assert_eq!(monkeys.len(), 4);
+            assert_eq!(monkeys[3].items, vec![74]);
+            assert_eq!(monkeys[3].operation, Operation::Add(3));
+        }
+
+        #[test]
+        fn copes_with_crlf_and_extra_blank_lines() {
+            let example = include_str!("../example.txt")
+                .replace('\n', "\r\n")
+                .replace("\r\n\r\n", "\r\n  \r\n\r\n");
+            assert_eq!(
+                parse(&example).unwrap(),
+                parse(include_str!("../example.txt")).unwrap()
+            );
+        }
+
+        #[test]
+        fn locates_a_mistake_in_a_later_monkey() {
+            let example = include_str!("../example.txt").replace("old + 3", "old - 3");
+            let error = parse(&example).unwrap_err();
+            let error = error.downcast_ref::<ParseError>().unwrap();
+            assert_eq!((error.line, error.column), (24, 24));
+            assert_eq!(error.found, "- 3");
+        }
+
+        #[test]
+        fn locates_a_monkey_out_of_order() {
+            let example = include_str!("../example.txt").replace("Monkey 2:", "Monkey 7:");
+            let error = parse(&example).unwrap_err();
+            let error = error.downcast_ref::<ParseError>().unwrap();
+            assert_eq!((error.line, error.column), (15, 1));
+            assert_eq!(error.found, "Monkey 7:");
+            assert_eq!(error.expected, "the notes on monkey 2");
+        }
+
+        #[test]
+        fn rejects_throws_to_missing_monkeys() {
+            let example = include_str!("../example.txt").replace("monkey 3", "monkey 4");
+            assert!(parse(&example).is_err());
+        }
+    }
+}
+
+struct LongGame {
+    items: Vec<(u64, usize)>,
+    monkeys: Vec<Monkey>,
+    absolute_limit: u64,
+}
+
+impl LongGame {
+    fn from(monkeys: Vec<Monkey>) -> Result<Self> {
+        let items = Vec::new();
+        let absolute_limit = monkeys
+            .iter()
+            .try_fold(1u64, |product, m| product.checked_mul(m.rule.divisor))
+            .context("The monkeys' divisors multiply to more than a worry level can hold")?;
+        Ok(LongGame {
+            items,
+            monkeys,
+            absolute_limit,
+        })
+    }
+
+    fn play_rough(&mut self) -> Result<()> {
+        for id in 0..self.monkeys.len() {
+            self.monkeys[id]
+                .handle_items_roughly(self.absolute_limit, &mut self.items)
+                .with_context(|| format!("Monkey {id} can't inspect its items"))?;
+
+            while let Some((item, target)) = self.items.pop() {
+                self.monkeys[target].catch(item);
+            }
+        }
+        Ok(())
+    }
+
+    fn max_monkey_biz(&self) -> u64 {
+        // find the top two monkeys with the most inspected items and multiply those values together
+        let mut top_two = self.monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>();
+        top_two.sort();
+        top_two.reverse();
+        top_two[0] as u64 * top_two[1] as u64
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    /// Parse input in to a vector of monkeys
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parser::parse(input)
+    }
+
+    fn part1(&self, monkeys: &Self::Input) -> anyhow::Result<u64> {
+        let _span = debug_span!("game", rounds = 20).entered();
+        let mut game = Game::from(monkeys.clone());
+
+        for _ in 0..20 {
+            game.play()?;
+        }
+        trace_inspections(&game.monkeys);
+
+        Ok(game.max_monkey_biz())
+    }
+
+    fn part2(&self, monkeys: &Self::Input) -> anyhow::Result<u64> {
+        let _span = debug_span!("game", rounds = 10_000).entered();
+        let mut long_game = LongGame::from(monkeys.clone())?;
+
+        for _ in 0..10_000 {
+            long_game.play_rough()?;
+        }
+        trace_inspections(&long_game.monkeys);
+
+        Ok(long_game.max_monkey_biz())
+    }
+}
+
+/// The game played with the worry levels in full, to check the modulo in
+/// `LongGame` against.
+#[cfg(test)]
+mod reference {
+    use super::*;
+    use num_bigint::BigUint;
+    use proptest::prelude::*;
+
+    /// How many items each monkey inspects over `rounds` rounds, with
+    /// nothing keeping the worry levels down.
+    fn inspections(monkeys: &[Monkey], rounds: usize) -> Vec<u32> {
+        let mut items: Vec<Vec<BigUint>> = monkeys
+            .iter()
+            .map(|monkey| monkey.items.iter().map(|&item| item.into()).collect())
+            .collect();
+        let mut inspected = vec![0; monkeys.len()];
+        for _ in 0..rounds {
+            for (id, monkey) in monkeys.iter().enumerate() {
+                for item in std::mem::take(&mut items[id]) {
+                    let item = match monkey.operation {
+                        Operation::Add(n) => item + n,
+                        Operation::Mult(n) => item * n,
+                        Operation::Square => &item * &item,
+                    };
+                    let divisible = (&item % monkey.rule.divisor) == BigUint::ZERO;
+                    let target = if divisible {
+                        monkey.rule.success
+                    } else {
+                        monkey.rule.fail
+                    };
+                    items[target].push(item);
+                    inspected[id] += 1;
+                }
+            }
+        }
+        inspected
+    }
+
+    /// Notes like the puzzle's: a handful of monkeys testing for different
+    /// primes, with small items, additions and multiplications, at most one
+    /// squaring the worry level, and none throwing to itself.
+    fn any_monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+        let primes = vec![2u64, 3, 5, 7, 11, 13, 17, 19, 23];
+        (2..=8usize)
+            .prop_flat_map(move |count| {
+                let divisors = prop::sample::subsequence(primes.clone(), count).prop_shuffle();
+                let operation = prop_oneof![
+                    (1..10u64).prop_map(Operation::Add),
+                    (2..20u64).prop_map(Operation::Mult),
+                ];
+                let monkey = (
+                    prop::collection::vec(1..100u64, 0..5),
+                    operation,
+                    0..count - 1,
+                    0..count - 1,
+                );
+                let monkeys = prop::collection::vec(monkey, count);
+                (divisors, monkeys, prop::option::of(0..count))
+            })
+            .prop_map(|(divisors, monkeys, squaring)| {
+                // Skip over the monkey itself when picking who to throw to
+                let other = |id: usize, target: usize| target + usize::from(target >= id);
+                monkeys
+                    .into_iter()
+                    .zip(divisors)
+                    .enumerate()
+                    .map(
+                        |(id, ((items, operation, success, fail), divisor))| Monkey {
+                            id,
+                            items,
+                            operation: match squaring {
+                                Some(squaring) if squaring == id => Operation::Square,
+                                _ => operation,
+                            },
+                            rule: Rule {
+                                divisor,
+                                success: other(id, success),
+                                fail: other(id, fail),
+                            },
+                            inspected: 0,
+                        },
+                    )
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn keeping_worry_down_inspects_the_same_items(
+            monkeys in any_monkeys(),
+            rounds in 1..=10usize,
+        ) {
+            let mut long_game = LongGame::from(monkeys.clone()).unwrap();
+            for _ in 0..rounds {
+                long_game.play_rough().unwrap();
+            }
+            let inspected: Vec<u32> = long_game.monkeys.iter().map(|m| m.inspected).collect();
+            prop_assert_eq!(inspected, inspections(&monkeys, rounds));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two monkeys throwing an item back and forth, one squaring its worry
+    /// level.
+    fn squarer(item: u64, divisor: u64) -> Vec<Monkey> {
+        let monkey = |id, operation, target| Monkey {
+            id,
+            items: vec![item],
+            operation,
+            rule: Rule {
+                divisor,
+                success: target,
+                fail: target,
+            },
+            inspected: 0,
+        };
+        vec![
+            monkey(0, Operation::Square, 1),
+            monkey(1, Operation::Add(1), 0),
+        ]
+    }
+
+    #[test]
+    fn fails_when_worry_levels_overflow() {
+        let error = Day11.part1(&squarer(5_000_000_000, 2)).unwrap_err();
+        assert_eq!(error.to_string(), "Monkey 0 can't inspect its items");
+        assert!(Day11.part2(&squarer(5_000_000_000, 2)).is_err());
+        assert!(Day11.part2(&squarer(1, u64::MAX)).is_err());
+    }
+}
@@ -0,0 +1,16 @@
+use common::Solution;
+use day11::Day11;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day11.parse(EXAMPLE).unwrap();
+    assert_eq!(Day11.part1(&input).unwrap(), 10605);
+}
+
+#[test]
+fn part2() {
+    let input = Day11.parse(EXAMPLE).unwrap();
+    assert_eq!(Day11.part2(&input).unwrap(), 2713310158);
+}
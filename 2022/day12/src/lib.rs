@@ -0,0 +1,191 @@
+use anyhow::anyhow;
+use common::Solution;
+use export::{colours, Export, Image, Shape};
+use grid::{Grid, Pos};
+
+#[derive(Clone)]
+#[allow(clippy::enum_variant_names)]
+enum Hill {
+    Start(u8),
+    End(u8),
+    Hill(u8),
+}
+
+impl Hill {
+    fn from_char(value: char) -> Option<Self> {
+        match value {
+            'S' => Some(Hill::Start(0)),
+            'E' => Some(Hill::End(25)),
+            c if c.is_ascii_lowercase() => Some(Hill::Hill(value as u8 - b'a')),
+            _ => None,
+        }
+    }
+
+    fn height(&self) -> u8 {
+        match self {
+            Hill::Start(h) => *h,
+            Hill::End(h) => *h,
+            Hill::Hill(h) => *h,
+        }
+    }
+
+    fn can_reach(&self, other: &Hill) -> bool {
+        other.height().saturating_sub(self.height()) <= 1
+    }
+}
+
+pub struct HillMap {
+    hills: Grid<Hill>,
+    start_at: Pos,
+    end_at: Pos,
+}
+
+impl HillMap {
+    fn parse(s: &str) -> anyhow::Result<Self> {
+        let hills = Grid::parse(s, Hill::from_char)
+            .map_err(|e| e.locate(s, "a height (a-z), the start (S) or the summit (E)"))?;
+        let start_at = hills
+            .position(|hill| matches!(hill, Hill::Start(_)))
+            .ok_or_else(|| anyhow!("The map has no start (S)"))?;
+        let end_at = hills
+            .position(|hill| matches!(hill, Hill::End(_)))
+            .ok_or_else(|| anyhow!("The map has no summit (E)"))?;
+
+        Ok(HillMap {
+            hills,
+            start_at,
+            end_at,
+        })
+    }
+
+    /// The neighbouring hills that can be climbed to from `pos`.
+    fn climbs(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.hills
+            .neighbours4(pos)
+            .filter(move |&neighbor| self.hills[pos].can_reach(&self.hills[neighbor]))
+    }
+
+    /// The neighbouring hills that `pos` can be climbed to from, for walking
+    /// the map backwards from the summit.
+    fn descents(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.hills
+            .neighbours4(pos)
+            .filter(move |&neighbor| self.hills[neighbor].can_reach(&self.hills[pos]))
+    }
+
+    fn climb_to_summit(&self, start_at: Pos) -> search::Search<Pos, usize> {
+        search::bfs(&|&pos: &Pos| self.climbs(pos), start_at, |&pos| {
+            pos == self.end_at
+        })
+    }
+
+    fn shortest_path_to_summit(&self, start_at: Pos) -> Option<u32> {
+        let search = self.climb_to_summit(start_at);
+        search.goal_cost().map(|steps| steps as u32)
+    }
+
+    /// Walk backwards from the summit to the nearest hill at the lowest
+    /// height.
+    fn shortest_path_from_summit(&self) -> Option<u32> {
+        let search = search::bfs(&|&pos: &Pos| self.descents(pos), self.end_at, |&pos| {
+            matches!(self.hills[pos], Hill::Hill(0))
+        });
+        search.goal_cost().map(|steps| steps as u32)
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = HillMap;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        HillMap::parse(input)
+    }
+
+    fn part1(&self, hill_map: &Self::Input) -> anyhow::Result<u32> {
+        let start_at = hill_map.start_at;
+
+        hill_map
+            .shortest_path_to_summit(start_at)
+            .ok_or_else(|| anyhow!("There's no way from the start to the summit"))
+    }
+
+    fn part2(&self, hill_map: &Self::Input) -> anyhow::Result<u32> {
+        hill_map
+            .shortest_path_from_summit()
+            .ok_or_else(|| anyhow!("There's no way to the summit from the lowest hills"))
+    }
+}
+
+impl Export for Day12 {
+    /// The height map, brighter higher up, with the shortest route from the
+    /// start to the summit drawn over it.
+    fn picture(&self, hill_map: &Self::Input) -> anyhow::Result<Image> {
+        let route = hill_map
+            .climb_to_summit(hill_map.start_at)
+            .path()
+            .ok_or_else(|| anyhow!("There's no way from the start to the summit"))?;
+        let hills = &hill_map.hills;
+        let mut image = Image::grid(
+            hills.width(),
+            hills.height(),
+            colours::BACKGROUND,
+            |col, row| {
+                let height = hills[(row, col)].height() as f64 / 25.0;
+                Some(colours::DIM.blend(colours::BRIGHT_GREEN, height))
+            },
+        );
+        image.push(Shape::Line {
+            points: route
+                .iter()
+                .map(|&(row, col)| (col as f64 + 0.5, row as f64 + 0.5))
+                .collect(),
+            stroke: colours::GOLD,
+            width: 0.3,
+        });
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_start_and_summit() {
+        let hill_map = Day12.parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(hill_map.start_at, (0, 0));
+        assert_eq!(hill_map.end_at, (2, 5));
+        assert_eq!(hill_map.shortest_path_to_summit((4, 0)), Some(29));
+        assert_eq!(hill_map.shortest_path_from_summit(), Some(29));
+    }
+
+    #[test]
+    fn fails_when_the_summit_is_out_of_reach() {
+        let error = Day12.parse("Sab\nabE\n9bz\n").err().unwrap();
+        let error = error.downcast_ref::<common::ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (3, 1));
+
+        let hill_map = Day12.parse("SaE\n").unwrap();
+        assert!(Day12.part1(&hill_map).is_err());
+    }
+
+    #[test]
+    fn picture_draws_the_route_over_the_map() {
+        let hill_map = Day12.parse(include_str!("../example.txt")).unwrap();
+        let image = Day12.picture(&hill_map).unwrap();
+        assert_eq!((image.width, image.height), (8.0, 5.0));
+        let Some(Shape::Line { points, .. }) = image.shapes.last() else {
+            panic!("the route should be drawn last");
+        };
+        // From the start to the summit in 31 steps, through cell centres
+        assert_eq!(points.len(), 32);
+        assert_eq!(points[0], (0.5, 0.5));
+        assert_eq!(points[31], (5.5, 2.5));
+    }
+}
@@ -0,0 +1,16 @@
+use common::Solution;
+use day12::Day12;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day12.parse(EXAMPLE).unwrap();
+    assert_eq!(Day12.part1(&input).unwrap(), 31);
+}
+
+#[test]
+fn part2() {
+    let input = Day12.parse(EXAMPLE).unwrap();
+    assert_eq!(Day12.part2(&input).unwrap(), 29);
+}
@@ -0,0 +1,303 @@
+use common::Solution;
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use Packet::{Integer, List};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Integer(u8),
+    List(Vec<Packet>),
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use Packet::*;
+        match (self, other) {
+            (Integer(i1), Integer(i2)) => i1.cmp(i2),
+            (Integer(i), List(_)) => List(vec![Integer(*i)]).cmp(other),
+            (List(_), Integer(i)) => self.cmp(&List(vec![Integer(*i)])),
+            (List(l1), List(l2)) => l1.cmp(l2),
+        }
+    }
+}
+
+/// Packets are written as they appear in the input, like `[1,[2,3]]`.
+impl Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Integer(i) => write!(f, "{i}"),
+            List(packets) => {
+                write!(f, "[")?;
+                for (idx, packet) in packets.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{packet}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Represents a pair of packets. Riveting stuff!
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketPair(Packet, Packet);
+
+/// One packet per line, as a pair appears in the input.
+impl Display for PacketPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(first, second) = self;
+        write!(f, "{first}\n{second}")
+    }
+}
+
+impl PacketPair {
+    fn is_sorted(&self) -> bool {
+        let Self(first, second) = self;
+        first < second
+    }
+}
+
+mod parser {
+    use super::*;
+    use anyhow::Result;
+    use common::Text;
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{newline, u8},
+        combinator::{cut, map, opt},
+        sequence::{delimited, separated_pair},
+        IResult,
+    };
+    use parsing::{blocks, commas, finish};
+
+    fn integer(s: &str) -> IResult<&str, Packet> {
+        map(u8, Packet::Integer)(s)
+    }
+
+    fn list(s: &str) -> IResult<&str, Packet> {
+        let list_contents = map(opt(commas(packet)), Option::unwrap_or_default);
+        map(delimited(tag("["), list_contents, tag("]")), Packet::List)(s)
+    }
+
+    fn packet(s: &str) -> IResult<&str, Packet> {
+        alt((integer, list))(s)
+    }
+
+    fn packet_pair(s: &str) -> IResult<&str, PacketPair> {
+        let (s, (first, second)) = separated_pair(packet, newline, packet)(s)?;
+        Ok((s, PacketPair(first, second)))
+    }
+
+    /// Parses the pairs of packets, which are separated by blank lines.
+    pub(crate) fn parse(s: &str) -> Result<Vec<PacketPair>> {
+        let text = Text::new(s);
+        let expected = "a pair of packets like [1,[2]]";
+        Ok(finish(&text, expected, blocks(cut(packet_pair)))?)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use common::ParseError;
+        use proptest::prelude::*;
+
+        /// Packets nested up to four lists deep, including empty lists.
+        fn any_packet() -> impl Strategy<Value = Packet> {
+            let integer = any::<u8>().prop_map(Integer);
+            integer.prop_recursive(4, 64, 6, |inner| {
+                prop::collection::vec(inner, 0..6).prop_map(List)
+            })
+        }
+
+        /// Pairs of packets that are both lists, as they are in the puzzle.
+        fn any_pair() -> impl Strategy<Value = PacketPair> {
+            let list = || prop::collection::vec(any_packet(), 0..6).prop_map(List);
+            (list(), list()).prop_map(|(first, second)| PacketPair(first, second))
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(2000))]
+
+            #[test]
+            fn packets_round_trip(expected in any_packet()) {
+                let written = expected.to_string();
+                let (rest, parsed) = packet(&written).unwrap();
+                prop_assert_eq!(rest, "");
+                prop_assert_eq!(parsed, expected);
+            }
+
+            #[test]
+            fn pair_lists_round_trip(expected in prop::collection::vec(any_pair(), 1..8)) {
+                let pairs: Vec<String> = expected.iter().map(PacketPair::to_string).collect();
+                let written = format!("{}\n", pairs.join("\n\n"));
+                prop_assert_eq!(parse(&written).unwrap(), expected);
+            }
+        }
+
+        #[test]
+        fn writes_the_example_back_out() {
+            let example = include_str!("../example.txt");
+            let lines: Vec<String> = parse(example)
+                .unwrap()
+                .iter()
+                .map(PacketPair::to_string)
+                .collect();
+            assert_eq!(format!("{}\n", lines.join("\n\n")), example);
+        }
+
+        #[test]
+        fn parses_nested_packets() {
+            let (rest, parsed) = packet("[[1],[2,3,4]]").unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(
+                parsed,
+                List(vec![
+                    List(vec![Integer(1)]),
+                    List(vec![Integer(2), Integer(3), Integer(4)]),
+                ])
+            );
+        }
+
+        #[test]
+        fn parses_empty_lists() {
+            assert_eq!(packet("[]").unwrap().1, List(vec![]));
+            assert_eq!(
+                packet("[[[]]]").unwrap().1,
+                List(vec![List(vec![List(vec![])])])
+            );
+        }
+
+        #[test]
+        fn parses_pairs_separated_by_blank_lines() {
+            let pairs = parse(include_str!("../example.txt")).unwrap();
+            assert_eq!(pairs.len(), 8);
+            let PacketPair(first, second) = &pairs[2];
+            assert_eq!(*first, List(vec![Integer(9)]));
+            assert_eq!(
+                *second,
+                List(vec![List(vec![Integer(8), Integer(7), Integer(6)])])
+            );
+        }
+
+        #[test]
+        fn copes_with_crlf_and_extra_blank_lines() {
+            let pairs = parse("[1]\r\n[2]\r\n\r\n\r\n[3]\r\n[4]\r\n\r\n").unwrap();
+            assert_eq!(
+                pairs[1],
+                PacketPair(List(vec![Integer(3)]), List(vec![Integer(4)]))
+            );
+            assert!(parse("\n").is_err());
+        }
+
+        #[test]
+        fn locates_an_unclosed_list() {
+            let error = parse("[1,[2]]\n[3]\n\n[1,[2]\n[3]\n").unwrap_err();
+            let error = error.downcast_ref::<ParseError>().unwrap();
+            assert_eq!((error.line, error.column), (4, 7));
+            assert_eq!(error.found, "");
+        }
+    }
+}
+
+impl IntoIterator for PacketPair {
+    type Item = Packet;
+    type IntoIter = std::array::IntoIter<Self::Item, 2>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let PacketPair(first, second) = self;
+        [first, second].into_iter()
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<PacketPair>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        parser::parse(input)
+    }
+
+    fn part1(&self, pairs: &Self::Input) -> anyhow::Result<u32> {
+        Ok(sorted_pair_indices(pairs))
+    }
+
+    fn part2(&self, pairs: &Self::Input) -> anyhow::Result<u32> {
+        Ok(decoder_key(pairs))
+    }
+}
+
+fn sorted_pair_indices(pairs: &[PacketPair]) -> u32 {
+    let mut total = 0;
+
+    // for each pair of packets
+    for (idx, packet_pair) in pairs.iter().enumerate() {
+        if !packet_pair.is_sorted() {
+            continue;
+        }
+        total += (idx as u32) + 1;
+    }
+
+    total
+}
+
+fn decoder_key(pairs: &[PacketPair]) -> u32 {
+    let divider1 = List(vec![List(vec![Integer(2)])]);
+    let divider2 = List(vec![List(vec![Integer(6)])]);
+    let dividers = [divider1, divider2];
+
+    let mut all_packets = pairs
+        .iter()
+        .cloned()
+        .flatten()
+        .chain(dividers.iter().cloned())
+        .collect::<Vec<_>>();
+
+    all_packets.sort_unstable();
+
+    let mut total = 1;
+    for (idx, packet) in all_packets.iter().enumerate() {
+        if dividers.contains(packet) {
+            total *= (idx as u32) + 1;
+        }
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_mixed_packets() {
+        // [[1],[2,3,4]] vs [[1],4]: the 4 becomes [4], which beats [2,3,4]
+        let left = List(vec![
+            List(vec![Integer(1)]),
+            List(vec![Integer(2), Integer(3), Integer(4)]),
+        ]);
+        let right = List(vec![List(vec![Integer(1)]), Integer(4)]);
+        assert!(PacketPair(left.clone(), right.clone()).is_sorted());
+        assert!(!PacketPair(right, left).is_sorted());
+    }
+
+    #[test]
+    fn shorter_list_sorts_first() {
+        let shorter = List(vec![Integer(7), Integer(7), Integer(7)]);
+        let longer = List(vec![Integer(7), Integer(7), Integer(7), Integer(7)]);
+        assert!(PacketPair(shorter, longer).is_sorted());
+    }
+}
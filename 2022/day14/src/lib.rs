@@ -0,0 +1,746 @@
+use animate::{Animate, Frame, Hook};
+use common::{Answer, Part, Solution};
+use export::{colours, Export, Image};
+use geometry::{Dir8, Vec2};
+use grid::Grid;
+use itertools::Itertools;
+use std::fmt::{self, Display};
+
+pub type Point = Vec2<i32>;
+
+/// A line of the scan: the corners of a path of rock, each joined to the next
+/// by a straight line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RockPath(Vec<Point>);
+
+/// Written as in the scan, like `498,4 -> 498,6 -> 496,6`.
+impl Display for RockPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let corners: Vec<String> = self.0.iter().map(Point::to_string).collect();
+        write!(f, "{}", corners.join(" -> "))
+    }
+}
+
+/// The ways a grain of sand can fall, in the order it tries them.
+const FALLS: [Dir8; 3] = [Dir8::Down, Dir8::DownLeft, Dir8::DownRight];
+
+mod parser {
+    use super::*;
+    use anyhow::Result;
+    use nom::{
+        bytes::complete::tag,
+        character::complete::i64,
+        error::{Error, ErrorKind},
+        sequence::{preceded, separated_pair},
+        IResult,
+    };
+    use parsing::{finish, lines1};
+
+    /// The furthest a point of rock can be from the origin in either
+    /// direction, which is well beyond any puzzle's cave but keeps the grid
+    /// of it small enough to hold.
+    pub(crate) const MAX_COORDINATE: i32 = 1000;
+
+    /// A point in the cave, which can't be above the entrypoint or left of
+    /// the cave wall, or further away than `MAX_COORDINATE`.
+    fn point(s: &str) -> IResult<&str, Point> {
+        let (rest, (x, y)) = separated_pair(i64, tag(","), i64)(s)?;
+        let range = 0..=i64::from(MAX_COORDINATE);
+        if !range.contains(&x) || !range.contains(&y) {
+            return Err(nom::Err::Failure(Error::new(s, ErrorKind::Verify)));
+        }
+        Ok((rest, Point::new(x as i32, y as i32)))
+    }
+
+    /// Corners joined by lines that are either horizontal or vertical. A
+    /// corner that isn't in line with the one before is a failure there.
+    fn rock_path(s: &str) -> IResult<&str, RockPath> {
+        let (mut s, first) = point(s)?;
+        let mut corners = vec![first];
+        loop {
+            let (rest, corner) = match preceded(tag(" -> "), point)(s) {
+                Ok(parsed) => parsed,
+                Err(nom::Err::Error(_)) => break,
+                Err(e) => return Err(e),
+            };
+            let last = corners[corners.len() - 1];
+            if corner.x != last.x && corner.y != last.y {
+                let at = &s[" -> ".len()..];
+                return Err(nom::Err::Failure(Error::new(at, ErrorKind::Verify)));
+            }
+            corners.push(corner);
+            s = rest;
+        }
+        Ok((s, RockPath(corners)))
+    }
+
+    pub(crate) fn parse(s: &str) -> Result<Vec<RockPath>> {
+        let expected = format!(
+            "a rock path of straight lines like 498,4 -> 498,6, \
+             with no point further than {MAX_COORDINATE} from the origin"
+        );
+        Ok(finish(s, expected, lines1(rock_path))?)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use common::ParseError;
+        use proptest::prelude::*;
+
+        /// A path of up to seven straight lines, each either across or down,
+        /// between points anywhere the parser allows.
+        fn any_rock_path() -> impl Strategy<Value = RockPath> {
+            let coordinate = || 0..=MAX_COORDINATE;
+            let start = (coordinate(), coordinate()).prop_map(|(x, y)| Point::new(x, y));
+            let lines = prop::collection::vec((any::<bool>(), coordinate()), 0..8);
+            (start, lines).prop_map(|(start, lines)| {
+                let mut corners = vec![start];
+                for (across, to) in lines {
+                    let Point { x, y } = corners[corners.len() - 1];
+                    corners.push(if across {
+                        Point::new(to, y)
+                    } else {
+                        Point::new(x, to)
+                    });
+                }
+                RockPath(corners)
+            })
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(2000))]
+
+            #[test]
+            fn rock_paths_round_trip(expected in prop::collection::vec(any_rock_path(), 1..10)) {
+                let paths: Vec<String> = expected.iter().map(RockPath::to_string).collect();
+                let written = format!("{}\n", paths.join("\n"));
+                prop_assert_eq!(parse(&written).unwrap(), expected);
+            }
+        }
+
+        #[test]
+        fn writes_the_example_back_out() {
+            let example = include_str!("../example.txt");
+            let lines: Vec<String> = parse(example)
+                .unwrap()
+                .iter()
+                .map(RockPath::to_string)
+                .collect();
+            assert_eq!(format!("{}\n", lines.join("\n")), example);
+        }
+
+        #[test]
+        fn parses_a_rock_path() {
+            let (rest, path) = rock_path("498,4 -> 498,6 -> 496,6").unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(
+                path,
+                RockPath(vec![
+                    Point::new(498, 4),
+                    Point::new(498, 6),
+                    Point::new(496, 6)
+                ])
+            );
+            assert_eq!(path.to_string(), "498,4 -> 498,6 -> 496,6");
+        }
+
+        #[test]
+        fn parses_one_path_per_line() {
+            let paths = parse(include_str!("../example.txt")).unwrap();
+            assert_eq!(paths.len(), 2);
+            assert_eq!(paths[1].0.len(), 4);
+            assert_eq!(paths[1].0[3], Point::new(494, 9));
+        }
+
+        #[test]
+        fn locates_a_broken_path() {
+            let error = parse("498,4 -> 498,6\n503,4 -> 502\n").unwrap_err();
+            let error = error.downcast_ref::<ParseError>().unwrap();
+            assert_eq!((error.line, error.column), (2, 6));
+            assert_eq!(error.found, " -> 502");
+            assert!(parse("498,-4 -> 498,6\n").is_err());
+        }
+
+        #[test]
+        fn rejects_diagonal_lines() {
+            let error = parse("498,4 -> 498,6\n0,0 -> 1,5\n").unwrap_err();
+            let error = error.downcast_ref::<ParseError>().unwrap();
+            assert_eq!((error.line, error.column), (2, 8));
+            assert_eq!(error.found, "1,5");
+        }
+
+        #[test]
+        fn rejects_points_too_far_away() {
+            let error = parse("498,4 -> 2000000000,4\n").unwrap_err();
+            let error = error.downcast_ref::<ParseError>().unwrap();
+            assert_eq!((error.line, error.column), (1, 10));
+            assert_eq!(error.found, "2000000000,4");
+            assert!(parse("1000,1000 -> 1000,0\n").is_ok());
+        }
+    }
+}
+
+struct RockLineIter {
+    end: Point,          // The point where the rock line ends
+    offset: Point,       // The incremental change from `start` to `end`
+    next: Option<Point>, // The next item to return from this iterator
+}
+
+trait RockLine {
+    fn rock_line(self) -> RockLineIter;
+}
+
+impl RockLine for (Point, Point) {
+    fn rock_line(self) -> RockLineIter {
+        let (start, end) = self;
+        let offset = (end - start).signum();
+        RockLineIter {
+            end,
+            offset,
+            next: Some(start), // The first point returned is the start
+        }
+    }
+}
+
+impl Iterator for RockLineIter {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next {
+            None => None, // This is how we know when `RockLineIter` is empty
+            Some(current) => {
+                self.next = if current == self.end {
+                    None
+                } else {
+                    Some(current + self.offset)
+                };
+
+                Some(current)
+            }
+        }
+    }
+}
+
+/// Every point of rock along the paths of the scan.
+fn rocks(paths: Vec<RockPath>) -> Vec<Point> {
+    let mut rocks = Vec::new();
+    for RockPath(corners) in paths {
+        for point_pair in corners.into_iter().tuple_windows::<(_, _)>() {
+            rocks.extend(point_pair.rock_line());
+        }
+    }
+    rocks
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let glyph = match self {
+            Tile::Air => '.',
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+        };
+        write!(f, "{glyph}")
+    }
+}
+
+/// The slice of the cave that sand can reach: down to the floor two rows
+/// below the lowest rock, and wide enough for the pile that builds up on it.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    tiles: Grid<Tile>,
+    /// The x coordinate of the grid's first column
+    left: i32,
+    /// The y coordinate of the lowest rock
+    depth: i32,
+    /// Where the sand pours in from
+    entrypoint: Point,
+}
+
+impl Cave {
+    fn new(rocks: &[Point], entrypoint: Point) -> anyhow::Result<Self> {
+        let depth = rocks.iter().map(|point| point.y).max().unwrap_or_default();
+
+        // Sand piles up on the floor in a triangle no wider than the floor is
+        // deep, either side of the entrypoint
+        let floor = depth + 2;
+        let xs = rocks.iter().map(|point| point.x);
+        let left = xs
+            .clone()
+            .min()
+            .unwrap_or(entrypoint.x)
+            .min(entrypoint.x - floor)
+            .max(0);
+        let right = xs.max().unwrap_or(entrypoint.x).max(entrypoint.x + floor);
+
+        let width = usize::try_from(i64::from(right) - i64::from(left) + 1)?;
+        let tiles = Grid::new(width, usize::try_from(floor)?, Tile::Air)?;
+        let mut cave = Cave {
+            tiles,
+            left,
+            depth,
+            entrypoint,
+        };
+        for &rock in rocks {
+            cave.set(rock, Tile::Rock);
+        }
+        Ok(cave)
+    }
+
+    /// Where `point` is in the grid, if it's inside it at all.
+    fn pos(&self, point: Point) -> Option<grid::Pos> {
+        let row = usize::try_from(point.y).ok()?;
+        let col = usize::try_from(point.x - self.left).ok()?;
+        Some((row, col))
+    }
+
+    fn tile(&self, point: Point) -> Tile {
+        self.pos(point)
+            .and_then(|pos| self.tiles.get(pos))
+            .copied()
+            .unwrap_or(Tile::Air)
+    }
+
+    fn set(&mut self, point: Point, tile: Tile) {
+        let pos = self.pos(point).expect("point should be inside the cave");
+        self.tiles[pos] = tile;
+    }
+
+    fn is_blocked(&self, point: Point) -> bool {
+        self.tile(point) != Tile::Air
+    }
+}
+
+impl Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tiles)
+    }
+}
+
+#[derive(Debug)]
+enum GrainStatus {
+    MovedTo(Point),
+    StoppedAt(Point),
+    LostToTheAbyss,
+}
+
+#[derive(Debug, Clone)]
+struct CaveMap {
+    obstacles: Cave,
+    entrypoint: Point,
+    depth: i32,
+    /// How many grains of sand have come to rest
+    settled: usize,
+}
+
+impl CaveMap {
+    fn new(obstacles: Cave) -> Self {
+        let depth = obstacles.depth;
+        let entrypoint = obstacles.entrypoint;
+
+        CaveMap {
+            obstacles,
+            entrypoint,
+            depth,
+            settled: 0,
+        }
+    }
+
+    /// Drop a grain of sand in, sending `hook` a frame at each step it falls.
+    fn add_sand(&mut self, hook: &mut Hook) -> GrainStatus {
+        let mut sand = self.entrypoint;
+
+        loop {
+            let sand_flow = self.try_move_sand(sand);
+
+            match sand_flow {
+                GrainStatus::MovedTo(point) => {
+                    sand = point;
+                    hook.frame(|| self.frame(sand));
+                }
+
+                GrainStatus::StoppedAt(point) => {
+                    self.obstacles.set(point, Tile::Sand);
+                    self.settled += 1;
+                    break sand_flow;
+                }
+
+                GrainStatus::LostToTheAbyss => break sand_flow,
+            }
+        }
+    }
+
+    fn try_move_sand(&self, sand: Point) -> GrainStatus {
+        for fall in FALLS {
+            let try_pos = sand + fall.step();
+
+            if self.obstacles.is_blocked(try_pos) {
+                continue;
+            }
+
+            if sand.y >= self.depth {
+                return GrainStatus::LostToTheAbyss;
+            }
+
+            return GrainStatus::MovedTo(try_pos);
+        }
+
+        GrainStatus::StoppedAt(sand)
+    }
+
+    /// The cave with the grain that's falling, which the frame follows.
+    fn frame(&self, sand: Point) -> Frame {
+        let frame = Frame::new(&self.obstacles).caption(format!("{} grains at rest", self.settled));
+        match self.obstacles.pos(sand) {
+            Some((row, col)) => frame.plot((col, row), '+').focus((col, row)),
+            None => frame,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FillMap {
+    obstacles: Cave,
+    entrypoint: Point,
+    depth: i32,
+}
+
+impl FillMap {
+    /// Unpack a `CaveMap` into a `FillMap`
+    fn from(grid_map: CaveMap) -> Self {
+        // Get the attributes from the `CaveMap`
+        let CaveMap {
+            obstacles,
+            entrypoint,
+            depth,
+            ..
+        } = grid_map;
+
+        // Adjust the depth to represent the floor. Hey, look, there's that grain of
+        // sand we thought was gone forever, breathing a huge sigh of relief. Good
+        // for him!
+        let depth = depth + 2;
+
+        // Now it's a `FillMap`!
+        FillMap {
+            obstacles,
+            entrypoint,
+            depth,
+        }
+    }
+
+    /// From a given Point, return an array indicating which points a grain of sand
+    /// can flow into (e.g., that aren't blocked by an obstacle or the floor).
+    fn get_neighbors(&self, point: Point) -> [Option<Point>; 3] {
+        // Array to hold the neighbors that can be moved to
+        let mut neighbors = [None; 3];
+
+        // For each of the same three potential moves as the first part...
+        for (idx, fall) in FALLS.iter().enumerate() {
+            // The position we might move to.
+            let try_pos = point + fall.step();
+
+            // If there's an obstacle there, skip it. Can't move there.
+            if self.obstacles.is_blocked(try_pos) {
+                continue;
+            }
+
+            // If there's floor there, skip it. Can't move there.
+            if try_pos.y >= self.depth {
+                continue;
+            }
+
+            // Otherwise, we can move there. Add this point to our neighbors array.
+            neighbors[idx] = Some(try_pos);
+        }
+
+        // Return the list of neighbors
+        neighbors
+    }
+
+    /// Calculate the number of sand grains it'll take to fill in the pile and
+    /// block off the entrypoint. Using Dijkstra's Algorithm! Nah, just kidding,
+    /// it's a flood fill.
+    fn sand_capacity(&self) -> u32 {
+        // Every point the sand can flow into from the entrypoint ends up
+        // holding a grain of sand, so flood fill them and count them up.
+        let flows = |&point: &Point| self.get_neighbors(point).into_iter().flatten();
+        search::flood_fill(&flows, self.entrypoint).len() as u32
+    }
+}
+
+common::configure! {
+    /// Where the sand comes from, which is the same for the example and the
+    /// real puzzle.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Day14 {
+        /// Where the sand pours into the cave from, as `x,y`.
+        pub entrypoint: Point,
+    }
+    presets: ["puzzle" => Day14::PUZZLE]
+}
+
+impl Day14 {
+    pub const PUZZLE: Day14 = Day14 {
+        entrypoint: Point::new(500, 0),
+    };
+}
+
+impl Solution for Day14 {
+    /// The cave, with every point that's blocked by rock filled in.
+    type Input = Cave;
+    type Answer1 = i32;
+    type Answer2 = u32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        let rocks = rocks(parser::parse(input)?);
+        // The sand has to start inside the cave, above the floor
+        let floor = rocks.iter().map(|point| point.y).max().unwrap_or_default() + 2;
+        let Point { x, y } = self.entrypoint;
+        anyhow::ensure!(
+            (0..=parser::MAX_COORDINATE).contains(&x) && (0..floor).contains(&y),
+            "The sand can't pour in from {}, outside the cave",
+            self.entrypoint
+        );
+        Cave::new(&rocks, self.entrypoint)
+    }
+
+    fn part1(&self, obstacles: &Self::Input) -> anyhow::Result<i32> {
+        grains_until_abyss(&mut CaveMap::new(obstacles.clone()), &mut Hook::none())
+    }
+
+    fn part2(&self, obstacles: &Self::Input) -> anyhow::Result<u32> {
+        let cave_map = CaveMap::new(obstacles.clone());
+        let fill_map = FillMap::from(cave_map);
+
+        Ok(fill_map.sand_capacity())
+    }
+}
+
+impl Animate for Day14 {
+    fn animate(
+        &self,
+        obstacles: &Self::Input,
+        part: Part,
+        hook: &mut Hook,
+    ) -> anyhow::Result<Answer> {
+        match part {
+            Part::One => {
+                let mut cave_map = CaveMap::new(obstacles.clone());
+                Ok(grains_until_abyss(&mut cave_map, hook)?.into())
+            }
+            Part::Two => {
+                anyhow::bail!("Part 2 floods the cave in one go, so there's nothing to watch")
+            }
+        }
+    }
+}
+
+impl Export for Day14 {
+    /// The cave once part 1's sand has filled it, and the next grain has
+    /// fallen into the abyss.
+    fn picture(&self, obstacles: &Self::Input) -> anyhow::Result<Image> {
+        let mut cave_map = CaveMap::new(obstacles.clone());
+        grains_until_abyss(&mut cave_map, &mut Hook::none())?;
+        let tiles = &cave_map.obstacles.tiles;
+        Ok(Image::grid(
+            tiles.width(),
+            tiles.height(),
+            colours::BACKGROUND,
+            |col, row| match tiles[(row, col)] {
+                Tile::Air => None,
+                Tile::Rock => Some(colours::SILVER),
+                Tile::Sand => Some(colours::GOLD),
+            },
+        ))
+    }
+}
+
+fn grains_until_abyss(cave_map: &mut CaveMap, hook: &mut Hook) -> anyhow::Result<i32> {
+    for grains in 1.. {
+        // When we find the first grain of sand that falls into the infinite
+        // abyss, we stop and return the current grain count minus one as
+        // the number of grains _before_ this poor soul was lost to the void.
+        match cave_map.add_sand(hook) {
+            GrainStatus::LostToTheAbyss => return Ok(grains - 1),
+            GrainStatus::StoppedAt(point) if point == cave_map.entrypoint => break,
+            _ => {}
+        }
+    }
+
+    anyhow::bail!("The sand piles up to the entrypoint without falling into the abyss")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    fn example_cave() -> CaveMap {
+        let obstacles = Day14::PUZZLE.parse(include_str!("../example.txt")).unwrap();
+        CaveMap::new(obstacles)
+    }
+
+    #[test]
+    fn rock_line_includes_both_ends() {
+        let line = (Point::new(498, 6), Point::new(496, 6))
+            .rock_line()
+            .collect_vec();
+        assert_eq!(
+            line,
+            vec![Point::new(498, 6), Point::new(497, 6), Point::new(496, 6)]
+        );
+    }
+
+    #[test]
+    fn sand_settles_on_the_rock() {
+        let mut cave = example_cave();
+        assert!(matches!(
+            cave.add_sand(&mut Hook::none()),
+            GrainStatus::StoppedAt(p) if p == Point::new(500, 8)
+        ));
+        assert!(matches!(
+            cave.add_sand(&mut Hook::none()),
+            GrainStatus::StoppedAt(p) if p == Point::new(499, 8)
+        ));
+        assert!(matches!(
+            cave.add_sand(&mut Hook::none()),
+            GrainStatus::StoppedAt(p) if p == Point::new(501, 8)
+        ));
+    }
+
+    #[test]
+    fn renders_rock_and_sand() {
+        let mut cave = example_cave();
+        cave.add_sand(&mut Hook::none());
+        let rendered = cave.obstacles.to_string();
+        let rows = rendered.lines().collect_vec();
+
+        // Rows down to the floor, from 11 columns left of the entrypoint
+        assert_eq!(rows.len(), 11);
+        assert_eq!(&rows[8][11..14], "o.#");
+        assert_eq!(&rows[9][5..16], "#########..");
+    }
+
+    #[test]
+    fn hook_follows_each_grain_down() {
+        let mut cave = example_cave();
+        let mut frames = Vec::new();
+        let mut watcher = |frame: Frame| {
+            frames.push(frame);
+            std::ops::ControlFlow::Continue(())
+        };
+        cave.add_sand(&mut Hook::new(&mut watcher));
+
+        // The first grain falls straight down, from 500,1 to 500,8
+        assert_eq!(frames.len(), 8);
+        let last = frames.last().unwrap();
+        assert_eq!(last.focus, Some((11, 8)));
+        assert_eq!(last.rows[8][11], '+');
+        assert_eq!(last.caption, "0 grains at rest");
+    }
+
+    #[test]
+    fn sand_falls_into_the_abyss_once_the_pile_is_full() {
+        let mut cave = example_cave();
+        for _ in 0..24 {
+            assert!(matches!(
+                cave.add_sand(&mut Hook::none()),
+                GrainStatus::StoppedAt(_)
+            ));
+        }
+        assert!(matches!(
+            cave.add_sand(&mut Hook::none()),
+            GrainStatus::LostToTheAbyss
+        ));
+    }
+
+    #[test]
+    fn picture_shows_the_settled_sand() {
+        let obstacles = Day14::PUZZLE.parse(include_str!("../example.txt")).unwrap();
+        let image = Day14::PUZZLE.picture(&obstacles).unwrap();
+        assert_eq!((image.width, image.height), (23.0, 11.0));
+        let sand: f64 = image
+            .shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                export::Shape::Rect { width, fill, .. } if *fill == colours::GOLD => Some(*width),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(sand, 24.0);
+    }
+}
+
+/// A grain-by-grain simulation to check the flood fill of `sand_capacity`
+/// against.
+#[cfg(test)]
+mod reference {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    /// How many grains come to rest before one blocks the entrypoint, with the
+    /// floor two rows below the lowest rock.
+    fn sand_capacity(rocks: &HashSet<Point>, entrypoint: Point) -> u32 {
+        let floor = rocks.iter().map(|rock| rock.y).max().unwrap_or_default() + 2;
+        let mut blocked = rocks.clone();
+        let mut grains = 0;
+        while !blocked.contains(&entrypoint) {
+            let mut sand = entrypoint;
+            while let Some(next) = FALLS
+                .iter()
+                .map(|fall| sand + fall.step())
+                .find(|next| next.y < floor && !blocked.contains(next))
+            {
+                sand = next;
+            }
+            blocked.insert(sand);
+            grains += 1;
+        }
+        grains
+    }
+
+    /// A scan of a few short paths of rock a little way below and either side
+    /// of the entrypoint, turning left, right, up or down at each corner.
+    fn any_scan() -> impl Strategy<Value = String> {
+        let step = prop_oneof![
+            (1..5i32).prop_map(|n| Point::new(n, 0)),
+            (1..5i32).prop_map(|n| Point::new(-n, 0)),
+            (1..5i32).prop_map(|n| Point::new(0, n)),
+            (1..5i32).prop_map(|n| Point::new(0, -n)),
+        ];
+        let start = (490..510i32, 1..12i32).prop_map(|(x, y)| Point::new(x, y));
+        let path = (start, prop::collection::vec(step, 0..4)).prop_map(|(start, steps)| {
+            let corners = steps.into_iter().scan(start, |at, step| {
+                // Keep the rock below the entrypoint's row
+                *at = Point::new(at.x + step.x, (at.y + step.y).max(1));
+                Some(*at)
+            });
+            RockPath(std::iter::once(start).chain(corners).collect())
+        });
+        prop::collection::vec(path, 1..6).prop_map(|paths| {
+            let lines: Vec<String> = paths.iter().map(RockPath::to_string).collect();
+            format!("{}\n", lines.join("\n"))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn flood_fill_holds_as_much_sand_as_falls(scan in any_scan()) {
+            let cave = Day14::PUZZLE.parse(&scan).unwrap();
+            let rocks = rocks(parser::parse(&scan).unwrap()).into_iter().collect();
+            let expected = sand_capacity(&rocks, Day14::PUZZLE.entrypoint);
+            prop_assert_eq!(Day14::PUZZLE.part2(&cave).unwrap(), expected);
+        }
+    }
+}
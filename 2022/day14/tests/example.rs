@@ -0,0 +1,16 @@
+use common::Solution;
+use day14::Day14;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day14::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day14::PUZZLE.part1(&input).unwrap(), 24);
+}
+
+#[test]
+fn part2() {
+    let input = Day14::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day14::PUZZLE.part2(&input).unwrap(), 93);
+}
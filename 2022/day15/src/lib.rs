@@ -0,0 +1,572 @@
+use common::Solution;
+use export::{colours, Export, Image, Shape};
+use geometry::Vec2;
+use itertools::Itertools;
+use std::fmt::{self, Display};
+
+pub type Point = Vec2<i64>;
+
+fn tuning_frequency(point: Point, multiplier: u64) -> u64 {
+    (multiplier * point.x as u64) + point.y as u64
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Sensor {
+    location: Point,
+    beacon: Point,
+    range: i64,
+}
+
+impl Sensor {
+    fn new(location: Point, beacon: Point) -> Self {
+        let range = location.manhattan(beacon);
+        Sensor {
+            location,
+            beacon,
+            range,
+        }
+    }
+}
+
+impl From<(Point, Point)> for Sensor {
+    fn from(value: (Point, Point)) -> Self {
+        let (location, beacon) = value;
+        Sensor::new(location, beacon)
+    }
+}
+
+/// Written as in the report, like `Sensor at x=2, y=18: closest beacon is at
+/// x=-2, y=15`.
+impl Display for Sensor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (sensor, beacon) = (self.location, self.beacon);
+        write!(
+            f,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            sensor.x, sensor.y, beacon.x, beacon.y
+        )
+    }
+}
+
+mod parser {
+    use super::*;
+    use anyhow::Result;
+    use nom::{combinator::map, sequence::pair, IResult};
+    use parsing::{finish, lines0, next_number};
+
+    fn point(s: &str) -> IResult<&str, Point> {
+        map(pair(next_number, next_number), Point::from)(s)
+    }
+
+    fn sensor(s: &str) -> IResult<&str, Sensor> {
+        map(pair(point, point), Sensor::from)(s)
+    }
+
+    pub(crate) fn parse(s: &str) -> Result<Vec<Sensor>> {
+        let expected = "a sensor like \"Sensor at x=2, y=18: closest beacon is at x=-2, y=15\"";
+        Ok(finish(s, expected, lines0(sensor))?)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use common::ParseError;
+        use proptest::prelude::*;
+
+        /// A sensor and its beacon anywhere within a few times the puzzle's
+        /// search area, either side of the origin, so their distance can't
+        /// overflow.
+        fn any_sensor() -> impl Strategy<Value = Sensor> {
+            let coordinate = || -20_000_000..=20_000_000i64;
+            let point = move || (coordinate(), coordinate()).prop_map(Point::from);
+            (point(), point()).prop_map(Sensor::from)
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(2000))]
+
+            #[test]
+            fn sensors_round_trip(expected in prop::collection::vec(any_sensor(), 0..10)) {
+                let sensors: Vec<String> = expected.iter().map(Sensor::to_string).collect();
+                let written = format!("{}\n", sensors.join("\n"));
+                prop_assert_eq!(parse(&written).unwrap(), expected);
+            }
+        }
+
+        #[test]
+        fn writes_the_example_back_out() {
+            let example = include_str!("../example.txt");
+            let sensors: Vec<String> = parse(example)
+                .unwrap()
+                .iter()
+                .map(Sensor::to_string)
+                .collect();
+            assert_eq!(format!("{}\n", sensors.join("\n")), example);
+        }
+
+        #[test]
+        fn parses_a_sensor() {
+            let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+            let (rest, parsed) = sensor(input).unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(parsed, Sensor::new(Point::new(2, 18), Point::new(-2, 15)));
+            assert_eq!(parsed.range, 7);
+        }
+
+        #[test]
+        fn parses_one_sensor_per_line() {
+            let sensors = parse(include_str!("../example.txt")).unwrap();
+            assert_eq!(sensors.len(), 14);
+            assert_eq!(
+                sensors[13],
+                Sensor::new(Point::new(20, 1), Point::new(15, 3))
+            );
+        }
+
+        #[test]
+        fn locates_a_sensor_without_a_beacon() {
+            let input =
+                "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16\n";
+            let error = parse(input).unwrap_err();
+            let error = error.downcast_ref::<ParseError>().unwrap();
+            assert_eq!((error.line, error.column), (2, 1));
+            assert_eq!(error.found, "Sensor at x=9, y=16");
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct RowRange(i64, i64);
+
+impl RowRange {
+    fn overlaps(&self, other: &Self) -> bool {
+        other.1 >= self.0 && self.1 >= other.0
+    }
+
+    fn merge(&mut self, other: &Self) {
+        *self = RowRange(self.0.min(other.0), self.1.max(other.1));
+    }
+
+    fn count_positions(&self) -> u64 {
+        self.0.abs_diff(self.1) + 1
+    }
+}
+
+impl Sensor {
+    fn can_detect(&self, point: &Point) -> bool {
+        self.location.manhattan(*point) <= self.range
+    }
+
+    fn row_range_sensed(&self, row: i64) -> Option<RowRange> {
+        let distance_to_row = (self.location.y - row).abs();
+        if distance_to_row > self.range {
+            return None;
+        }
+
+        // The spread indicates how much of the Manhattan distance for detection
+        // is remaining to 'spread' out to the left and right. Essentially half
+        // the width of the detection zone on this row.
+        let spread = self.range - distance_to_row;
+        Some(RowRange(self.location.x - spread, self.location.x + spread))
+    }
+
+    /// The same sensor, mirrored in the line `x = y`, so that its columns
+    /// become rows.
+    fn transposed(&self) -> Sensor {
+        let flip = |point: Point| Point::new(point.y, point.x);
+        Sensor::new(flip(self.location), flip(self.beacon))
+    }
+
+    fn beacon_on_row(&self, row: i64) -> Option<Point> {
+        if self.beacon.y == row {
+            return Some(self.beacon);
+        }
+        None
+    }
+
+    fn gap_size(&self, other: &Self) -> Option<i64> {
+        let distance = self.location.manhattan(other.location);
+        let total_range = self.range + other.range;
+        if total_range >= distance {
+            return None;
+        }
+        Some(distance - total_range - 1)
+    }
+
+    /// Calculate the formula for the line that lies in the gap between two
+    /// Sensor detection ranges. The line will lie diagonally just outside
+    /// the range of `self`.
+    fn diagonal_between(&self, other: &Self) -> Diagonal {
+        let Vec2 { x: x1, y: y1 } = self.location;
+        let Vec2 { x: x2, y: y2 } = other.location;
+        let offset = self.range + 1;
+
+        // Here, we identify two points on the diagonal line. We'll pick points just
+        // outside the cardinal direction points of the `self` sensor range.
+        let (p1x, p1y) = if x2 > x1 {
+            (x1 + offset, y1)
+        } else {
+            (x1 - offset, y1)
+        };
+        let (p2x, p2y) = if y2 > y1 {
+            (x1, y1 + offset)
+        } else {
+            (x1, y1 - offset)
+        };
+
+        // We know that the slope will either be 1 or -1, since these lines
+        // are diagonals.
+        let slope = (p2x - p1x) / (p2y - p1y);
+        let intercept = p1y - (slope * p1x);
+        if slope > 0 {
+            Diagonal::Positive(intercept)
+        } else {
+            Diagonal::Negative(intercept)
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Diagonal {
+    Positive(i64),
+    Negative(i64),
+}
+
+impl Diagonal {
+    /// Identify the point where two Diagonal lines intersect.
+    fn intersect(&self, other: &Self) -> Option<Point> {
+        // It's simple geometry! Which explains why it was so hard for me
+        // to implement. Uses the formula for the two lines to calculate the
+        // intersecting point, with some shortcuts because we know the slope
+        // will either be positive or negative one for both lines, and if
+        // the lines have the same slope, they're parallel and we can bail.
+        use Diagonal::*;
+        let (neg, pos) = match (self, other) {
+            (Positive(pos), Negative(neg)) => (neg, pos),
+            (Negative(neg), Positive(pos)) => (neg, pos),
+            (Positive(_), Positive(_)) => return None,
+            (Negative(_), Negative(_)) => return None,
+        };
+        let x = (neg - pos) / 2;
+        let y = x + pos;
+        Some(Point::new(x, y))
+    }
+}
+
+common::configure! {
+    /// The puzzle's parameters, which differ between the example and the real
+    /// puzzle.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Day15 {
+        /// The row to count the positions that can't contain a beacon on.
+        pub row: i64,
+        /// The largest x and y coordinate the distress beacon could have.
+        pub bound: i64,
+        /// What the distress beacon's x coordinate is multiplied by in its
+        /// tuning frequency.
+        pub tuning: u64,
+    }
+    presets: ["puzzle" => Day15::PUZZLE, "example" => Day15::EXAMPLE]
+}
+
+impl Day15 {
+    pub const PUZZLE: Day15 = Day15 {
+        row: 2_000_000,
+        bound: 4_000_000,
+        tuning: 4_000_000,
+    };
+
+    pub const EXAMPLE: Day15 = Day15 {
+        row: 10,
+        bound: 20,
+        ..Day15::PUZZLE
+    };
+}
+
+impl Solution for Day15 {
+    /// The sensors, sorted by location.
+    type Input = Vec<Sensor>;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input: &str) -> anyhow::Result<Self::Input> {
+        let mut sensors = parser::parse(input)?;
+        sensors.sort_unstable();
+        Ok(sensors)
+    }
+
+    fn part1(&self, sensors: &Self::Input) -> anyhow::Result<u32> {
+        Ok(definitely_not_beacons(sensors, self.row))
+    }
+
+    fn part2(&self, sensors: &Self::Input) -> anyhow::Result<u64> {
+        distress_beacon(sensors, self.bound, self.tuning)
+            .ok_or_else(|| anyhow::anyhow!("Could not find the beacon!"))
+    }
+}
+
+fn definitely_not_beacons(sensors: &[Sensor], row: i64) -> u32 {
+    let mut ranges: Vec<RowRange> = Vec::new();
+    for range in sensors.iter().flat_map(|s| s.row_range_sensed(row)) {
+        // manhattan distance
+        if let Some(last_rng) = ranges.last_mut() {
+            if last_rng.overlaps(&range) {
+                last_rng.merge(&range);
+            }
+            continue;
+        }
+        ranges.push(range);
+    }
+
+    // manhattan distance
+    let sensed_on_row = ranges.iter().map(|r| r.count_positions()).sum::<u64>();
+
+    // manhattan distance
+    let beacons_on_row = sensors
+        .iter()
+        .filter_map(|s| s.beacon_on_row(row))
+        .unique()
+        .count();
+
+    let definitely_not_beacons = sensed_on_row - beacons_on_row as u64;
+
+    definitely_not_beacons as u32
+}
+
+fn distress_beacon(sensors: &[Sensor], bound: i64, tuning: u64) -> Option<u64> {
+    distress_beacon_at(sensors, bound).map(|point| tuning_frequency(point, tuning))
+}
+
+fn distress_beacon_at(sensors: &[Sensor], bound: i64) -> Option<Point> {
+    let mut diagonal_gaps = Vec::new();
+    for (sensor1, sensor2) in sensors.iter().tuple_combinations() {
+        let Some(gap) = sensor1.gap_size(sensor2) else {
+            continue;
+        };
+        if gap == 1 {
+            diagonal_gaps.push(sensor1.diagonal_between(sensor2));
+        }
+    }
+
+    // Identify all the points where these one-wide gaps intersect.
+    let intersects = diagonal_gaps
+        .iter()
+        .tuple_combinations()
+        .flat_map(|(diag1, diag2)| diag1.intersect(diag2))
+        .filter(|p| (0..=bound).contains(&p.x) && (0..=bound).contains(&p.y))
+        .unique()
+        .collect_vec();
+
+    'outer: for intersect in intersects {
+        for sensor in sensors.iter() {
+            if sensor.can_detect(&intersect) {
+                continue 'outer;
+            }
+        }
+        return Some(intersect);
+    }
+
+    // There's no intersection that can't be detected, so the beacon has to be
+    // on the edge of the square, where the gaps don't need to box it in.
+    distress_beacon_on_edge(sensors, bound)
+}
+
+/// The position on the edge of the square that no sensor can detect, found
+/// by sweeping along each edge past the sensors' ranges.
+fn distress_beacon_on_edge(sensors: &[Sensor], bound: i64) -> Option<Point> {
+    let transposed: Vec<Sensor> = sensors.iter().map(Sensor::transposed).collect();
+    let first_gap = |sensors: &[Sensor], row: i64| {
+        let mut ranges: Vec<RowRange> = sensors
+            .iter()
+            .flat_map(|sensor| sensor.row_range_sensed(row))
+            .collect();
+        ranges.sort_unstable_by_key(|range| range.0);
+        let mut next = 0;
+        for RowRange(start, end) in ranges {
+            if start > next {
+                break;
+            }
+            next = next.max(end + 1);
+        }
+        (next <= bound).then_some(next)
+    };
+
+    for edge in [0, bound] {
+        if let Some(x) = first_gap(sensors, edge) {
+            return Some(Point::new(x, edge));
+        }
+        if let Some(y) = first_gap(&transposed, edge) {
+            return Some(Point::new(edge, y));
+        }
+    }
+    None
+}
+
+impl Export for Day15 {
+    /// The square the distress beacon is in, covered by the sensors'
+    /// diamonds, with the sensors, their beacons and the distress beacon
+    /// marked on it.
+    fn picture(&self, sensors: &Self::Input) -> anyhow::Result<Image> {
+        let side = (self.bound + 1) as f64;
+        let mut image = Image::new(side, side, colours::BACKGROUND);
+        for sensor in sensors {
+            let Vec2 { x, y } = sensor.location;
+            // Out to the edges of the cells at the tips of the range
+            let (x, y, reach) = (x as f64 + 0.5, y as f64 + 0.5, sensor.range as f64 + 0.5);
+            image.push(Shape::Polygon {
+                points: vec![
+                    (x, y - reach),
+                    (x + reach, y),
+                    (x, y + reach),
+                    (x - reach, y),
+                ],
+                fill: colours::GREEN,
+                opacity: 0.3,
+            });
+        }
+
+        // Markers a hundredth of the picture across, but at least a cell
+        let size = (side / 100.0).max(1.0);
+        let mut mark = |point: Point, fill| {
+            image.push(Shape::Rect {
+                x: point.x as f64 + 0.5 - size / 2.0,
+                y: point.y as f64 + 0.5 - size / 2.0,
+                width: size,
+                height: size,
+                fill,
+            })
+        };
+        for sensor in sensors {
+            mark(sensor.location, colours::BRIGHT_GREEN);
+            mark(sensor.beacon, colours::SILVER);
+        }
+        if let Some(beacon) = distress_beacon_at(sensors, self.bound) {
+            mark(beacon, colours::GOLD);
+        }
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_range_sensed_narrows_away_from_the_sensor() {
+        let sensor = Sensor::new(Point::new(8, 7), Point::new(2, 10));
+        assert_eq!(sensor.row_range_sensed(7), Some(RowRange(-1, 17)));
+        assert_eq!(sensor.row_range_sensed(10), Some(RowRange(2, 14)));
+        assert_eq!(sensor.row_range_sensed(16), Some(RowRange(8, 8)));
+        assert_eq!(sensor.row_range_sensed(-2), Some(RowRange(8, 8)));
+        assert_eq!(sensor.row_range_sensed(17), None);
+    }
+
+    #[test]
+    fn diagonals_intersect_unless_parallel() {
+        let rising = Diagonal::Positive(1);
+        let falling = Diagonal::Negative(5);
+        assert_eq!(rising.intersect(&falling), Some(Point::new(2, 3)));
+        assert_eq!(falling.intersect(&rising), Some(Point::new(2, 3)));
+        assert_eq!(rising.intersect(&Diagonal::Positive(3)), None);
+    }
+
+    #[test]
+    fn finds_a_distress_beacon_on_the_edge() {
+        // The counterexample the reference found: nothing boxes the corner in
+        let sensors = [
+            ((0, 0), (3, 0)),
+            ((0, 4), (7, 4)),
+            ((2, 2), (5, 2)),
+            ((3, 1), (4, 1)),
+        ]
+        .map(|(sensor, beacon)| Sensor::new(Point::from(sensor), Point::from(beacon)));
+        assert_eq!(distress_beacon_at(&sensors, 4), Some(Point::new(4, 0)));
+    }
+
+    #[test]
+    fn picture_marks_the_distress_beacon() {
+        let sensors = Day15::EXAMPLE
+            .parse(include_str!("../example.txt"))
+            .unwrap();
+        let image = Day15::EXAMPLE.picture(&sensors).unwrap();
+        assert_eq!((image.width, image.height), (21.0, 21.0));
+        assert_eq!(
+            image.shapes.last(),
+            Some(&Shape::Rect {
+                x: 14.0,
+                y: 11.0,
+                width: 1.0,
+                height: 1.0,
+                fill: colours::GOLD,
+            })
+        );
+        // The diamonds cover the cells around the distress beacon
+        let raster = image.rasterize(1.0).unwrap();
+        assert_eq!(raster.get(14, 11), colours::GOLD);
+        assert_ne!(raster.get(13, 11), colours::BACKGROUND);
+    }
+}
+
+/// Brute-force solutions to check the diagonal shortcuts against, by
+/// looking at every position in turn.
+#[cfg(test)]
+mod reference {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// The distress beacon, if exactly one position in the square can't be
+    /// detected by any sensor.
+    fn distress_beacon_at(sensors: &[Sensor], bound: i64) -> Option<Point> {
+        let mut hidden = (0..=bound)
+            .flat_map(|y| (0..=bound).map(move |x| Point::new(x, y)))
+            .filter(|point| sensors.iter().all(|sensor| !sensor.can_detect(point)));
+        match (hidden.next(), hidden.next()) {
+            (Some(point), None) => Some(point),
+            _ => None,
+        }
+    }
+
+    /// A square of side `bound + 1` with a distress beacon hidden in it, and
+    /// sensors that can see every other position but not the beacon. The
+    /// sensors sit at the given points and then wherever there's a gap left,
+    /// each reaching as far as it can without seeing the beacon, with its
+    /// own beacon off to one side.
+    fn any_report() -> impl Strategy<Value = (Vec<Sensor>, i64, Point)> {
+        (4..=20i64)
+            .prop_flat_map(|bound| {
+                let point = move || (0..=bound, 0..=bound).prop_map(|(x, y)| Point::new(x, y));
+                (Just(bound), point(), prop::collection::vec(point(), 0..8))
+            })
+            .prop_map(|(bound, hidden, seeds)| {
+                let sensor_at = |location: Point| {
+                    let range = location.manhattan(hidden) - 1;
+                    Sensor::new(location, location + Point::new(range, 0))
+                };
+                let mut sensors: Vec<Sensor> = seeds
+                    .into_iter()
+                    .filter(|&seed| seed != hidden)
+                    .map(sensor_at)
+                    .collect();
+                for y in 0..=bound {
+                    for x in 0..=bound {
+                        let point = Point::new(x, y);
+                        if point != hidden && sensors.iter().all(|s| !s.can_detect(&point)) {
+                            sensors.push(sensor_at(point));
+                        }
+                    }
+                }
+                sensors.sort_unstable();
+                (sensors, bound, hidden)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn finds_the_same_distress_beacon((sensors, bound, hidden) in any_report()) {
+            prop_assert_eq!(distress_beacon_at(&sensors, bound), Some(hidden));
+            prop_assert_eq!(super::distress_beacon_at(&sensors, bound), Some(hidden));
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use common::Solution;
+use day15::Day15;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day15::EXAMPLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day15::EXAMPLE.part1(&input).unwrap(), 26);
+}
+
+#[test]
+fn part2() {
+    let input = Day15::EXAMPLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day15::EXAMPLE.part2(&input).unwrap(), 56000011);
+}
@@ -0,0 +1,333 @@
+use anyhow::{bail, Result};
+use common::Solution;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+common::configure! {
+    /// Where and for how long the valves are opened, which is the same for
+    /// the example and the real puzzle.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Day16 {
+        /// The valve to start at.
+        pub start: ValveName,
+        /// How many minutes there are before the volcano erupts.
+        pub minutes: u32,
+        /// How many of those minutes it takes to teach the elephant to help,
+        /// in part 2.
+        pub training: u32,
+    }
+    presets: ["puzzle" => Day16::PUZZLE]
+}
+
+impl Day16 {
+    pub const PUZZLE: Day16 = Day16 {
+        start: ValveName(*b"AA"),
+        minutes: 30,
+        training: 4,
+    };
+
+    /// The index of the start valve, as long as there's a valve there.
+    fn start(&self, valves: &[Valve]) -> Result<usize> {
+        let start = self.start.index();
+        if !valves.iter().any(|valve| valve.index == start) {
+            bail!("There's no valve {} to start at", self.start);
+        }
+        Ok(start)
+    }
+}
+
+/// A valve's name, two capital letters like `AA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValveName([u8; 2]);
+
+impl ValveName {
+    fn index(self) -> usize {
+        let [first, second] = self.0;
+        (first - b'A') as usize * 26 + (second - b'A') as usize
+    }
+}
+
+impl Display for ValveName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [first, second] = self.0;
+        write!(f, "{}{}", first as char, second as char)
+    }
+}
+
+impl FromStr for ValveName {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match *s.as_bytes() {
+            [first, second] if first.is_ascii_uppercase() && second.is_ascii_uppercase() => {
+                Ok(ValveName([first, second]))
+            }
+            _ => bail!("valve names are two capital letters, like AA"),
+        }
+    }
+}
+
+impl Solution for Day16 {
+    type Input = Vec<Valve>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, contents: &str) -> Result<Self::Input> {
+        let expected = "a valve like \"Valve BB has flow rate=13; tunnels lead to valves CC, AA\"";
+        Ok(common::parse_lines(
+            contents.trim_end(),
+            expected,
+            Valve::from_string,
+        )?)
+    }
+
+    fn part1(&self, valves: &Self::Input) -> Result<u32> {
+        Ok(part1(valves, self.start(valves)?, self.minutes))
+    }
+
+    fn part2(&self, valves: &Self::Input) -> Result<u32> {
+        let minutes = self.minutes.saturating_sub(self.training);
+        part2(valves, self.start(valves)?, minutes)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Valve {
+    index: usize,
+    flow_rate: u32,
+    tunnels: Vec<usize>,
+}
+
+impl Valve {
+    fn from_string(s: &str) -> Option<Self> {
+        let re = regex::Regex::new(
+            r"^Valve ([A-Z]{2}) has flow rate=(\d+); tunnels? leads? to valves? ([A-Z]{2}(, [A-Z]{2})*)$",
+        )
+        .unwrap();
+        let captures = re.captures(s)?;
+
+        Some(Valve {
+            index: Valve::index_from(&captures[1]),
+            flow_rate: captures[2].parse().ok()?,
+            tunnels: Valve::tunnels_from(&captures[3]),
+        })
+    }
+
+    fn index_from(s: &str) -> usize {
+        let chars = s.as_bytes();
+        (chars[0] - b'A') as usize * 26 + (chars[1] - b'A') as usize
+    }
+
+    fn tunnels_from(s: &str) -> Vec<usize> {
+        s.split(", ").map(Valve::index_from).collect()
+    }
+}
+
+type DistanceGrid = Vec<Vec<u32>>;
+
+fn part1(valves: &[Valve], start: usize, num_minutes: u32) -> u32 {
+    let distance_grid = build_distance_grid(valves);
+    let closed_valves = valves.iter().filter(|valve| valve.flow_rate > 0).collect();
+    run(&distance_grid, closed_valves, start, num_minutes)
+}
+
+/// The most pressure we and the elephant can release together in
+/// `num_minutes`, once the elephant has been trained.
+fn part2(valves: &[Valve], start: usize, num_minutes: u32) -> Result<u32> {
+    let distance_grid = build_distance_grid(valves);
+    let valves = valves
+        .iter()
+        .filter(|v| v.flow_rate > 0)
+        .collect::<Vec<&Valve>>();
+    let count = valves.len();
+
+    if count > 16 {
+        bail!("Expected at most 16 valves with any flow, found {count}");
+    }
+
+    let last_index = 2_u32.pow(count as u32);
+
+    // phase 1: compute and store the result of best_path() for each possible subset of valves
+    let mut pressures: Vec<u32> = vec![0; last_index as usize];
+
+    for i in 1..last_index {
+        let valve_set: Vec<&Valve> = get_valves_for_bitstring(i, count, &valves);
+        let pressure = run(&distance_grid, valve_set, start, num_minutes);
+
+        pressures[i as usize] = pressure;
+    }
+
+    // phase 2: find the best pressure possible when adding the pressure from one set of
+    // valves to its *complement* set of valves. this accounts for both us and the elephant
+    let mut best = 0;
+
+    for us in 1..last_index {
+        let elephant = bitstring_complement(us, count as u32);
+        let sum = pressures[us as usize] + pressures[elephant as usize];
+
+        best = best.max(sum)
+    }
+
+    Ok(best)
+}
+
+fn run(
+    distance_grid: &DistanceGrid,
+    closed_valves: Vec<&Valve>,
+    start: usize,
+    num_minutes: u32,
+) -> u32 {
+    let mut pressures: Vec<u32> = vec![];
+
+    for valve in &closed_valves {
+        let distance = distance_grid[start][valve.index];
+
+        if distance >= num_minutes {
+            continue;
+        }
+
+        let pressure = valve.flow_rate * (num_minutes - distance - 1);
+
+        let remaining = closed_valves
+            .iter()
+            .filter(|v| v.index != valve.index)
+            .cloned()
+            .collect();
+
+        let best_pressure = pressure
+            + run(
+                distance_grid,
+                remaining,
+                valve.index,
+                num_minutes - distance - 1,
+            );
+
+        pressures.push(best_pressure);
+    }
+
+    pressures.into_iter().max().unwrap_or(0)
+}
+
+fn build_distance_grid(valves: &[Valve]) -> DistanceGrid {
+    let last_index = Valve::index_from("ZZ");
+    let mut edges = vec![vec![]; last_index + 1];
+
+    for valve in valves {
+        for tunnel in &valve.tunnels {
+            edges[valve.index].push(*tunnel);
+        }
+    }
+
+    // A step to a neighbouring valve is always a distance of 1, so a
+    // breadth-first search from each valve finds the distance to every other.
+    let tunnels = |&valve: &usize| edges[valve].iter().copied();
+    let mut distance_grid = vec![vec![0; last_index + 1]; last_index + 1];
+    for valve in valves {
+        let search = search::bfs(&tunnels, valve.index, |_| false);
+        distance_grid[valve.index] = (0..=last_index)
+            .map(|to| search.cost(&to).map_or(u32::MAX - 1, |steps| steps as u32))
+            .collect();
+    }
+
+    distance_grid
+}
+
+// if the bitstring is 5 (binary 101) then include the 1st and 3rd valves
+fn get_valves_for_bitstring<'a>(
+    bitstring: u32,
+    count: usize,
+    valves: &[&'a Valve],
+) -> Vec<&'a Valve> {
+    get_elements_for_bitstring(bitstring, count, valves)
+}
+
+fn get_elements_for_bitstring<'a, T>(
+    bitstring: u32,
+    count: usize,
+    elements: &[&'a T],
+) -> Vec<&'a T> {
+    let mut vec: Vec<&T> = Vec::with_capacity(count);
+
+    for (i, el) in elements.iter().enumerate() {
+        let anded = bitstring & 2_u32.pow(i as u32);
+        if anded > 0 {
+            vec.push(el);
+        }
+    }
+
+    vec
+}
+
+// 011000 -> 100111
+fn bitstring_complement(num: u32, bit_count: u32) -> u32 {
+    !num & (2_u32.pow(bit_count) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_valve() {
+        let valve =
+            Valve::from_string("Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE")
+                .unwrap();
+        assert_eq!(
+            valve,
+            Valve {
+                index: Valve::index_from("DD"),
+                flow_rate: 20,
+                tunnels: vec![
+                    Valve::index_from("CC"),
+                    Valve::index_from("AA"),
+                    Valve::index_from("EE"),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_a_valve_with_a_single_tunnel() {
+        let valve =
+            Valve::from_string("Valve HH has flow rate=22; tunnel leads to valve GG").unwrap();
+        assert_eq!(valve.flow_rate, 22);
+        assert_eq!(valve.tunnels, vec![Valve::index_from("GG")]);
+    }
+
+    #[test]
+    fn start_valve_must_exist() {
+        let valves = Day16::PUZZLE
+            .parse("Valve AA has flow rate=0; tunnel leads to valve BB\n")
+            .unwrap();
+        let day = Day16 {
+            start: "BB".parse().unwrap(),
+            ..Day16::PUZZLE
+        };
+        assert!(day.part1(&valves).is_err());
+        assert_eq!(day.start.index(), Valve::index_from("BB"));
+        assert!("aa".parse::<ValveName>().is_err());
+        assert!("AAA".parse::<ValveName>().is_err());
+    }
+
+    #[test]
+    fn valve_names_index_in_base_26() {
+        assert_eq!(Valve::index_from("AA"), 0);
+        assert_eq!(Valve::index_from("AB"), 1);
+        assert_eq!(Valve::index_from("BA"), 26);
+        assert_eq!(Valve::index_from("ZZ"), 675);
+    }
+
+    #[test]
+    fn locates_a_malformed_valve() {
+        let input = "Valve AA has flow rate=0; tunnel leads to valve BB\n\
+                     Valve BB has flow rate=13; tunnel leads to valve a\n";
+        let error = Day16::PUZZLE.parse(input).unwrap_err();
+        let error = error.downcast_ref::<common::ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert!(
+            Valve::from_string("Valve BB has flow rate=13; tunnels lead to valves AA,CC").is_none()
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use common::Solution;
+use day16::Day16;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day16::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day16::PUZZLE.part1(&input).unwrap(), 1651);
+}
+
+#[test]
+fn part2() {
+    let input = Day16::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day16::PUZZLE.part2(&input).unwrap(), 1707);
+}
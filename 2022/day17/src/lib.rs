@@ -0,0 +1,366 @@
+use animate::{Animate, Frame, Hook};
+use common::{Answer, Part, Solution, Text};
+use export::{colours, Export, Image};
+use geometry::{Dir4, Vec2};
+use std::cmp;
+use std::collections::BTreeSet;
+
+const WIDTH: i64 = 7;
+
+const CACHE_LEN: usize = 20;
+
+/// How far down the tower a frame shows, below the top.
+const FRAME_DEPTH: i64 = 40;
+
+type Point = Vec2<i64>;
+
+type Rock = Vec<Point>;
+
+/// The state that identifies a repeating point in the simulation: the jet
+/// index, the next piece, and the shape of the reachable ground.
+type CycleKey = (i64, i64, Vec<Point>);
+
+fn read_input(contents: &str) -> Vec<char> {
+    contents.chars().collect::<Vec<char>>()
+}
+
+/// Which way a jet pushes the falling rock. `y` counts up from the floor in
+/// the tower, so only the horizontal directions are used.
+fn push(jet: char) -> Point {
+    match jet {
+        '<' => Dir4::Left.step(),
+        '>' => Dir4::Right.step(),
+        _ => Point::zero(),
+    }
+}
+
+fn free(settled: &BTreeSet<Point>, cell: Point) -> bool {
+    (0..WIDTH).contains(&cell.x) && (cell.y > 0) && !settled.contains(&cell)
+}
+
+fn can_move(settled: &BTreeSet<Point>, piece: i64, at: Point, rocks: &[Rock]) -> bool {
+    rocks[piece as usize]
+        .iter()
+        .all(|&offset| free(settled, at + offset))
+}
+
+/// Drop a rock until it comes to rest, sending `hook` a frame at each step
+/// it falls.
+fn place(
+    settled: &mut BTreeSet<Point>,
+    jet: i64,
+    piece: i64,
+    max_y: i64,
+    jets: &[char],
+    rocks: &[Rock],
+    hook: &mut Hook,
+) -> (i64, i64, i64) {
+    let fall = Point::new(0, -1);
+    let mut at = Point::new(2, max_y + 5);
+    let mut new_jet = jet;
+    while can_move(settled, piece, at + fall, rocks) {
+        at += fall;
+        let pushed = at + push(jets[new_jet as usize]);
+        if can_move(settled, piece, pushed, rocks) {
+            at = pushed;
+        }
+        new_jet = (new_jet + 1) % (jets.len() as i64);
+        hook.frame(|| frame(settled, &rocks[piece as usize], at, max_y));
+    }
+    let new_cells: Vec<Point> = rocks[piece as usize]
+        .iter()
+        .map(|&offset| at + offset)
+        .collect();
+    settled.extend(&new_cells);
+    (
+        new_jet,
+        (piece + 1) % rocks.len() as i64,
+        cmp::max(max_y, new_cells.iter().map(|cell| cell.y).max().unwrap()),
+    )
+}
+
+/// The top of the tower, with the falling rock drawn in at `at`.
+fn frame(settled: &BTreeSet<Point>, rock: &Rock, at: Point, max_y: i64) -> Frame {
+    let falling: BTreeSet<Point> = rock.iter().map(|&offset| at + offset).collect();
+    let top = falling
+        .iter()
+        .map(|cell| cell.y)
+        .max()
+        .unwrap_or(max_y)
+        .max(max_y);
+    let bottom = (top - FRAME_DEPTH).max(1);
+
+    let mut picture = String::new();
+    for y in (bottom..=top).rev() {
+        picture.push('|');
+        for x in 0..WIDTH {
+            let cell = Point::new(x, y);
+            picture.push(if falling.contains(&cell) {
+                '@'
+            } else if settled.contains(&cell) {
+                '#'
+            } else {
+                '.'
+            });
+        }
+        picture.push_str("|\n");
+    }
+    if bottom == 1 {
+        picture.push_str("+-------+\n");
+    }
+    Frame::new(picture)
+        .focus((at.x as usize + 1, (top - at.y) as usize))
+        .caption(format!("{max_y} rows of rock"))
+}
+
+fn ground_shape(settled: &BTreeSet<Point>, max_y: i64) -> Option<Vec<Point>> {
+    let mut state: BTreeSet<Point> = BTreeSet::new();
+    for x in 0..WIDTH {
+        search(Point::new(x, 0), &mut state, max_y, settled);
+    }
+    if state.len() <= CACHE_LEN {
+        Some(state.into_iter().collect::<Vec<Point>>())
+    } else {
+        None
+    }
+}
+
+fn search(cell: Point, visited: &mut BTreeSet<Point>, max_y: i64, settled: &BTreeSet<Point>) {
+    let top = Point::new(0, max_y);
+    if (!free(settled, top + cell)) || visited.contains(&cell) || visited.len() > CACHE_LEN {
+        return;
+    }
+    visited.insert(cell);
+    [Point::new(-1, 0), Point::new(1, 0), Point::new(0, -1)]
+        .iter()
+        .for_each(|&step| {
+            search(cell + step, visited, max_y, settled);
+        });
+}
+
+/// The tower as it grows, rock by rock.
+#[derive(Debug, Default)]
+struct Tower {
+    settled: BTreeSet<Point>,
+    jet: i64,
+    piece: i64,
+    max_y: i64,
+}
+
+/// The height of the tower after `num_rocks` rocks, skipping ahead through
+/// whole cycles once the jets, the next piece and the shape of the ground
+/// come round again.
+fn solve(num_rocks: usize, jets: &[char], rocks: &[Rock], hook: &mut Hook) -> i64 {
+    cycles::simulate(
+        Tower::default(),
+        num_rocks,
+        |tower| {
+            (tower.jet, tower.piece, tower.max_y) = place(
+                &mut tower.settled,
+                tower.jet,
+                tower.piece,
+                tower.max_y,
+                jets,
+                rocks,
+                hook,
+            );
+        },
+        |tower| -> Option<CycleKey> {
+            let ground = ground_shape(&tower.settled, tower.max_y)?;
+            Some((tower.jet, tower.piece, ground))
+        },
+        |tower| tower.max_y,
+    )
+}
+
+/// Drop `num_rocks` rocks one by one, without skipping ahead through the
+/// cycles, returning every cell of rock in the tower and its height.
+fn tower(num_rocks: usize, jets: &[char], rocks: &[Rock]) -> (BTreeSet<Point>, i64) {
+    let mut settled: BTreeSet<Point> = BTreeSet::new();
+    let (mut jet, mut piece, mut max_y) = (0, 0, 0);
+    for _ in 0..num_rocks {
+        (jet, piece, max_y) = place(
+            &mut settled,
+            jet,
+            piece,
+            max_y,
+            jets,
+            rocks,
+            &mut Hook::none(),
+        );
+    }
+    (settled, max_y)
+}
+
+fn rocks() -> Vec<Rock> {
+    let shapes: [&[(i64, i64)]; 5] = [
+        &[(0, 0), (1, 0), (2, 0), (3, 0)],
+        &[(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)],
+        &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+        &[(0, 0), (0, 1), (0, 2), (0, 3)],
+        &[(0, 0), (0, 1), (1, 0), (1, 1)],
+    ];
+    shapes
+        .iter()
+        .map(|shape| shape.iter().copied().map(Point::from).collect())
+        .collect()
+}
+
+common::configure! {
+    /// How many rocks fall in each part of the puzzle.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Day17 {
+        /// How many rocks fall in part 1.
+        pub part1_rocks: usize,
+        /// How many rocks fall in part 2.
+        pub part2_rocks: usize,
+    }
+    presets: ["puzzle" => Day17::PUZZLE]
+}
+
+impl Day17 {
+    pub const PUZZLE: Day17 = Day17 {
+        part1_rocks: 2022,
+        part2_rocks: 1_000_000_000_000,
+    };
+}
+
+impl Solution for Day17 {
+    /// The jet pattern, as `<` and `>` characters.
+    type Input = Vec<char>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input_str: &str) -> anyhow::Result<Self::Input> {
+        let text = Text::new(input_str);
+        let jets: Vec<char> = read_input(text.trim_end());
+        // Without any jets, no rock would ever be pushed
+        let bad_jet = jets.iter().position(|&jet| jet != '<' && jet != '>');
+        if let Some(at) = bad_jet.or(jets.is_empty().then_some(0)) {
+            return Err(text.error(&text[at..], "a jet (< or >)").into());
+        }
+        Ok(jets)
+    }
+
+    fn part1(&self, jets: &Self::Input) -> anyhow::Result<i64> {
+        Ok(solve(self.part1_rocks, jets, &rocks(), &mut Hook::none()))
+    }
+
+    fn part2(&self, jets: &Self::Input) -> anyhow::Result<i64> {
+        Ok(solve(self.part2_rocks, jets, &rocks(), &mut Hook::none()))
+    }
+}
+
+impl Animate for Day17 {
+    fn animate(&self, jets: &Self::Input, part: Part, hook: &mut Hook) -> anyhow::Result<Answer> {
+        let num_rocks = match part {
+            Part::One => self.part1_rocks,
+            Part::Two => self.part2_rocks,
+        };
+        Ok(solve(num_rocks, jets, &rocks(), hook).into())
+    }
+}
+
+impl Export for Day17 {
+    /// The tower once part 1's rocks have fallen, with the floor at the
+    /// bottom.
+    fn picture(&self, jets: &Self::Input) -> anyhow::Result<Image> {
+        let (settled, height) = tower(self.part1_rocks, jets, &rocks());
+        Ok(Image::grid(
+            WIDTH as usize,
+            height as usize,
+            colours::BACKGROUND,
+            |col, row| {
+                let cell = Point::new(col as i64, height - row as i64);
+                settled.contains(&cell).then_some(colours::SILVER)
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn parses_jets_without_the_newline() {
+        let jets = Day17::PUZZLE.parse("<<>\n").unwrap();
+        assert_eq!(jets, vec!['<', '<', '>']);
+
+        let error = Day17::PUZZLE.parse("<<v>\n").unwrap_err();
+        let error = error.downcast_ref::<common::ParseError>().unwrap();
+        assert_eq!((error.column, error.found.as_str()), (3, "v>"));
+        assert!(Day17::PUZZLE.parse("\n").is_err());
+    }
+
+    #[test]
+    fn height_counts_every_row_of_rock() {
+        // A single flat rock, then a plus sitting on top of it
+        assert_eq!(solve(1, &['<'], &rocks(), &mut Hook::none()), 1);
+        assert_eq!(solve(2, &['<'], &rocks(), &mut Hook::none()), 4);
+    }
+
+    #[test]
+    fn frames_show_the_rock_falling_onto_the_tower() {
+        let mut frames = Vec::new();
+        let mut watcher = |frame: Frame| {
+            frames.push(frame);
+            std::ops::ControlFlow::Continue(())
+        };
+        solve(1, &['<'], &rocks(), &mut Hook::new(&mut watcher));
+
+        // The flat rock starts four rows up, and is pushed left as it falls
+        assert_eq!(frames.len(), 4);
+        let rows = |frame: &Frame| -> Vec<String> {
+            frame.rows.iter().map(|row| row.iter().collect()).collect()
+        };
+        assert_eq!(rows(&frames[0])[0], "|.@@@@..|");
+        assert_eq!(rows(&frames[3]), ["|@@@@...|", "+-------+"]);
+        assert_eq!(frames[3].focus, Some((1, 0)));
+    }
+
+    #[test]
+    fn picture_stands_the_tower_on_the_floor() {
+        let day = Day17 {
+            part1_rocks: 2,
+            part2_rocks: 2,
+        };
+        let image = day.picture(&vec!['<']).unwrap();
+        assert_eq!((image.width, image.height), (7.0, 4.0));
+        let raster = image.rasterize(1.0).unwrap();
+        let rows: Vec<String> = (0..4)
+            .map(|y| {
+                (0..7)
+                    .map(|x| match raster.get(x, y) {
+                        colours::SILVER => '#',
+                        _ => '.',
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rows, [".#.....", "###....", ".#.....", "####..."]);
+    }
+}
+
+/// Checks for the cycle skipping in `solve`, against dropping every rock.
+#[cfg(test)]
+mod reference {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// A jet pattern of up to 20 pushes either way.
+    fn any_jets() -> impl Strategy<Value = Vec<char>> {
+        prop::collection::vec(prop_oneof![Just('<'), Just('>')], 1..20)
+    }
+
+    proptest! {
+        #[test]
+        fn skips_cycles_to_the_same_height(jets in any_jets(), num_rocks in 1..500usize) {
+            let (_, height) = tower(num_rocks, &jets, &rocks());
+            prop_assert_eq!(solve(num_rocks, &jets, &rocks(), &mut Hook::none()), height);
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use common::Solution;
+use day17::Day17;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day17::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day17::PUZZLE.part1(&input).unwrap(), 3068);
+}
+
+#[test]
+fn part2() {
+    let input = Day17::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day17::PUZZLE.part2(&input).unwrap(), 1514285714288);
+}
+
+#[test]
+fn tower_after_ten_rocks() {
+    let day = Day17 {
+        part1_rocks: 10,
+        ..Day17::PUZZLE
+    };
+    let jets = day.parse(EXAMPLE).unwrap();
+    assert_eq!(day.part1(&jets).unwrap(), 17);
+}
@@ -0,0 +1,128 @@
+use anyhow::Result;
+use common::Solution;
+use geometry::Vec3;
+use std::collections::HashSet;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Cube>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, s: &str) -> Result<Self::Input> {
+        let expected = format!("a cube like 2,1,5, with coordinates from 0 to {MAX_COORDINATE}");
+        Ok(common::parse_lines(s, expected, parse_cube)?)
+    }
+
+    fn part1(&self, cubes: &Self::Input) -> Result<usize> {
+        Ok(part1(cubes))
+    }
+
+    fn part2(&self, cubes: &Self::Input) -> Result<usize> {
+        Ok(part2(cubes))
+    }
+}
+
+fn part1(content: &[Cube]) -> usize {
+    let mut exposed = 0;
+
+    for cube in content {
+        let neighbors: Vec<Cube> = cube.neighbours6().collect();
+
+        exposed += 6 - content
+            .iter()
+            .filter(|c| *c != cube)
+            .filter(|c| neighbors.contains(c))
+            .count();
+    }
+
+    exposed
+}
+
+fn part2(content: &[Cube]) -> usize {
+    let cubes: Vec<Cube> = content.iter().map(|&c| c + Cube::new(1, 1, 1)).collect();
+
+    let x = content.iter().map(|cube| cube.x).max().unwrap_or_default() + 2;
+    let y = content.iter().map(|cube| cube.y).max().unwrap_or_default() + 2;
+    let z = content.iter().map(|cube| cube.z).max().unwrap_or_default() + 2;
+
+    count_faces(&cubes, x, y, z)
+}
+
+fn count_faces(cubes: &[Cube], x: i32, y: i32, z: i32) -> usize {
+    let (min, max) = (Cube::zero(), Cube::new(x, y, z));
+    let cubes: HashSet<Cube> = cubes.iter().copied().collect();
+
+    // flood the air around the droplet, which leaves out any pockets inside it
+    let air = |cursor: &Cube| {
+        cursor
+            .neighbours6()
+            .filter(|cube| cube.within(min, max) && !cubes.contains(cube))
+    };
+    let outside = search::flood_fill(&air, min);
+
+    // every cube next to the outside air has a face we can count
+    outside
+        .iter()
+        .flat_map(|cursor| cursor.neighbours6())
+        .filter(|c| cubes.contains(c))
+        .count()
+}
+
+pub type Cube = Vec3<i32>;
+
+/// The biggest coordinate a cube can have. Puzzle droplets fit in 25 units a
+/// side, and part 2 floods the whole box around the droplet, so this keeps
+/// that box to about a million cubes.
+pub(crate) const MAX_COORDINATE: i32 = 100;
+
+/// Parse a cube like `2,1,5`. The droplet is scanned from the origin, so no
+/// coordinate can be negative, or more than [`MAX_COORDINATE`].
+fn parse_cube(s: &str) -> Option<Cube> {
+    let coordinates = s
+        .split(',')
+        .map(|n| {
+            n.parse()
+                .ok()
+                .filter(|n: &i32| (0..=MAX_COORDINATE).contains(n))
+        })
+        .collect::<Option<Vec<i32>>>()?;
+    match coordinates[..] {
+        [x, y, z] => Some(Cube::new(x, y, z)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_cube() {
+        assert_eq!(parse_cube("2,1,5"), Some(Cube::new(2, 1, 5)));
+        assert_eq!(parse_cube("0,12,3"), Some(Cube::new(0, 12, 3)));
+        assert_eq!(parse_cube("1,2"), None);
+        assert_eq!(parse_cube("1,2,3,4"), None);
+        assert_eq!(parse_cube("1,-2,3"), None);
+        assert_eq!(parse_cube("100,0,100"), Some(Cube::new(100, 0, 100)));
+        assert_eq!(parse_cube("101,0,0"), None);
+        assert_eq!(parse_cube("2147483647,0,0"), None);
+
+        let error = Day18.parse("2,1,5\n1,2\n").unwrap_err();
+        let error = error.downcast_ref::<common::ParseError>().unwrap();
+        assert_eq!((error.line, error.found.as_str()), (2, "1,2"));
+        let error = Day18.parse("2,1,5\n3000,3000,3000\n").unwrap_err();
+        let error = error.downcast_ref::<common::ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn adjacent_cubes_hide_a_face_each() {
+        let cubes = [Cube::new(1, 1, 1), Cube::new(2, 1, 1)];
+        assert_eq!(part1(&cubes), 10);
+        assert_eq!(part2(&cubes), 10);
+    }
+}
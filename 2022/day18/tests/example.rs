@@ -0,0 +1,16 @@
+use common::Solution;
+use day18::Day18;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day18.parse(EXAMPLE).unwrap();
+    assert_eq!(Day18.part1(&input).unwrap(), 64);
+}
+
+#[test]
+fn part2() {
+    let input = Day18.parse(EXAMPLE).unwrap();
+    assert_eq!(Day18.part2(&input).unwrap(), 58);
+}
@@ -0,0 +1,619 @@
+use common::Solution;
+use rayon::prelude::*;
+use std::{
+    cell::Cell,
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+    iter::zip,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, SubAssign},
+};
+use tracing::{debug, debug_span};
+use Resource::*;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, input_str: &str) -> anyhow::Result<Self::Input> {
+        parser::parse(input_str)
+    }
+
+    fn part1(&self, input: &Self::Input) -> anyhow::Result<u32> {
+        Ok(part1(input))
+    }
+
+    fn part2(&self, input: &Self::Input) -> anyhow::Result<u32> {
+        Ok(part2(input))
+    }
+}
+
+fn part1(input: &[Blueprint]) -> u32 {
+    input
+        .par_iter()
+        .map(|blueprint| Factory::new(blueprint, 24))
+        .map(|factory| factory.quality_level())
+        .sum::<u32>()
+}
+
+fn part2(input: &[Blueprint]) -> u32 {
+    input
+        .par_iter()
+        .take(3)
+        .map(|blueprint| Factory::new(blueprint, 32))
+        .map(|factory| factory.geodes_produced())
+        .product::<u32>()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resource {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+struct ResourceCountArray([u32; 4]);
+
+impl Index<Resource> for ResourceCountArray {
+    type Output = u32;
+
+    fn index(&self, index: Resource) -> &Self::Output {
+        match index {
+            Resource::Ore => &self.0[0],
+            Resource::Clay => &self.0[1],
+            Resource::Obsidian => &self.0[2],
+            Resource::Geode => &self.0[3],
+        }
+    }
+}
+
+impl IndexMut<Resource> for ResourceCountArray {
+    fn index_mut(&mut self, index: Resource) -> &mut Self::Output {
+        match index {
+            Resource::Ore => &mut self.0[0],
+            Resource::Clay => &mut self.0[1],
+            Resource::Obsidian => &mut self.0[2],
+            Resource::Geode => &mut self.0[3],
+        }
+    }
+}
+
+impl Index<usize> for ResourceCountArray {
+    type Output = u32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl IndexMut<usize> for ResourceCountArray {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl Add<ResourceCountArray> for ResourceCountArray {
+    type Output = ResourceCountArray;
+
+    fn add(self, other: ResourceCountArray) -> Self::Output {
+        let mut sum: ResourceCountArray = Default::default();
+        for (idx, (lhs, rhs)) in self.into_iter().zip(other).enumerate() {
+            sum[idx] = lhs + rhs;
+        }
+        sum
+    }
+}
+
+impl AddAssign<ResourceCountArray> for ResourceCountArray {
+    fn add_assign(&mut self, rhs: ResourceCountArray) {
+        for (idx, value) in rhs.into_iter().enumerate() {
+            self[idx] += value;
+        }
+    }
+}
+
+impl SubAssign<ResourceCountArray> for ResourceCountArray {
+    fn sub_assign(&mut self, rhs: ResourceCountArray) {
+        for (idx, value) in rhs.into_iter().enumerate() {
+            self[idx] -= value;
+        }
+    }
+}
+
+impl Mul<u32> for ResourceCountArray {
+    type Output = ResourceCountArray;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        let mut product: ResourceCountArray = Default::default();
+        for (idx, value) in self.into_iter().enumerate() {
+            product[idx] = value * rhs;
+        }
+        product
+    }
+}
+
+impl IntoIterator for ResourceCountArray {
+    type Item = u32;
+    type IntoIter = std::array::IntoIter<Self::Item, 4>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl ResourceCountArray {
+    /// But only because _saturating_sub()_ isn't from a trait. We needed this one
+    /// for a _very_ important performance optimization, though. You'll see that in
+    /// the code for part one.
+    fn saturating_sub(&self, other: ResourceCountArray) -> ResourceCountArray {
+        let mut difference: ResourceCountArray = Default::default();
+        for (idx, (lhs, rhs)) in self.into_iter().zip(other).enumerate() {
+            difference[idx] = lhs.saturating_sub(rhs);
+        }
+        difference
+    }
+}
+
+/// Represents a recipe for making a new robot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Recipe {
+    bot: Resource,
+    cost: ResourceCountArray, // See, everywere.
+}
+
+/// Represents an entire blueprint, with ID and recipe for each bot type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blueprint {
+    id: u32,
+    recipes: [Recipe; 4],
+}
+
+impl Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Ore => "ore",
+            Clay => "clay",
+            Obsidian => "obsidian",
+            Geode => "geode",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Written as in the blueprints, like `Each obsidian robot costs 3 ore and
+/// 14 clay.`, with the costs in the order of the resources.
+impl Display for Recipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let costs: Vec<String> = zip([Ore, Clay, Obsidian, Geode], self.cost)
+            .filter(|&(_, amount)| amount > 0)
+            .map(|(resource, amount)| format!("{amount} {resource}"))
+            .collect();
+        write!(f, "Each {} robot costs {}.", self.bot, costs.join(" and "))
+    }
+}
+
+/// A line of the input, with the blueprint's ID and then its four recipes.
+impl Display for Blueprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Blueprint {}:", self.id)?;
+        for recipe in &self.recipes {
+            write!(f, " {recipe}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The usual module wrapping the parsers for today's input. I'll be honest, the whole
+/// inner module thing still seems a little odd to me, but it's the best way I could
+/// come up with to namespace the parsing functions so far. So, I'm keeping it until
+/// I can think of (or steal) a better idea.
+mod parser {
+    use super::*;
+    use anyhow::Result;
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{space1, u32},
+        combinator::{cut, value},
+        sequence::{pair, preceded, separated_pair, terminated, tuple},
+        IResult,
+    };
+    use parsing::{finish, lines0};
+
+    /// Nom parser for "ore" -> Resource::Ore
+    fn ore(s: &str) -> IResult<&str, Resource> {
+        value(Resource::Ore, tag("ore"))(s)
+    }
+
+    /// Nom parser for "clay" -> Resource::Clay
+    fn clay(s: &str) -> IResult<&str, Resource> {
+        value(Resource::Clay, tag("clay"))(s)
+    }
+
+    /// Nom parser for "obsidian" -> Resource::Obsidian
+    fn obsidian(s: &str) -> IResult<&str, Resource> {
+        value(Resource::Obsidian, tag("obsidian"))(s)
+    }
+
+    /// Nom parser for "geode" -> Resource::Geode
+    fn geode(s: &str) -> IResult<&str, Resource> {
+        value(Resource::Geode, tag("geode"))(s)
+    }
+
+    /// Parses any resource name into the relevant `Resource`
+    fn resource(s: &str) -> IResult<&str, Resource> {
+        alt((ore, clay, obsidian, geode))(s)
+    }
+
+    /// Nom parser for "4 ore" -> ResourceCountArray([4, 0, 0, 0])
+    fn cost(s: &str) -> IResult<&str, ResourceCountArray> {
+        let (s, (amt, resource)) = separated_pair(u32, space1, resource)(s)?;
+        let mut cost: ResourceCountArray = Default::default();
+        cost[resource] += amt;
+        Ok((s, cost))
+    }
+
+    /// Nom parser for "3 ore and 14 clay" -> ResourceCountArray([3, 14, 0, 0])
+    fn cost2(s: &str) -> IResult<&str, ResourceCountArray> {
+        let (s, (cost1, cost2)) = separated_pair(cost, tag(" and "), cost)(s)?;
+        Ok((s, cost1 + cost2))
+    }
+
+    /// Nom parser for
+    ///   "Each ore robot costs 4 ore"
+    ///     -> Recipe { bot: Resource::Ore, cost: ResourceCountArray([4, 0, 0, 0]) }
+    fn recipe(s: &str) -> IResult<&str, Recipe> {
+        let (s, _) = tag("Each ")(s)?;
+        let (s, bot) = resource(s)?;
+        let (s, _) = tag(" robot costs ")(s)?;
+        let (s, cost) = alt((cost2, cost))(s)?;
+        let (s, _) = tag(".")(s)?;
+        Ok((s, Recipe { bot, cost }))
+    }
+
+    /// Nom parser for all four recipes from a line
+    fn recipes(s: &str) -> IResult<&str, [Recipe; 4]> {
+        let (s, (r1, r2, r3, r4)) = tuple((
+            preceded(space1, recipe),
+            preceded(space1, recipe),
+            preceded(space1, recipe),
+            preceded(space1, recipe),
+        ))(s)?;
+        Ok((s, [r1, r2, r3, r4]))
+    }
+
+    /// Nom parser for a single line of the input, producing a Blueprint.
+    /// Once a line starts like a blueprint, the rest of it has to be one.
+    fn blueprint(s: &str) -> IResult<&str, Blueprint> {
+        let (s, _) = tag("Blueprint ")(s)?;
+        let (s, (id, recipes)) = cut(pair(terminated(u32, tag(":")), recipes))(s)?;
+        Ok((s, Blueprint { id, recipes }))
+    }
+
+    /// Entrypoint for the parsing functions, one Blueprint per line
+    pub(crate) fn parse(s: &str) -> Result<Vec<Blueprint>> {
+        Ok(finish(
+            s,
+            "a blueprint of four robot recipes",
+            lines0(blueprint),
+        )?)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use common::ParseError;
+        use proptest::prelude::*;
+
+        fn any_resource() -> impl Strategy<Value = Resource> {
+            prop_oneof![Just(Ore), Just(Clay), Just(Obsidian), Just(Geode)]
+        }
+
+        /// A recipe costing one or two different resources, in amounts that
+        /// are never zero, since a zero cost isn't written down.
+        fn any_recipe() -> impl Strategy<Value = Recipe> {
+            let amount = || 1..=u32::MAX;
+            let single = (any_resource(), amount()).prop_map(|(resource, amount)| {
+                let mut cost = ResourceCountArray::default();
+                cost[resource] = amount;
+                cost
+            });
+            let double = (any_resource(), any_resource(), amount(), amount())
+                .prop_filter("the two costs are of different resources", |(a, b, ..)| {
+                    a != b
+                })
+                .prop_map(|(first, second, amount1, amount2)| {
+                    let mut cost = ResourceCountArray::default();
+                    cost[first] = amount1;
+                    cost[second] = amount2;
+                    cost
+                });
+            (any_resource(), prop_oneof![single, double])
+                .prop_map(|(bot, cost)| Recipe { bot, cost })
+        }
+
+        fn any_blueprint() -> impl Strategy<Value = Blueprint> {
+            let recipes = [any_recipe(), any_recipe(), any_recipe(), any_recipe()];
+            (any::<u32>(), recipes).prop_map(|(id, recipes)| Blueprint { id, recipes })
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(2000))]
+
+            #[test]
+            fn recipes_round_trip(expected in any_recipe()) {
+                let written = expected.to_string();
+                let (rest, parsed) = recipe(&written).unwrap();
+                prop_assert_eq!(rest, "");
+                prop_assert_eq!(parsed, expected);
+            }
+
+            #[test]
+            fn blueprints_round_trip(expected in prop::collection::vec(any_blueprint(), 0..6)) {
+                let blueprints: Vec<String> = expected.iter().map(Blueprint::to_string).collect();
+                let written = format!("{}\n", blueprints.join("\n"));
+                prop_assert_eq!(parse(&written).unwrap(), expected);
+            }
+        }
+
+        #[test]
+        fn writes_the_example_back_out() {
+            let example = include_str!("../example.txt");
+            let blueprints: Vec<String> = parse(example)
+                .unwrap()
+                .iter()
+                .map(Blueprint::to_string)
+                .collect();
+            assert_eq!(format!("{}\n", blueprints.join("\n")), example);
+        }
+
+        #[test]
+        fn parses_single_cost_recipes() {
+            let (rest, parsed) = recipe("Each clay robot costs 2 ore.").unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(
+                parsed,
+                Recipe {
+                    bot: Clay,
+                    cost: ResourceCountArray([2, 0, 0, 0]),
+                }
+            );
+        }
+
+        #[test]
+        fn parses_two_cost_recipes() {
+            let (_, parsed) = recipe("Each geode robot costs 2 ore and 7 obsidian.").unwrap();
+            assert_eq!(
+                parsed,
+                Recipe {
+                    bot: Geode,
+                    cost: ResourceCountArray([2, 0, 7, 0]),
+                }
+            );
+        }
+
+        #[test]
+        fn parses_one_blueprint_per_line() {
+            let blueprints = parse(include_str!("../example.txt")).unwrap();
+            assert_eq!(blueprints.len(), 2);
+            assert_eq!(blueprints[1].id, 2);
+            assert_eq!(
+                blueprints[1].recipes[2],
+                Recipe {
+                    bot: Obsidian,
+                    cost: ResourceCountArray([3, 8, 0, 0]),
+                }
+            );
+        }
+
+        #[test]
+        fn locates_a_recipe_for_an_unknown_robot() {
+            let example = include_str!("../example.txt").replacen("clay robot", "sand robot", 2);
+            let error = parse(&example).unwrap_err();
+            let error = error.downcast_ref::<ParseError>().unwrap();
+            assert_eq!((error.line, error.column), (1, 47));
+            assert!(error.found.starts_with("sand robot"));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Factory<'a> {
+    blueprint: &'a Blueprint,
+    remaining: u32,
+    bots: ResourceCountArray,
+    stockpile: ResourceCountArray,
+    produced: ResourceCountArray,
+}
+
+/// Factories are the same search state when their `key()`s match, whatever
+/// else they produced along the way.
+impl PartialEq for Factory<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Factory<'_> {}
+
+impl Hash for Factory<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl<'a> Factory<'a> {
+    /// Create a new Factory!
+    fn new(blueprint: &'a Blueprint, time: u32) -> Self {
+        Factory {
+            blueprint,
+            remaining: time,
+            bots: ResourceCountArray([1, 0, 0, 0]),
+            stockpile: Default::default(),
+            produced: Default::default(),
+        }
+    }
+
+    /// Try to produce a bot from the given recipe. Attempts to fast-forward the
+    /// state until the given bot is produced, if it can be. There are a few
+    /// different guard clauses that aim to prevent creating new states that won't
+    /// lead to the optimal solution.
+    fn produce_recipe(&self, recipe: Recipe) -> Option<Factory<'a>> {
+        let Recipe { bot, cost } = recipe;
+
+        // Don't produce this recipe if the factory can't produce enough
+        // resources to build this bot
+        let max_production = (self.bots * self.remaining) + self.stockpile;
+        if zip(cost, max_production).any(|(lhs, rhs)| lhs > rhs) {
+            return None;
+        }
+
+        // If there's only one turn left, then skip it. This factory won't
+        // produce anything else.
+        if self.remaining == 1 {
+            return None;
+        }
+
+        // If this factory could have produced the current recipe in its last
+        // incarnation, then it should have produced that bot back then. Too
+        // late, now!
+        let last_turn_resources = self.stockpile.saturating_sub(self.bots);
+        if zip(cost, last_turn_resources)
+            .filter(|(lhs, _)| *lhs > 0)
+            .all(|(lhs, rhs)| lhs < rhs)
+        {
+            return None;
+        }
+
+        // If we're actually going to produce this bot, we need to advance the
+        // current Factory state minute-by-minute until we've gathered enough
+        // resources to produce the bot. Then we pay the price, produce the bot,
+        // and return the state.
+        let mut new_state = *self;
+        while new_state.remaining > 0 && self.bots == new_state.bots {
+            let available = new_state.stockpile;
+            new_state.remaining -= 1;
+            new_state.stockpile += new_state.bots;
+            new_state.produced += new_state.bots;
+
+            if zip(available, cost).all(|(lhs, rhs)| lhs >= rhs) {
+                new_state.bots[bot] += 1;
+                new_state.stockpile -= cost;
+            }
+        }
+
+        Some(new_state)
+    }
+
+    /// Identify all the next states that can be reached from the current Factory.
+    /// Tries to produce one of each bot and includes a "wait" state where the
+    /// Factory just lets time run out. This is for cases when not enough resources
+    /// will be generated to produce any more bots before time runs out.
+    fn next_states(&self) -> impl Iterator<Item = Factory<'a>> + '_ {
+        let mut wait_state = *self;
+        wait_state.stockpile += self.bots * self.remaining;
+        wait_state.produced += self.bots * self.remaining;
+        wait_state.remaining = 0;
+
+        let wait_state_iter = std::iter::once(wait_state);
+
+        self.blueprint
+            .recipes
+            .into_iter()
+            .flat_map(|recipe| self.produce_recipe(recipe))
+            .chain(wait_state_iter)
+    }
+
+    /// Identify the most possible resources of a given type that could be
+    /// produced under ideal circumstances.
+    fn best_estimate(&self, resource: Resource) -> u32 {
+        // Assume that we can make one new bot per minute for
+        // the remaining time. In that perfect scenario, how many
+        // `resource` would we have a the end of time?
+        // This always _overestimates_, and never goes up from one state to
+        // the next, which is what lets the search below use it as a cost.
+        let resource_bots = self.bots[resource] + self.remaining;
+        let new_resources = resource_bots * self.remaining;
+        new_resources + self.produced[resource]
+    }
+
+    /// This is our unique identifier for a given Factory: two factories with
+    /// the same time left, bots and stockpile will always end up with the same
+    /// number of geodes.
+    fn key(&self) -> (u32, ResourceCountArray, ResourceCountArray) {
+        (self.remaining, self.bots, self.stockpile)
+    }
+
+    /// Searches through the possible Factory states for the one that produces
+    /// the most geodes. Each step costs however much it lowers the best
+    /// estimate, so the cheapest state where time has run out is the one
+    /// that ends with the most geodes.
+    fn geodes_produced(&self) -> u32 {
+        let _span = debug_span!(
+            "blueprint",
+            id = self.blueprint.id,
+            minutes = self.remaining
+        )
+        .entered();
+        let most_geodes = Cell::new(0); // Used for optimization
+        let pruned = Cell::new(0u64);
+
+        let steps = |state: &Factory<'a>| {
+            // Update the most geodes produced by any state seen so far.
+            most_geodes.set(most_geodes.get().max(state.produced[Geode]));
+            let estimate = state.best_estimate(Geode);
+
+            state
+                .next_states()
+                // If the best possible geode production for this state is
+                // still less than the most geodes we've actually seen in a
+                // state so far, skip it. The best estimate is an overestimate
+                // by design.
+                .filter(|next_state| {
+                    let promising = next_state.best_estimate(Geode) >= most_geodes.get();
+                    if !promising {
+                        pruned.set(pruned.get() + 1);
+                    }
+                    promising
+                })
+                .map(|next_state| (next_state, estimate - next_state.best_estimate(Geode)))
+                .collect::<Vec<_>>()
+        };
+
+        let search = search::dijkstra(&steps, *self, |state| state.remaining == 0);
+        let finished = search.goal().expect("time always runs out eventually");
+        debug!(
+            geodes = finished.stockpile[Geode],
+            pruned = pruned.get(),
+            "found the most geodes"
+        );
+        finished.stockpile[Geode]
+    }
+
+    /// Calcualate the quality level of this Factory
+    fn quality_level(&self) -> u32 {
+        self.blueprint.id * self.geodes_produced()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn finds_the_most_geodes_for_each_blueprint() {
+        let blueprints = Day19.parse(include_str!("../example.txt")).unwrap();
+        let geodes = blueprints
+            .iter()
+            .map(|blueprint| Factory::new(blueprint, 24).geodes_produced())
+            .collect::<Vec<_>>();
+        assert_eq!(geodes, vec![9, 12]);
+    }
+}
@@ -0,0 +1,16 @@
+use common::Solution;
+use day19::Day19;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day19.parse(EXAMPLE).unwrap();
+    assert_eq!(Day19.part1(&input).unwrap(), 33);
+}
+
+#[test]
+fn part2() {
+    let input = Day19.parse(EXAMPLE).unwrap();
+    assert_eq!(Day19.part2(&input).unwrap(), 3472);
+}
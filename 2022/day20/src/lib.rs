@@ -0,0 +1,188 @@
+use anyhow::{anyhow, ensure, Result};
+use common::Solution;
+
+common::configure! {
+    /// How part 2 decrypts the sequence, which is the same for the example
+    /// and the real puzzle.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Day20 {
+        /// What every number is multiplied by before mixing in part 2.
+        pub key: i64,
+        /// How many times the numbers are mixed in part 2.
+        pub rounds: usize,
+    }
+    presets: ["puzzle" => Day20::PUZZLE]
+}
+
+impl Day20 {
+    pub const PUZZLE: Day20 = Day20 {
+        key: 811_589_153,
+        rounds: 10,
+    };
+}
+
+impl Solution for Day20 {
+    /// The encrypted sequence of numbers.
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(&self, s: &str) -> Result<Self::Input> {
+        let sequence = common::parse_lines(s.trim_end(), "a number", |l| l.parse().ok())?;
+        Ok(sequence)
+    }
+
+    fn part1(&self, sequence: &Self::Input) -> Result<i64> {
+        decrypt(sequence, 1, 1)
+    }
+
+    fn part2(&self, sequence: &Self::Input) -> Result<i64> {
+        decrypt(sequence, self.key, self.rounds)
+    }
+}
+
+#[derive(Clone)]
+struct Number {
+    original_index: usize,
+    move_by: i64,
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.original_index == other.original_index
+    }
+}
+
+fn decrypt(numbers: &[i64], decryption_key: i64, iterations: usize) -> Result<i64> {
+    // a lone number has nowhere to move to
+    ensure!(
+        numbers.len() >= 2,
+        "Expected at least two numbers, found {}",
+        numbers.len()
+    );
+
+    // assign the index of each number in the input and multiply by the decryption key
+    let mut sequence: Vec<Number> = numbers
+        .iter()
+        .enumerate()
+        .map(|(original_index, &move_by)| {
+            let move_by = move_by.checked_mul(decryption_key).ok_or_else(|| {
+                anyhow!("{move_by} times the decryption key {decryption_key} is too big")
+            })?;
+            Ok(Number {
+                original_index,
+                move_by,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    let len = sequence.len();
+
+    for _ in 0..iterations {
+        for i in 0..len {
+            let index = sequence
+                .iter()
+                .position(|num| num.original_index == i)
+                .unwrap();
+            let offset = sequence[index].move_by;
+            let shifted = shift_element(&sequence, index, offset);
+            sequence = shifted;
+        }
+    }
+
+    // find 0
+    let index_zero = sequence
+        .iter()
+        .position(|num| num.move_by == 0)
+        .ok_or_else(|| anyhow!("The sequence has no 0 to find the coordinates from"))?;
+
+    [1000, 2000, 3000]
+        .iter()
+        .try_fold(0i64, |sum, after| {
+            sum.checked_add(sequence[(index_zero + after) % len].move_by)
+        })
+        .ok_or_else(|| anyhow!("The grove coordinates add up to more than fits in an i64"))
+}
+
+// this is a functional way to move an element in a cycle to some other place in the cycle.
+// it wastes a bit of space but it's conceptually easier to understand I think
+fn shift_element<T>(vec: &[T], index: usize, offset: i64) -> Vec<T>
+where
+    T: Clone + PartialEq,
+{
+    let len = vec.len() as i64;
+
+    // line up three copies of the vector
+    let tripled = [vec, vec, vec].concat();
+
+    // wrap the offset to somewhere in our tripled vector
+    let offset = (offset % (len - 1) + len) % len + if offset > 0 { 1 } else { 0 };
+
+    [
+        // put the indexed value at the front of the new vector
+        vec![vec[index].clone()],
+        // pull the rest of the cycle, making sure to filter out the one we put at the front
+        tripled
+            .into_iter()
+            .skip(index + offset as usize)
+            .filter(|el| *el != vec[index])
+            .take(vec.len() - 1)
+            .collect(),
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rotate a cycle so it starts at `first`, to compare cycles that were
+    /// shifted by different amounts.
+    fn starting_at(cycle: Vec<i64>, first: i64) -> Vec<i64> {
+        let start = cycle.iter().position(|&n| n == first).unwrap();
+        [&cycle[start..], &cycle[..start]].concat()
+    }
+
+    #[test]
+    fn shifts_forwards_past_the_next_number() {
+        let shifted = shift_element(&[1, 2, -3, 3, -2, 0, 4], 0, 1);
+        assert_eq!(starting_at(shifted, 2), vec![2, 1, -3, 3, -2, 0, 4]);
+    }
+
+    #[test]
+    fn shifts_backwards_around_the_cycle() {
+        let shifted = shift_element(&[4, -2, 5, 6, 7, 8, 9], 1, -2);
+        assert_eq!(starting_at(shifted, 4), vec![4, 5, 6, 7, 8, -2, 9]);
+    }
+
+    #[test]
+    fn decrypts_the_grove_coordinates() {
+        assert_eq!(decrypt(&[1, 2, -3, 3, -2, 0, 4], 1, 1).unwrap(), 3);
+        assert_eq!(
+            decrypt(&[1, 2, -3, 3, -2, 0, 4], 811589153, 10).unwrap(),
+            1623178306
+        );
+    }
+
+    #[test]
+    fn fails_without_a_zero() {
+        assert!(decrypt(&[1, 2, -3], 1, 1).is_err());
+        assert!(decrypt(&[0], 1, 1).is_err());
+    }
+
+    #[test]
+    fn fails_instead_of_overflowing() {
+        let error = decrypt(&[1, 2, -3, 3, -2, 0, 4], i64::MAX, 1).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "2 times the decryption key 9223372036854775807 is too big"
+        );
+        assert!(decrypt(&[0, i64::MAX, i64::MAX], 1, 0).is_err());
+
+        let error = Day20::PUZZLE.parse("1\n2\nthree\n").unwrap_err();
+        let error = error.downcast_ref::<common::ParseError>().unwrap();
+        assert_eq!((error.line, error.found.as_str()), (3, "three"));
+    }
+}
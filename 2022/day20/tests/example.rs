@@ -0,0 +1,16 @@
+use common::Solution;
+use day20::Day20;
+
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[test]
+fn part1() {
+    let input = Day20::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day20::PUZZLE.part1(&input).unwrap(), 3);
+}
+
+#[test]
+fn part2() {
+    let input = Day20::PUZZLE.parse(EXAMPLE).unwrap();
+    assert_eq!(Day20::PUZZLE.part2(&input).unwrap(), 1623178306);
+}
@@ -0,0 +1,229 @@
+//! Pictures of puzzle states for write-ups.
+//!
+//! An [`Image`] is a set of [`Shape`]s in puzzle units, like the cells of a
+//! map or the corners of a sensor's range. It can be written as an SVG, or
+//! rasterized into a [`Raster`] of pixels and written as a PNG. A sequence of
+//! rasters can be written as an animated GIF. Everything is encoded in pure
+//! Rust, so exporting works headless, with no system libraries.
+//!
+//! Days with a picture worth exporting implement [`Export`].
+
+use anyhow::{bail, Context, Result};
+use common::Solution;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+mod raster;
+mod svg;
+
+pub use raster::{write_gif, Raster};
+
+/// A colour, as red, green and blue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// The colour `opacity` of the way from this one to `other`.
+    pub fn blend(self, other: Rgb, opacity: f64) -> Rgb {
+        let mix = |under: u8, over: u8| {
+            (under as f64 + (over as f64 - under as f64) * opacity).round() as u8
+        };
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+
+    /// The colour as an HTML hex code, like `#0f0f23`.
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// The colours of the Advent of Code site, so the pictures match the puzzles.
+pub mod colours {
+    use super::Rgb;
+
+    pub const BACKGROUND: Rgb = Rgb(0x0f, 0x0f, 0x23);
+    pub const DIM: Rgb = Rgb(0x33, 0x33, 0x40);
+    pub const TEXT: Rgb = Rgb(0xcc, 0xcc, 0xcc);
+    pub const SILVER: Rgb = Rgb(0x99, 0x99, 0xcc);
+    pub const GOLD: Rgb = Rgb(0xff, 0xff, 0x66);
+    pub const GREEN: Rgb = Rgb(0x00, 0x99, 0x00);
+    pub const BRIGHT_GREEN: Rgb = Rgb(0x00, 0xcc, 0x00);
+    pub const RED: Rgb = Rgb(0xff, 0x00, 0x00);
+}
+
+/// Something to draw, in puzzle units.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// A filled rectangle, from its top left corner.
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        fill: Rgb,
+    },
+    /// A filled polygon, drawn with `opacity` over whatever is under it.
+    Polygon {
+        points: Vec<(f64, f64)>,
+        fill: Rgb,
+        opacity: f64,
+    },
+    /// A line through the points.
+    Line {
+        points: Vec<(f64, f64)>,
+        stroke: Rgb,
+        width: f64,
+    },
+}
+
+/// A picture, `width` by `height` puzzle units, with `y` growing downwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: f64,
+    pub height: f64,
+    pub background: Rgb,
+    pub shapes: Vec<Shape>,
+}
+
+impl Image {
+    pub fn new(width: f64, height: f64, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            background,
+            shapes: Vec::new(),
+        }
+    }
+
+    /// A grid of unit cells, coloured by `colour`, or left as the background
+    /// where it gives `None`. Runs of the same colour along a row are drawn
+    /// as one rectangle, to keep SVGs small.
+    pub fn grid(
+        cols: usize,
+        rows: usize,
+        background: Rgb,
+        colour: impl Fn(usize, usize) -> Option<Rgb>,
+    ) -> Self {
+        let mut image = Image::new(cols as f64, rows as f64, background);
+        for row in 0..rows {
+            let mut col = 0;
+            while col < cols {
+                let fill = colour(col, row);
+                let run = (col..cols).take_while(|&c| colour(c, row) == fill).count();
+                if let Some(fill) = fill {
+                    image.push(Shape::Rect {
+                        x: col as f64,
+                        y: row as f64,
+                        width: run as f64,
+                        height: 1.0,
+                        fill,
+                    });
+                }
+                col += run;
+            }
+        }
+        image
+    }
+
+    pub fn push(&mut self, shape: Shape) {
+        self.shapes.push(shape);
+    }
+
+    /// Pixels per unit for a picture about 800 pixels wide, unless that
+    /// would make it more than 12000 tall: a whole number for small pictures,
+    /// so cells stay square, and a fraction for big ones.
+    pub fn default_scale(&self) -> f64 {
+        let scale = (800.0 / self.width.max(1.0)).min(12000.0 / self.height.max(1.0));
+        if scale >= 1.0 {
+            scale.floor()
+        } else {
+            scale
+        }
+    }
+
+    /// Write the picture to `path`, as a PNG or an SVG by its extension,
+    /// `scale` pixels to the unit.
+    pub fn save(&self, path: &Path, scale: f64) -> Result<()> {
+        let file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        let mut out = BufWriter::new(file);
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => self.rasterize(scale)?.write_png(&mut out),
+            Some("svg") => self.write_svg(&mut out, scale),
+            _ => bail!(
+                "Pictures can be saved as .png or .svg, not {}",
+                path.display()
+            ),
+        }
+        .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// The colour to draw a character of an animation frame in, so frames can be
+/// turned into GIFs: rock as silver, sand and falling rock as gold, heads and
+/// falling grains as green, walls and brackets dimmed, and text as text.
+pub fn glyph_colour(glyph: char) -> Rgb {
+    match glyph {
+        ' ' | '.' => colours::BACKGROUND,
+        '#' => colours::SILVER,
+        'o' | '@' => colours::GOLD,
+        '+' | 'H' => colours::BRIGHT_GREEN,
+        c if c.is_alphanumeric() => colours::TEXT,
+        _ => colours::DIM,
+    }
+}
+
+/// A solution with a picture of the puzzle to export.
+pub trait Export: Solution {
+    /// A picture of the puzzle as the solution leaves it, like the finished
+    /// screen or the route it found.
+    fn picture(&self, input: &Self::Input) -> Result<Image>;
+}
+
+/// An object-safe view of an `Export`, like `DynSolution`.
+pub trait DynExport {
+    /// Parse `input` with [`common::parse_tidied`] and draw the picture of
+    /// it.
+    fn picture(&self, input: &str) -> Result<Image>;
+}
+
+impl<S: Export> DynExport for S {
+    fn picture(&self, input: &str) -> Result<Image> {
+        let input = common::parse_tidied(self, input)?;
+        Export::picture(self, &input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grids_merge_runs_of_a_colour() {
+        let cells = ["##.#", "...."];
+        let image = Image::grid(4, 2, colours::BACKGROUND, |col, row| {
+            (cells[row].as_bytes()[col] == b'#').then_some(colours::GOLD)
+        });
+        let rect = |x: f64, width: f64| Shape::Rect {
+            x,
+            y: 0.0,
+            width,
+            height: 1.0,
+            fill: colours::GOLD,
+        };
+        assert_eq!(image.shapes, vec![rect(0.0, 2.0), rect(3.0, 1.0)]);
+        assert_eq!(image.default_scale(), 200.0);
+    }
+
+    #[test]
+    fn colours_blend_and_print_as_hex() {
+        let grey = Rgb(0, 0, 0).blend(Rgb(255, 255, 255), 0.5);
+        assert_eq!(grey, Rgb(128, 128, 128));
+        assert_eq!(colours::BACKGROUND.hex(), "#0f0f23");
+    }
+}
@@ -0,0 +1,353 @@
+use anyhow::{bail, ensure, Result};
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::Duration;
+
+use crate::{Image, Rgb, Shape};
+
+/// The most pixels across or down a raster can be, as for a GIF.
+pub const MAX_SIDE: usize = u16::MAX as usize;
+
+/// The most pixels a raster can have in all, which at three bytes each is
+/// 768 MiB.
+pub const MAX_PIXELS: usize = 1 << 28;
+
+/// A picture as pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Raster {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Raster {
+    /// A raster filled with `background`, as long as it's no bigger than
+    /// [`MAX_SIDE`] a side and [`MAX_PIXELS`] in all.
+    pub fn new(width: usize, height: usize, background: Rgb) -> Result<Self> {
+        ensure!(
+            width <= MAX_SIDE && height <= MAX_SIDE && width * height <= MAX_PIXELS,
+            "A {width}x{height} picture is too big to draw: it can be at most {MAX_SIDE} \
+             pixels across and {MAX_PIXELS} in all, so try a smaller scale"
+        );
+        Ok(Raster {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// A block of `scale` by `scale` pixels for each character of `rows`,
+    /// coloured by `colour`, on a canvas of `size` characters (so pictures of
+    /// different sizes line up as frames). Missing characters are drawn as
+    /// spaces.
+    pub fn from_chars(
+        rows: &[Vec<char>],
+        size: (usize, usize),
+        scale: usize,
+        colour: impl Fn(char) -> Rgb,
+    ) -> Result<Raster> {
+        let (cols, lines) = size;
+        let (width, height) = (cols.saturating_mul(scale), lines.saturating_mul(scale));
+        let mut raster = Raster::new(width, height, colour(' '))?;
+        for (row, line) in rows.iter().enumerate().take(lines) {
+            for (col, &glyph) in line.iter().enumerate().take(cols) {
+                let fill = colour(glyph);
+                for y in row * scale..(row + 1) * scale {
+                    for x in col * scale..(col + 1) * scale {
+                        raster.set(x, y, fill);
+                    }
+                }
+            }
+        }
+        Ok(raster)
+    }
+
+    /// Write the pixels as an 8-bit RGB PNG.
+    pub fn write_png(&self, out: &mut impl Write) -> Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let data: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|&Rgb(r, g, b)| [r, g, b])
+            .collect();
+        encoder.write_header()?.write_image_data(&data)?;
+        Ok(())
+    }
+}
+
+impl Image {
+    /// Draw the picture as pixels, `scale` to the unit. Each pixel takes the
+    /// colour of whatever covers its centre. Fails if that's more pixels than
+    /// a `Raster` can have.
+    pub fn rasterize(&self, scale: f64) -> Result<Raster> {
+        let width = (self.width * scale).ceil().max(1.0) as usize;
+        let height = (self.height * scale).ceil().max(1.0) as usize;
+        let mut raster = Raster::new(width, height, self.background)?;
+        // The centre of a pixel, in puzzle units
+        let centre = |pixel: usize| (pixel as f64 + 0.5) / scale;
+        // The pixels whose centres lie in `from..to` units along an axis
+        let span = |from: f64, to: f64, len: usize| {
+            let first = (from * scale - 0.5).ceil().max(0.0) as usize;
+            let end = ((to * scale - 0.5).ceil().max(0.0) as usize).min(len);
+            first..end
+        };
+
+        for shape in &self.shapes {
+            match shape {
+                &Shape::Rect {
+                    x,
+                    y,
+                    width: w,
+                    height: h,
+                    fill,
+                } => {
+                    for py in span(y, y + h, height) {
+                        for px in span(x, x + w, width) {
+                            raster.set(px, py, fill);
+                        }
+                    }
+                }
+                Shape::Polygon {
+                    points,
+                    fill,
+                    opacity,
+                } => {
+                    for py in 0..height {
+                        let y = centre(py);
+                        // Where the row crosses the edges; inside is between
+                        // each pair of crossings
+                        let mut crossings: Vec<f64> = points
+                            .iter()
+                            .zip(points.iter().cycle().skip(1))
+                            .filter(|(a, b)| (a.1 <= y) != (b.1 <= y))
+                            .map(|(a, b)| a.0 + (y - a.1) * (b.0 - a.0) / (b.1 - a.1))
+                            .collect();
+                        crossings.sort_by(f64::total_cmp);
+                        for pair in crossings.chunks_exact(2) {
+                            for px in span(pair[0], pair[1], width) {
+                                let under = raster.get(px, py);
+                                raster.set(px, py, under.blend(*fill, *opacity));
+                            }
+                        }
+                    }
+                }
+                Shape::Line {
+                    points,
+                    stroke,
+                    width: w,
+                } => {
+                    // However thin the line, it's at least a pixel wide
+                    let reach = (w / 2.0).max(0.5 / scale);
+                    // Pixels a pixel past the reach are checked too, since
+                    // the ones right on the edge count
+                    let edge = reach + 1.0 / scale;
+                    for (a, b) in points.iter().zip(points.iter().skip(1)) {
+                        let ys = span(a.1.min(b.1) - edge, a.1.max(b.1) + edge, height);
+                        for py in ys {
+                            let xs = span(a.0.min(b.0) - edge, a.0.max(b.0) + edge, width);
+                            for px in xs {
+                                if distance((centre(px), centre(py)), *a, *b) <= reach {
+                                    raster.set(px, py, *stroke);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(raster)
+    }
+}
+
+/// How far `point` is from the segment from `a` to `b`.
+fn distance(point: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    let t = if length == 0.0 {
+        0.0
+    } else {
+        (((point.0 - a.0) * dx + (point.1 - a.1) * dy) / length).clamp(0.0, 1.0)
+    };
+    let (x, y) = (a.0 + t * dx, a.1 + t * dy);
+    ((point.0 - x).powi(2) + (point.1 - y).powi(2)).sqrt()
+}
+
+/// Write `frames` as an animated GIF that loops forever, showing each one for
+/// `delay`. The frames must be the same size, and each can use no more than
+/// 256 colours. Every frame carries its own palette, so they can be drawn
+/// and written one at a time, without holding them all at once.
+pub fn write_gif(
+    frames: impl IntoIterator<Item = Raster>,
+    delay: Duration,
+    out: impl Write,
+) -> Result<()> {
+    let mut frames = frames.into_iter().peekable();
+    let Some(first) = frames.peek() else {
+        bail!("There are no frames to make a GIF of");
+    };
+    let (width, height) = (first.width, first.height);
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        bail!("{width}x{height} is too big for a GIF, which can be at most 65535 pixels across");
+    };
+
+    let mut encoder = gif::Encoder::new(out, gif_width, gif_height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    // GIF delays are in hundredths of a second
+    let delay = (delay.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+    for (n, raster) in frames.enumerate() {
+        ensure!(
+            (raster.width, raster.height) == (width, height),
+            "The frames of a GIF must all be the same size, but frame {} is {}x{} and the first is {width}x{height}",
+            n + 1,
+            raster.width,
+            raster.height
+        );
+        let (palette, indices) = raster.indexed()?;
+        let mut frame = gif::Frame::from_indexed_pixels(gif_width, gif_height, indices, None);
+        frame.palette = Some(palette);
+        frame.delay = delay;
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
+impl Raster {
+    /// The pixels as indices into a palette of the colours they use, along
+    /// with the palette as bytes of red, green and blue.
+    fn indexed(&self) -> Result<(Vec<u8>, Vec<u8>)> {
+        let mut palette: Vec<u8> = Vec::new();
+        let mut index: HashMap<Rgb, u8> = HashMap::new();
+        let mut indices = Vec::with_capacity(self.pixels.len());
+        for &pixel in &self.pixels {
+            let i = match index.get(&pixel) {
+                Some(&i) => i,
+                None => {
+                    let Ok(next) = u8::try_from(index.len()) else {
+                        bail!("A frame uses more than the 256 colours a GIF can have");
+                    };
+                    index.insert(pixel, next);
+                    palette.extend([pixel.0, pixel.1, pixel.2]);
+                    next
+                }
+            };
+            indices.push(i);
+        }
+        Ok((palette, indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colours::{BACKGROUND, GOLD, GREEN, RED};
+
+    #[test]
+    fn rasterizes_rects_polygons_and_lines() {
+        let mut image = Image::new(4.0, 4.0, BACKGROUND);
+        image.push(Shape::Rect {
+            x: 1.0,
+            y: 1.0,
+            width: 2.0,
+            height: 1.0,
+            fill: GOLD,
+        });
+        image.push(Shape::Polygon {
+            points: vec![(0.0, 2.0), (4.0, 2.0), (4.0, 4.0), (0.0, 4.0)],
+            fill: RED,
+            opacity: 1.0,
+        });
+        image.push(Shape::Line {
+            points: vec![(0.5, 3.5), (3.5, 3.5)],
+            stroke: GREEN,
+            width: 0.5,
+        });
+
+        // Two pixels to the unit
+        let raster = image.rasterize(2.0).unwrap();
+        assert_eq!((raster.width, raster.height), (8, 8));
+        let rows: Vec<String> = (0..8)
+            .map(|y| {
+                (0..8)
+                    .map(|x| match raster.get(x, y) {
+                        BACKGROUND => '.',
+                        GOLD => 'o',
+                        RED => 'r',
+                        GREEN => 'g',
+                        _ => '?',
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                "........", "........", "..oooo..", "..oooo..", "rrrrrrrr", "rrrrrrrr", "rggggggr",
+                "rggggggr",
+            ]
+        );
+    }
+
+    #[test]
+    fn blends_translucent_polygons() {
+        let mut image = Image::new(1.0, 1.0, Rgb(0, 0, 0));
+        let square = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        for _ in 0..2 {
+            image.push(Shape::Polygon {
+                points: square.clone(),
+                fill: Rgb(200, 200, 200),
+                opacity: 0.5,
+            });
+        }
+        assert_eq!(image.rasterize(1.0).unwrap().get(0, 0), Rgb(150, 150, 150));
+    }
+
+    #[test]
+    fn encodes_pngs_and_gifs() {
+        let raster = Raster::from_chars(&[vec!['#', '.'], vec!['.']], (2, 2), 3, |glyph| {
+            if glyph == '#' {
+                GOLD
+            } else {
+                BACKGROUND
+            }
+        })
+        .unwrap();
+        assert_eq!((raster.width, raster.height), (6, 6));
+        assert_eq!((raster.get(2, 2), raster.get(3, 3)), (GOLD, BACKGROUND));
+
+        let mut png = Vec::new();
+        raster.write_png(&mut png).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let mut gif = Vec::new();
+        let frames = [raster.clone(), Raster::new(6, 6, RED).unwrap()];
+        write_gif(frames, Duration::from_millis(100), &mut gif).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+
+        let (palette, indices) = raster.indexed().unwrap();
+        assert_eq!(palette, [0xff, 0xff, 0x66, 0x0f, 0x0f, 0x23]);
+        assert_eq!(&indices[..4], [0, 0, 0, 1]);
+
+        let mismatched = [raster, Raster::new(1, 1, RED).unwrap()];
+        assert!(write_gif(mismatched, Duration::ZERO, Vec::new()).is_err());
+        assert!(write_gif([], Duration::ZERO, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn refuses_to_draw_too_many_pixels() {
+        let image = Image::new(40.0, 1.0, BACKGROUND);
+        assert!(image.rasterize(1e9).is_err());
+        assert!(image.rasterize(MAX_SIDE as f64 / 40.0).is_ok());
+        assert!(Raster::from_chars(&[vec!['#']], (40, 6), usize::MAX, |_| GOLD).is_err());
+        assert!(Raster::new(MAX_SIDE, MAX_SIDE, GOLD).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::io::Write;
+
+use crate::{Image, Shape};
+
+impl Image {
+    /// The picture as an SVG, in puzzle units, sized to `scale` pixels to the
+    /// unit.
+    pub fn to_svg(&self, scale: f64) -> String {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+            self.width * scale,
+            self.height * scale,
+            self.width,
+            self.height
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            self.background.hex()
+        );
+        for shape in &self.shapes {
+            let _ = match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    fill,
+                } => writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{}"/>"#,
+                    fill.hex()
+                ),
+                Shape::Polygon {
+                    points,
+                    fill,
+                    opacity,
+                } => writeln!(
+                    svg,
+                    r#"<polygon points="{}" fill="{}" fill-opacity="{opacity}"/>"#,
+                    points_list(points),
+                    fill.hex()
+                ),
+                Shape::Line {
+                    points,
+                    stroke,
+                    width,
+                } => writeln!(
+                    svg,
+                    r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{width}" stroke-linejoin="round" stroke-linecap="round"/>"#,
+                    points_list(points),
+                    stroke.hex()
+                ),
+            };
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn write_svg(&self, out: &mut impl Write, scale: f64) -> Result<()> {
+        out.write_all(self.to_svg(scale).as_bytes())?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+fn points_list(points: &[(f64, f64)]) -> String {
+    let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+    points.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{colours, Image, Shape};
+
+    #[test]
+    fn writes_shapes_in_puzzle_units() {
+        let mut image = Image::new(20.0, 10.0, colours::BACKGROUND);
+        image.push(Shape::Polygon {
+            points: vec![(5.0, 0.0), (10.0, 5.0), (5.0, 10.0), (0.0, 5.0)],
+            fill: colours::GREEN,
+            opacity: 0.5,
+        });
+        image.push(Shape::Line {
+            points: vec![(0.5, 0.5), (3.5, 0.5)],
+            stroke: colours::GOLD,
+            width: 0.25,
+        });
+
+        let svg = image.to_svg(4.0);
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="80" height="40" viewBox="0 0 20 10""#
+        ));
+        assert!(svg.contains(
+            r##"<polygon points="5,0 10,5 5,10 0,5" fill="#009900" fill-opacity="0.5"/>"##
+        ));
+        assert!(svg.contains(r##"<polyline points="0.5,0.5 3.5,0.5" fill="none" stroke="#ffff66" stroke-width="0.25""##));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,454 @@
+//! Points and vectors for the puzzles that move things around in two or three
+//! dimensions.
+//!
+//! In two dimensions `y` grows downwards, the way puzzle maps are drawn, so
+//! [`Dir4::Up`] is a step of `y - 1`.
+
+use num_traits::{One, Signed, Zero};
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// The four directions along the axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// The eight directions along the axes and diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Down, Dir4::Left, Dir4::Right];
+
+    /// A step of one in this direction.
+    pub fn step<T: Signed>(self) -> Vec2<T> {
+        match self {
+            Dir4::Up => Vec2::new(T::zero(), -T::one()),
+            Dir4::Down => Vec2::new(T::zero(), T::one()),
+            Dir4::Left => Vec2::new(-T::one(), T::zero()),
+            Dir4::Right => Vec2::new(T::one(), T::zero()),
+        }
+    }
+
+    pub fn opposite(self) -> Dir4 {
+        match self {
+            Dir4::Up => Dir4::Down,
+            Dir4::Down => Dir4::Up,
+            Dir4::Left => Dir4::Right,
+            Dir4::Right => Dir4::Left,
+        }
+    }
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// A step of one in this direction, moving along both axes for the
+    /// diagonals.
+    pub fn step<T: Signed + Copy>(self) -> Vec2<T> {
+        let (up, down, left, right) = (
+            Dir4::Up.step(),
+            Dir4::Down.step(),
+            Dir4::Left.step(),
+            Dir4::Right.step(),
+        );
+        match self {
+            Dir8::Up => up,
+            Dir8::UpRight => up + right,
+            Dir8::Right => right,
+            Dir8::DownRight => down + right,
+            Dir8::Down => down,
+            Dir8::DownLeft => down + left,
+            Dir8::Left => left,
+            Dir8::UpLeft => up + left,
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Dir8::Up,
+            Dir4::Down => Dir8::Down,
+            Dir4::Left => Dir8::Left,
+            Dir4::Right => Dir8::Right,
+        }
+    }
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T: Copy> Vec2<T> {
+    /// Apply `f` to each coordinate.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Vec2<U> {
+        Vec2::new(f(self.x), f(self.y))
+    }
+
+    /// Combine the coordinates of two vectors pairwise with `f`.
+    pub fn zip_with<U, V>(self, other: Vec2<U>, mut f: impl FnMut(T, U) -> V) -> Vec2<V> {
+        Vec2::new(f(self.x, other.x), f(self.y, other.y))
+    }
+
+    /// Clamp each coordinate between the coordinates of `min` and `max`.
+    pub fn clamp(self, min: Self, max: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        let clamp = |value: T, min: T, max: T| {
+            if value < min {
+                min
+            } else if value > max {
+                max
+            } else {
+                value
+            }
+        };
+        Vec2::new(clamp(self.x, min.x, max.x), clamp(self.y, min.y, max.y))
+    }
+}
+
+impl<T: Signed + Copy> Vec2<T> {
+    pub fn abs(self) -> Self {
+        self.map(|n| n.abs())
+    }
+
+    /// The sign of each coordinate: a step of at most one along each axis
+    /// that heads the same way as this vector.
+    pub fn signum(self) -> Self {
+        self.map(|n| n.signum())
+    }
+
+    /// The distance to `other` moving only along the axes.
+    pub fn manhattan(self, other: Self) -> T {
+        let d = (self - other).abs();
+        d.x + d.y
+    }
+
+    /// The distance to `other` when diagonal moves count as one step, like a
+    /// king in chess.
+    pub fn chebyshev(self, other: Self) -> T
+    where
+        T: PartialOrd,
+    {
+        let d = (self - other).abs();
+        if d.x > d.y {
+            d.x
+        } else {
+            d.y
+        }
+    }
+
+    /// The four points sharing an edge with this one.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL.into_iter().map(move |dir| self + dir.step())
+    }
+
+    /// The eight points sharing an edge or a corner with this one.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |dir| self + dir.step())
+    }
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T: Copy> Vec3<T> {
+    /// Apply `f` to each coordinate.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Vec3<U> {
+        Vec3::new(f(self.x), f(self.y), f(self.z))
+    }
+
+    /// Combine the coordinates of two vectors pairwise with `f`.
+    pub fn zip_with<U, V>(self, other: Vec3<U>, mut f: impl FnMut(T, U) -> V) -> Vec3<V> {
+        Vec3::new(f(self.x, other.x), f(self.y, other.y), f(self.z, other.z))
+    }
+
+    /// Whether every coordinate is between the coordinates of `min` and
+    /// `max`, inclusive.
+    pub fn within(self, min: Self, max: Self) -> bool
+    where
+        T: PartialOrd,
+    {
+        (min.x..=max.x).contains(&self.x)
+            && (min.y..=max.y).contains(&self.y)
+            && (min.z..=max.z).contains(&self.z)
+    }
+}
+
+impl<T: Signed + Copy> Vec3<T> {
+    pub fn abs(self) -> Self {
+        self.map(|n| n.abs())
+    }
+
+    pub fn signum(self) -> Self {
+        self.map(|n| n.signum())
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        let d = (self - other).abs();
+        d.x + d.y + d.z
+    }
+
+    /// Steps of one along each axis, in both directions.
+    pub fn faces() -> [Self; 6] {
+        let (zero, one) = (T::zero(), T::one());
+        [
+            Vec3::new(one, zero, zero),
+            Vec3::new(-one, zero, zero),
+            Vec3::new(zero, one, zero),
+            Vec3::new(zero, -one, zero),
+            Vec3::new(zero, zero, one),
+            Vec3::new(zero, zero, -one),
+        ]
+    }
+
+    /// The six points sharing a face with this one.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        Self::faces().into_iter().map(move |step| self + step)
+    }
+}
+
+macro_rules! vector_ops {
+    ($vec:ident { $($field:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $vec<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $vec { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $vec<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $vec { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $vec<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $vec { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $vec<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $vec { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $vec<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $vec<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)+
+            }
+        }
+
+        impl<T: Zero + PartialEq> $vec<T> {
+            pub fn zero() -> Self {
+                $vec { $($field: T::zero()),+ }
+            }
+
+            pub fn is_zero(&self) -> bool {
+                $(self.$field == T::zero())&&+
+            }
+        }
+
+        /// Renders the coordinates comma separated, the way the puzzle
+        /// inputs write them.
+        impl<T: Display> Display for $vec<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut separator = "";
+                $(
+                    write!(f, "{separator}{}", self.$field)?;
+                    separator = ",";
+                )+
+                let _ = separator;
+                Ok(())
+            }
+        }
+
+        /// Reads the coordinates back from the way `Display` writes them.
+        impl<T: FromStr> FromStr for $vec<T> {
+            type Err = ParseVecError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let error = || ParseVecError(s.to_string());
+                let mut coordinates = s.split(',');
+                $(let $field = coordinates
+                    .next()
+                    .and_then(|c| c.trim().parse().ok())
+                    .ok_or_else(error)?;)+
+                if coordinates.next().is_some() {
+                    return Err(error());
+                }
+                Ok($vec { $($field),+ })
+            }
+        }
+    };
+}
+
+/// Text that isn't a point's coordinates, comma separated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVecError(String);
+
+impl Display for ParseVecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected comma separated coordinates, found {:?}",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseVecError {}
+
+vector_ops!(Vec2 { x, y });
+vector_ops!(Vec3 { x, y, z });
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(v: Vec2<T>) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl<T: One> Vec2<T> {
+    /// A vector of ones, handy for the corner opposite the origin.
+    pub fn one() -> Self {
+        Vec2::new(T::one(), T::one())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_works_per_coordinate() {
+        let a = Vec2::new(3, -4);
+        let b = Vec2::new(1, 2);
+        assert_eq!(a + b, Vec2::new(4, -2));
+        assert_eq!(a - b, Vec2::new(2, -6));
+        assert_eq!(a * 2, Vec2::new(6, -8));
+        assert_eq!(-a, Vec2::new(-3, 4));
+
+        let mut c = a;
+        c += b;
+        c -= Vec2::new(4, 0);
+        assert_eq!(c, Vec2::new(0, -2));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vec2::new(1, 1);
+        let b = Vec2::new(4, -1);
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 3);
+        assert_eq!(Vec3::new(1, 2, 3).manhattan(Vec3::new(0, 0, 0)), 6);
+    }
+
+    #[test]
+    fn sign_and_clamp() {
+        assert_eq!(Vec2::new(-7, 0).signum(), Vec2::new(-1, 0));
+        let clamped = Vec2::new(-7, 9).clamp(Vec2::new(0, 0), Vec2::new(5, 5));
+        assert_eq!(clamped, Vec2::new(0, 5));
+    }
+
+    #[test]
+    fn directions_step_with_y_down() {
+        assert_eq!(Dir4::Up.step::<i32>(), Vec2::new(0, -1));
+        assert_eq!(Dir8::DownLeft.step::<i32>(), Vec2::new(-1, 1));
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+    }
+
+    #[test]
+    fn neighbours() {
+        let origin = Vec2::<i32>::zero();
+        assert_eq!(origin.neighbours4().count(), 4);
+        assert!(origin.neighbours8().all(|n| n.chebyshev(origin) == 1));
+        assert!(Vec3::new(1, 1, 1)
+            .neighbours6()
+            .all(|n| n.manhattan(Vec3::new(1, 1, 1)) == 1));
+    }
+
+    #[test]
+    fn displays_like_the_puzzle_input() {
+        assert_eq!(Vec2::new(498, 4).to_string(), "498,4");
+        assert_eq!(Vec3::new(2, -1, 5).to_string(), "2,-1,5");
+    }
+
+    #[test]
+    fn parses_what_it_displays() {
+        assert_eq!("498,4".parse(), Ok(Vec2::new(498, 4)));
+        assert_eq!("2, -1, 5".parse(), Ok(Vec3::new(2, -1, 5)));
+        assert!("498".parse::<Vec2<i32>>().is_err());
+        assert!("1,2,3".parse::<Vec2<i32>>().is_err());
+        assert!("1,x".parse::<Vec2<i32>>().is_err());
+    }
+}
@@ -0,0 +1,482 @@
+//! A dense two dimensional grid, for the puzzles that take place on a map.
+//!
+//! Positions are `(row, column)` pairs counted from the top left, and steps
+//! between them are `(row, column)` deltas like the ones in [`ORTHOGONAL`].
+
+use common::ParseError;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+/// A cell in the grid, as `(row, column)`.
+pub type Pos = (usize, usize);
+
+/// A step from one cell to another, as `(row, column)` deltas.
+pub type Step = (isize, isize);
+
+pub const UP: Step = (-1, 0);
+pub const DOWN: Step = (1, 0);
+pub const LEFT: Step = (0, -1);
+pub const RIGHT: Step = (0, 1);
+
+/// Steps to the four cells sharing an edge with a cell.
+pub const ORTHOGONAL: [Step; 4] = [UP, LEFT, DOWN, RIGHT];
+
+/// Steps to the eight cells sharing an edge or a corner with a cell.
+pub const ALL_AROUND: [Step; 8] = [(-1, -1), UP, (-1, 1), LEFT, RIGHT, (1, -1), DOWN, (1, 1)];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Why some text couldn't be read as a grid. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// No cells at all: no lines, or a first line with nothing on it.
+    Empty,
+    /// A line that's a different length from the first one.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character the cell parser didn't accept.
+    Cell {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "expected a grid, found no lines"),
+            ParseGridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} is {found} characters wide, but the grid is {expected} wide"
+            ),
+            ParseGridError::Cell {
+                line,
+                column,
+                found,
+            } => write!(f, "unexpected {found:?} at line {line}, column {column}"),
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+/// Why a grid couldn't be made at the size asked for: it would have no cells,
+/// or more than can be counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeError {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl SizeError {
+    /// Check that a grid `width` by `height` has at least one cell, and that
+    /// its cells can be counted.
+    fn check(width: usize, height: usize) -> Result<usize, SizeError> {
+        width
+            .checked_mul(height)
+            .filter(|&cells| cells > 0)
+            .ok_or(SizeError { width, height })
+    }
+}
+
+impl Display for SizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let SizeError { width, height } = self;
+        if *width == 0 || *height == 0 {
+            write!(f, "a {width}x{height} grid has no cells")
+        } else {
+            write!(f, "a {width}x{height} grid has too many cells")
+        }
+    }
+}
+
+impl Error for SizeError {}
+
+impl ParseGridError {
+    /// The same problem as a [`ParseError`] pointing into `input`, the text
+    /// the grid was parsed from. `cell` describes what each character should
+    /// have been, like "a tree height (0-9)".
+    pub fn locate(&self, input: &str, cell: impl Display) -> ParseError {
+        match *self {
+            ParseGridError::Empty => ParseError::new(1, 1, "", "a grid"),
+            ParseGridError::Ragged { line, expected, .. } => {
+                let text = input.lines().nth(line - 1).unwrap_or_default();
+                ParseError::new(line, 1, text, format!("a row {expected} cells wide"))
+            }
+            ParseGridError::Cell {
+                line,
+                column,
+                found,
+            } => ParseError::new(line, column, &found.to_string(), cell),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid filled with copies of `value`, which has to have at least one
+    /// cell.
+    pub fn new(width: usize, height: usize, value: T) -> Result<Self, SizeError>
+    where
+        T: Clone,
+    {
+        let cells = SizeError::check(width, height)?;
+        Ok(Grid {
+            width,
+            height,
+            cells: vec![value; cells],
+        })
+    }
+
+    /// Build a grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseGridError> {
+        let width = rows.first().ok_or(ParseGridError::Empty)?.len();
+        if width == 0 {
+            return Err(ParseGridError::Empty);
+        }
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (idx, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(ParseGridError::Ragged {
+                    line: idx + 1,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parse a character matrix, one row per line, turning each character
+    /// into a cell with `cell`. Trailing blank lines are ignored.
+    pub fn parse(s: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseGridError> {
+        let rows = s
+            .trim_end_matches(['\n', '\r'])
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(column, c)| {
+                        cell(c).ok_or(ParseGridError::Cell {
+                            line: row + 1,
+                            column: column + 1,
+                            found: c,
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<T>>, _>>()?;
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// The cell one `step` away from `pos`, if it's inside the grid.
+    pub fn step(&self, (row, col): Pos, (dr, dc): Step) -> Option<Pos> {
+        let pos = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell matching `predicate`, row by row.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The positions inside the grid one of `steps` away from `pos`.
+    pub fn neighbours<'a>(&'a self, pos: Pos, steps: &'a [Step]) -> impl Iterator<Item = Pos> + 'a {
+        steps.iter().filter_map(move |&step| self.step(pos, step))
+    }
+
+    /// The up to four positions sharing an edge with `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &ORTHOGONAL)
+    }
+
+    /// The up to eight positions sharing an edge or a corner with `pos`.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &ALL_AROUND)
+    }
+
+    pub fn row(&self, row: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(
+            row < self.height,
+            "row {row} is outside a grid {} high",
+            self.height
+        );
+        self.cells[row * self.width..(row + 1) * self.width].iter()
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(
+            col < self.width,
+            "column {col} is outside a grid {} wide",
+            self.width
+        );
+        self.cells[col..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// The cells met walking from `pos` by `step` until leaving the grid,
+    /// not including `pos` itself.
+    pub fn ray(&self, pos: Pos, step: Step) -> impl Iterator<Item = (Pos, &T)> {
+        std::iter::successors(self.step(pos, step), move |&pos| self.step(pos, step))
+            .map(|pos| (pos, &self[pos]))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Build a grid of the given size, which has to have at least one cell,
+    /// by calling `f` for each position.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut f: impl FnMut(Pos) -> T,
+    ) -> Result<Self, SizeError> {
+        SizeError::check(width, height)?;
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(&mut f)
+            .collect();
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Swap rows and columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(row, col)| {
+            self[(col, row)].clone()
+        })
+        .expect("a grid's turned size has as many cells")
+    }
+
+    /// Turn the grid a quarter turn clockwise.
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(row, col)| {
+            self[(self.height - 1 - col, row)].clone()
+        })
+        .expect("a grid's turned size has as many cells")
+    }
+
+    /// Turn the grid a quarter turn anticlockwise.
+    pub fn rotate_left(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(row, col)| {
+            self[(col, self.width - 1 - row)].clone()
+        })
+        .expect("a grid's turned size has as many cells")
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "position {pos:?} is outside the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside the {width}x{height} grid"))
+    }
+}
+
+/// Renders each cell with its own `Display`, one row per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef\n", Some).unwrap()
+    }
+
+    #[test]
+    fn parses_a_character_matrix() {
+        let grid = letters();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn rejects_ragged_and_unexpected_input() {
+        assert_eq!(
+            Grid::parse("abc\nde", Some),
+            Err(ParseGridError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Grid::parse("12\n3x", |c| c.to_digit(10)),
+            Err(ParseGridError::Cell {
+                line: 2,
+                column: 2,
+                found: 'x'
+            })
+        );
+        assert_eq!(Grid::parse("", Some), Err(ParseGridError::Empty));
+        assert_eq!(Grid::parse("\nab\n", Some), Err(ParseGridError::Empty));
+        assert_eq!(
+            Grid::from_rows(vec![Vec::<char>::new()]),
+            Err(ParseGridError::Empty)
+        );
+    }
+
+    #[test]
+    fn makes_grids_with_cells() {
+        let grid = Grid::from_fn(3, 2, |(row, col)| row * 3 + col).unwrap();
+        assert_eq!(grid.to_string(), "012\n345");
+        assert_eq!(Grid::new(2, 2, '.').unwrap().to_string(), "..\n..");
+
+        let empty = SizeError {
+            width: 0,
+            height: 3,
+        };
+        assert_eq!(Grid::new(0, 3, '.'), Err(empty));
+        assert_eq!(Grid::from_fn(0, 3, |_| '.'), Err(empty));
+        assert_eq!(empty.to_string(), "a 0x3 grid has no cells");
+        let huge = Grid::from_fn(usize::MAX, 2, |_| '.').unwrap_err();
+        assert_eq!(
+            huge.to_string(),
+            format!("a {}x2 grid has too many cells", usize::MAX)
+        );
+    }
+
+    #[test]
+    fn locates_parse_errors_in_the_input() {
+        let input = "abc\nde\n";
+        let error = Grid::parse(input, Some)
+            .unwrap_err()
+            .locate(input, "a letter");
+        assert_eq!(error, ParseError::new(2, 1, "de", "a row 3 cells wide"));
+    }
+
+    #[test]
+    fn neighbours_stay_inside_the_grid() {
+        let grid = letters();
+        let corner: Vec<Pos> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(corner, vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn walks_rows_columns_and_rays() {
+        let grid = letters();
+        assert_eq!(grid.row(1).collect::<String>(), "def");
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert!(std::panic::catch_unwind(|| grid.column(3).count()).is_err());
+        assert!(std::panic::catch_unwind(|| grid.row(2).count()).is_err());
+        let ray: String = grid.ray((1, 2), LEFT).map(|(_, c)| c).collect();
+        assert_eq!(ray, "ed");
+        assert_eq!(grid.ray((0, 0), UP).count(), 0);
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = letters();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+}
@@ -0,0 +1,251 @@
+//! nom combinators for the shapes puzzle input comes in, so each day's
+//! parser only has to describe its own items: one item per line, blocks of
+//! lines separated by blank lines, comma-separated lists, `Label: value`
+//! fields and `name=value` assignments, numbers among other text and grids
+//! of cells.
+//!
+//! The combinators don't `cut`, so an item that doesn't parse ends the list
+//! it's in and the error lands on the start of it. A day that wants errors
+//! from inside its items, like day 11's monkeys, cuts them itself.
+//! [`finish`] runs a parser over the whole input and turns where it gave up
+//! into a [`ParseError`] at that line and column.
+
+use common::ParseError;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, i64, newline, space0},
+    combinator::{eof, not, recognize, value},
+    error::{Error, ErrorKind},
+    multi::{many0, many1, separated_list0, separated_list1},
+    sequence::{pair, preceded, terminated, tuple},
+    Finish, IResult, Parser,
+};
+use std::fmt::Display;
+
+/// Run `parser` over the whole of `input`, allowing for newlines at the end.
+/// If it can't, the error is where it gave up, or where the input should
+/// have ended, described as `expected`.
+pub fn finish<'a, O>(
+    input: &'a str,
+    expected: impl Display,
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> Result<O, ParseError> {
+    terminated(parser, end)(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|e| ParseError::at(input, e.input, expected))
+}
+
+/// The end of the input, after any number of newlines.
+pub fn end(s: &str) -> IResult<&str, ()> {
+    value((), pair(many0(newline), eof))(s)
+}
+
+/// A newline with more input after it.
+fn line_break(s: &str) -> IResult<&str, char> {
+    terminated(newline, not(end))(s)
+}
+
+/// One or more blank lines with more input after them.
+fn blank_lines(s: &str) -> IResult<&str, &str> {
+    terminated(recognize(pair(newline, many1(newline))), not(end))(s)
+}
+
+/// One `item` per line, like day 14's rock paths, or none if the input's
+/// empty. The newline after the last is left for [`end`].
+pub fn lines0<'a, O>(
+    item: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list0(line_break, item)
+}
+
+/// One `item` per line, and at least one of them.
+pub fn lines1<'a, O>(
+    item: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_break, item)
+}
+
+/// Items that take up several lines, like day 11's monkeys, separated by
+/// one or more blank lines.
+pub fn blocks<'a, O>(
+    item: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(blank_lines, item)
+}
+
+/// One or more items separated by commas, with or without spaces after
+/// them: "1,2,3" or "79, 98".
+pub fn commas<'a, O>(
+    item: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(pair(char(','), space0), item)
+}
+
+/// `value` after "Label:" and any spaces, which the line can be indented by:
+/// "  Starting items: 79, 98".
+pub fn field<'a, O>(
+    label: &'a str,
+    value: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(tuple((space0, tag(label), char(':'), space0)), value)
+}
+
+/// `value` after "name=": "x=-2".
+pub fn assignment<'a, O>(
+    name: &'a str,
+    value: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(pair(tag(name), char('=')), value)
+}
+
+/// The next signed number on the line, skipping whatever text comes before
+/// it: 18 from ", y=18: closest beacon". A `-` right after the number is a
+/// dash rather than the sign of the next one, and goes with this one, so
+/// "2-4" is 2 and then 4, like day 4's ranges. Fails where the line ends if
+/// there's no number left on it.
+pub fn next_number(s: &str) -> IResult<&str, i64> {
+    let starts_number = |at: usize, c: char| {
+        c.is_ascii_digit() || (c == '-' && s[at + 1..].starts_with(|c: char| c.is_ascii_digit()))
+    };
+    let start = s
+        .char_indices()
+        .find(|&(at, c)| c == '\n' || starts_number(at, c))
+        .map_or(s.len(), |(at, _)| at);
+    let (rest, number) = i64(&s[start..])?;
+    match rest.strip_prefix('-') {
+        Some(after) if after.starts_with(|c: char| c.is_ascii_digit()) => Ok((after, number)),
+        _ => Ok((rest, number)),
+    }
+}
+
+/// Every signed number in `s`, in order, ignoring the text around them.
+pub fn numbers(s: &str) -> Vec<i64> {
+    s.lines()
+        .flat_map(|line| {
+            let mut rest = line;
+            std::iter::from_fn(move || {
+                let (after, number) = next_number(rest).ok()?;
+                rest = after;
+                Some(number)
+            })
+        })
+        .collect()
+}
+
+/// Rows of one or more `cell`s, one per line, as wide as each other. Once a
+/// row has started, a character that isn't a cell or a row of a different
+/// width is a failure at that character or the start of the row, rather
+/// than the end of the grid. The grid ends at a blank line or the end of
+/// the input.
+pub fn grid<'a, O>(
+    mut cell: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<O>>> {
+    move |s: &'a str| {
+        let mut rows: Vec<Vec<O>> = Vec::new();
+        let (mut rest, mut next) = (s, s);
+        loop {
+            let (after, row) = match many1(|s| cell.parse(s))(next) {
+                Ok(parsed) => parsed,
+                Err(nom::Err::Error(_)) if !rows.is_empty() => break,
+                Err(e) => return Err(e),
+            };
+            if !after.is_empty() && !after.starts_with('\n') {
+                return Err(nom::Err::Failure(Error::new(after, ErrorKind::Many1)));
+            }
+            if rows.first().is_some_and(|first| first.len() != row.len()) {
+                return Err(nom::Err::Failure(Error::new(next, ErrorKind::Verify)));
+            }
+            rows.push(row);
+            rest = after;
+            match after.strip_prefix('\n') {
+                Some(line) => next = line,
+                None => break,
+            }
+        }
+        Ok((rest, rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::{one_of, u32};
+    use nom::combinator::cut;
+    use nom::sequence::separated_pair;
+
+    fn locate(error: ParseError) -> (usize, usize, String) {
+        (error.line, error.column, error.found)
+    }
+
+    #[test]
+    fn finishes_at_the_end_of_the_input() {
+        assert_eq!(finish("1\n2\n\n", "a number", lines1(u32)), Ok(vec![1, 2]));
+        assert_eq!(finish("", "a number", lines0(u32)), Ok(vec![]));
+        let error = finish("1\n2\nthree\n", "a number", lines1(u32)).unwrap_err();
+        assert_eq!(locate(error), (3, 1, "three".to_string()));
+        assert!(finish("", "a number", lines1(u32)).is_err());
+    }
+
+    #[test]
+    fn splits_blocks_on_blank_lines() {
+        let block = || separated_pair(u32, newline, u32);
+        assert_eq!(
+            finish("1\n2\n\n\n3\n4\n\n", "a block", blocks(block())),
+            Ok(vec![(1, 2), (3, 4)])
+        );
+        // Without a cut, a block that goes wrong ends the list at its start
+        let error = finish("1\n2\n\n3\nx\n", "a block", blocks(block())).unwrap_err();
+        assert_eq!(locate(error), (4, 1, "3".to_string()));
+        let error = finish("1\n2\n\n3\nx\n", "a block", blocks(cut(block()))).unwrap_err();
+        assert_eq!(locate(error), (5, 1, "x".to_string()));
+    }
+
+    #[test]
+    fn reads_fields_and_lists() {
+        let (rest, items) =
+            field("Starting items", commas(u32))("  Starting items: 79, 98").unwrap();
+        assert_eq!((rest, items), ("", vec![79, 98]));
+        assert_eq!(commas(u32)("1,2,3").unwrap(), ("", vec![1, 2, 3]));
+        assert_eq!(commas(u32)("1,x").unwrap(), (",x", vec![1]));
+        assert_eq!(assignment("x", i64)("x=-2, y").unwrap(), (", y", -2));
+    }
+
+    #[test]
+    fn finds_numbers_among_text() {
+        let sensor = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+        assert_eq!(
+            next_number(sensor).unwrap(),
+            (", y=-18: closest beacon is at x=-2, y=15", 2)
+        );
+        assert_eq!(numbers(sensor), [2, -18, -2, 15]);
+        assert_eq!(
+            numbers("a well-made 3-4\nmove 1 from 2 to 3\n"),
+            [3, 4, 1, 2, 3]
+        );
+        assert_eq!(numbers("2-4,6-8\n"), [2, 4, 6, 8]);
+        assert_eq!(numbers("from -3 to 5--2"), [-3, 5, -2]);
+        // The next number has to be on the same line
+        let (rest, _) = next_number("y=1\nx=2").unwrap();
+        assert_eq!(
+            next_number(rest).unwrap_err(),
+            nom::Err::Error(Error::new("\nx=2", ErrorKind::Digit))
+        );
+    }
+
+    #[test]
+    fn parses_grids() {
+        let cell = || one_of("#.");
+        assert_eq!(
+            finish("#.\n.#\n\n", "a grid", grid(cell())),
+            Ok(vec![vec!['#', '.'], vec!['.', '#']])
+        );
+        let (rest, rows) = grid(cell())("##\n##\n\nR10L5\n").unwrap();
+        assert_eq!((rest, rows.len()), ("\n\nR10L5\n", 2));
+
+        let error = finish("#.#\n.#\n#.#\n", "a grid", grid(cell())).unwrap_err();
+        assert_eq!(locate(error), (2, 1, ".#".to_string()));
+        let error = finish("#.#\n.x#\n", "a grid", grid(cell())).unwrap_err();
+        assert_eq!(locate(error), (2, 2, "x#".to_string()));
+    }
+}
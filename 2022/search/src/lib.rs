@@ -0,0 +1,362 @@
+//! Graph searches shared by the path-finding puzzles.
+//!
+//! A graph is anything that can list the neighbours of a node: implement
+//! [`Graph`] (or [`WeightedGraph`] when steps have different costs), or pass
+//! a closure, which implements them both. Searches stop at the first node the
+//! goal predicate accepts, and return a [`Search`] with the cost to every node
+//! they reached and the predecessors to rebuild the path there.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+use tracing::debug;
+
+/// A graph where every step costs the same.
+pub trait Graph<N> {
+    /// The nodes one step away from `node`.
+    fn neighbours(&self, node: &N) -> impl IntoIterator<Item = N>;
+}
+
+/// A graph where each step has its own cost.
+pub trait WeightedGraph<N, C> {
+    /// The nodes one step away from `node`, with the cost of each step.
+    fn edges(&self, node: &N) -> impl IntoIterator<Item = (N, C)>;
+}
+
+impl<N, F, I> Graph<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    fn neighbours(&self, node: &N) -> impl IntoIterator<Item = N> {
+        self(node)
+    }
+}
+
+impl<N, C, F, I> WeightedGraph<N, C> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    fn edges(&self, node: &N) -> impl IntoIterator<Item = (N, C)> {
+        self(node)
+    }
+}
+
+/// A cost that can be summed along a path. `Default` is the cost of not
+/// moving at all.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// What a search found: the cost to each node it reached, the node each was
+/// reached from, and the goal, if it got there.
+///
+/// Costs are exact for every node the search finished with before it
+/// stopped; nodes still waiting on the frontier have the cheapest cost found
+/// so far.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    reached: HashMap<N, Reached<N, C>>,
+    goal: Option<N>,
+}
+
+/// How a search got to a node, and what that cost.
+#[derive(Debug, Clone)]
+struct Reached<N, C> {
+    cost: C,
+    from: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        let start = (
+            start,
+            Reached {
+                cost: zero,
+                from: None,
+            },
+        );
+        Search {
+            reached: HashMap::from([start]),
+            goal: None,
+        }
+    }
+
+    /// Record that `node` can be reached from `from` at `cost`.
+    fn reach(&mut self, node: N, from: &N, cost: C) {
+        let from = Some(from.clone());
+        self.reached.insert(node, Reached { cost, from });
+    }
+
+    /// The node that satisfied the goal.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of reaching the goal.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|goal| self.cost(goal))
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.reached.get(node).map(|reached| reached.cost)
+    }
+
+    /// Every node reached, with its cost.
+    pub fn costs(&self) -> impl Iterator<Item = (&N, C)> {
+        self.reached
+            .iter()
+            .map(|(node, reached)| (node, reached.cost))
+    }
+
+    /// The node `node` was reached from, or `None` for the start.
+    pub fn predecessor(&self, node: &N) -> Option<&N> {
+        self.reached.get(node)?.from.as_ref()
+    }
+
+    /// The path from the start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.reached.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessor(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The path from the start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.as_ref().and_then(|goal| self.path_to(goal))
+    }
+}
+
+/// Breadth-first search from `start` until `goal` accepts a node. The cost
+/// of a node is the number of steps to it.
+pub fn bfs<N, G>(graph: &G, start: N, mut goal: impl FnMut(&N) -> bool) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N> + ?Sized,
+{
+    let mut search = Search::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let steps = search.reached[&node].cost + 1;
+        for next in graph.neighbours(&node) {
+            if search.reached.contains_key(&next) {
+                continue;
+            }
+            search.reach(next.clone(), &node, steps);
+            queue.push_back(next);
+        }
+    }
+
+    search
+}
+
+/// Every node reachable from `start`, in no particular order.
+pub fn flood_fill<N, G>(graph: &G, start: N) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N> + ?Sized,
+{
+    let mut reached = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        for next in graph.neighbours(&node) {
+            if reached.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+
+    reached
+}
+
+/// Dijkstra's algorithm from `start` until `goal` accepts a node. Step costs
+/// must not be negative.
+pub fn dijkstra<N, C, G>(graph: &G, start: N, goal: impl FnMut(&N) -> bool) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    G: WeightedGraph<N, C> + ?Sized,
+{
+    astar(graph, start, |_: &N| C::default(), goal)
+}
+
+/// A* search from `start` until `goal` accepts a node. The `heuristic` must
+/// never overestimate the cost still to go, and must not drop by more than
+/// the cost of a step, for the path it finds to be the cheapest.
+pub fn astar<N, C, G>(
+    graph: &G,
+    start: N,
+    heuristic: impl Fn(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    G: WeightedGraph<N, C> + ?Sized,
+{
+    let mut search = Search::new(start.clone(), C::default());
+    let mut open = BinaryHeap::from([Open {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+    let (mut expanded, mut stale, mut largest_heap) = (0usize, 0usize, 1);
+
+    while let Some(Open { cost, node, .. }) = open.pop() {
+        // Nodes are pushed again whenever a cheaper way to them is found,
+        // which leaves the old entries to skip here.
+        if search.cost(&node).is_some_and(|best| best < cost) {
+            stale += 1;
+            continue;
+        }
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        expanded += 1;
+        for (next, step) in graph.edges(&node) {
+            let next_cost = cost + step;
+            if search.cost(&next).is_some_and(|best| best <= next_cost) {
+                continue;
+            }
+            search.reach(next.clone(), &node, next_cost);
+            open.push(Open {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+        largest_heap = largest_heap.max(open.len());
+    }
+
+    debug!(
+        expanded,
+        stale,
+        largest_heap,
+        left_in_heap = open.len(),
+        reached = search.reached.len(),
+        found_goal = search.goal.is_some(),
+        "search finished"
+    );
+    search
+}
+
+/// A node on the frontier of a weighted search, ordered so the cheapest
+/// comes out of the (max-)heap first.
+struct Open<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Open<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Open<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Open<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Open<N, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 5x5 room with a wall down the middle, open at the bottom.
+    fn room(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .into_iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y))
+            .filter(|&(x, y)| !(x == 2 && y < 4))
+            .collect()
+    }
+
+    #[test]
+    fn bfs_finds_the_way_around_the_wall() {
+        let search = bfs(&room, (0, 0), |&node| node == (4, 0));
+        assert_eq!(search.goal_cost(), Some(12));
+
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(4, 0)));
+        assert!(path.contains(&(2, 4)));
+    }
+
+    #[test]
+    fn bfs_without_a_goal_reaches_everything() {
+        let search = bfs(&room, (0, 0), |_| false);
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.costs().count(), 25 - 4);
+        assert_eq!(search.cost(&(1, 1)), Some(2));
+        assert_eq!(search.cost(&(2, 0)), None);
+    }
+
+    #[test]
+    fn flood_fill_stays_on_its_side_of_a_wall() {
+        let closed = |&(x, y): &(i32, i32)| room(&(x, y)).into_iter().filter(|&(_, y)| y < 4);
+        assert_eq!(flood_fill(&closed, (0, 0)).len(), 8);
+    }
+
+    /// Towns joined by roads of different lengths, the shortcut being the
+    /// long way round.
+    fn roads(&town: &char) -> Vec<(char, u32)> {
+        match town {
+            'a' => vec![('b', 7), ('c', 2)],
+            'b' => vec![('d', 1)],
+            'c' => vec![('b', 3), ('d', 8)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_takes_the_cheapest_path() {
+        let search = dijkstra(&roads, 'a', |&town| town == 'd');
+        assert_eq!(search.goal_cost(), Some(6));
+        assert_eq!(search.path(), Some(vec!['a', 'c', 'b', 'd']));
+        assert_eq!(search.predecessor(&'a'), None);
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let graph = |node: &(i32, i32)| room(node).into_iter().map(|next| (next, 1));
+        let manhattan = |&(x, y): &(i32, i32)| (4 - x).abs() + y.abs();
+        let search = astar(&graph, (0, 0), manhattan, |&node| node == (4, 0));
+        assert_eq!(search.goal_cost(), Some(12));
+        assert_eq!(search.path().map(|path| path.len()), Some(13));
+    }
+
+    #[test]
+    fn unreachable_goals_have_no_path() {
+        let search = dijkstra(&roads, 'd', |&town| town == 'a');
+        assert_eq!(search.goal_cost(), None);
+        assert_eq!(search.path(), None);
+        assert_eq!(search.path_to(&'d'), Some(vec!['d']));
+    }
+}